@@ -1,10 +1,15 @@
-﻿use soroban_sdk::{Env, String, Vec};
+﻿use soroban_sdk::{Address, Env, String, Vec};
 
 use crate::analytics::types::{
     BudgetUtilization, CategoryBreakdown, SpendingForecast, SpendingSummary, SpendingTrend,
+    TokenBalanceReport,
+};
+use crate::treasury::storage::{
+    get_committed, get_treasury, get_treasury_transactions, get_vault_allocations,
+    list_budgets_for_treasury,
 };
-use crate::treasury::storage::{get_treasury_transactions, list_budgets_for_treasury};
 use crate::treasury::types::{Transaction, TransactionStatus, TransactionType};
+use crate::treasury::vault::position_value;
 
 /// Compute an aggregated spending summary for a treasury within [period_start, period_end].
 pub fn compute_spending_summary(
@@ -26,6 +31,10 @@ pub fn compute_spending_summary(
         if tx.created_at < period_start || tx.created_at > period_end {
             continue;
         }
+        // a batch's legs are recorded as their own withdrawals
+        if tx.tx_type == TransactionType::BatchWithdrawal {
+            continue;
+        }
 
         tx_count += 1;
         match tx.tx_type {
@@ -37,7 +46,13 @@ pub fn compute_spending_summary(
             | TransactionType::MilestonePayment => {
                 total_withdrawals += tx.amount;
             }
-            TransactionType::AllowanceGrant => {}
+            // swaps and vault moves shift value between holdings, not in or out
+            TransactionType::AllowanceGrant
+            | TransactionType::BatchWithdrawal
+            | TransactionType::Swap
+            | TransactionType::VaultDeposit
+            | TransactionType::VaultWithdrawal
+            | TransactionType::SwapperChange => {}
         }
     }
 
@@ -66,15 +81,17 @@ pub fn compute_budget_utilization(env: &Env, treasury_id: u64) -> Vec<BudgetUtil
     let mut result = Vec::new(env);
 
     for budget in budgets.iter() {
-        let remaining = if budget.allocated_amount > budget.spent_amount {
-            budget.allocated_amount - budget.spent_amount
+        // allocation carried over from the previous period is spendable too
+        let allocated = budget.allocated_amount + budget.carried_over;
+        let remaining = if allocated > budget.spent_amount {
+            allocated - budget.spent_amount
         } else {
             0
         };
 
-        let utilization_bps: u32 = if budget.allocated_amount > 0 {
+        let utilization_bps: u32 = if allocated > 0 {
             // (spent * 10000) / allocated â€” safe since allocated > 0
-            let bps = (budget.spent_amount * 10000) / budget.allocated_amount;
+            let bps = (budget.spent_amount * 10000) / allocated;
             // Cap at 10000 (100%)
             if bps > 10000 {
                 10000u32
@@ -87,7 +104,7 @@ pub fn compute_budget_utilization(env: &Env, treasury_id: u64) -> Vec<BudgetUtil
 
         result.push_back(BudgetUtilization {
             category: budget.category.clone(),
-            allocated: budget.allocated_amount,
+            allocated,
             spent: budget.spent_amount,
             remaining,
             utilization_bps,
@@ -116,6 +133,12 @@ pub fn compute_category_breakdown(
     let mut milestone_count: u32 = 0;
     let mut allowance_amount: i128 = 0;
     let mut allowance_count: u32 = 0;
+    let mut swap_amount: i128 = 0;
+    let mut swap_count: u32 = 0;
+    let mut vault_deposit_amount: i128 = 0;
+    let mut vault_deposit_count: u32 = 0;
+    let mut vault_withdrawal_amount: i128 = 0;
+    let mut vault_withdrawal_count: u32 = 0;
 
     for tx in txs.iter() {
         if !is_executed(&tx) {
@@ -145,6 +168,22 @@ pub fn compute_category_breakdown(
                 allowance_amount += tx.amount;
                 allowance_count += 1;
             }
+            TransactionType::Swap => {
+                swap_amount += tx.amount;
+                swap_count += 1;
+            }
+            TransactionType::VaultDeposit => {
+                vault_deposit_amount += tx.amount;
+                vault_deposit_count += 1;
+            }
+            TransactionType::VaultWithdrawal => {
+                vault_withdrawal_amount += tx.amount;
+                vault_withdrawal_count += 1;
+            }
+            // counted through the withdrawal recorded for each leg
+            TransactionType::BatchWithdrawal => {}
+            // moves no funds
+            TransactionType::SwapperChange => {}
         }
     }
 
@@ -185,6 +224,69 @@ pub fn compute_category_breakdown(
             tx_count: allowance_count,
         });
     }
+    if swap_count > 0 {
+        result.push_back(CategoryBreakdown {
+            category_name: String::from_str(env, "Swap"),
+            total_amount: swap_amount,
+            tx_count: swap_count,
+        });
+    }
+    if vault_deposit_count > 0 {
+        result.push_back(CategoryBreakdown {
+            category_name: String::from_str(env, "VaultDeposit"),
+            total_amount: vault_deposit_amount,
+            tx_count: vault_deposit_count,
+        });
+    }
+    if vault_withdrawal_count > 0 {
+        result.push_back(CategoryBreakdown {
+            category_name: String::from_str(env, "VaultWithdrawal"),
+            total_amount: vault_withdrawal_amount,
+            tx_count: vault_withdrawal_count,
+        });
+    }
+
+    result
+}
+
+/// Report liquid and vault-deployed holdings for XLM and every token the
+/// treasury holds or has deployed.
+pub fn compute_balance_report(env: &Env, treasury_id: u64) -> Vec<TokenBalanceReport> {
+    let treasury = get_treasury(env, treasury_id).expect("treasury not found");
+    let allocations = get_vault_allocations(env, treasury_id);
+
+    let mut tokens: Vec<Address> = treasury.token_balances.keys();
+    for allocation in allocations.values().iter() {
+        if !tokens.contains(&allocation.token) {
+            tokens.push_back(allocation.token);
+        }
+    }
+
+    let mut result = Vec::new(env);
+    result.push_back(TokenBalanceReport {
+        token: None,
+        liquid: treasury.balance_xlm,
+        committed: get_committed(env, treasury_id, &None),
+        deployed: 0,
+        deployed_value: 0,
+    });
+    for token in tokens.iter() {
+        let mut deployed: i128 = 0;
+        let mut deployed_value: i128 = 0;
+        for allocation in allocations.values().iter() {
+            if allocation.token == token {
+                deployed += allocation.principal;
+                deployed_value += position_value(env, &allocation);
+            }
+        }
+        result.push_back(TokenBalanceReport {
+            liquid: treasury.token_balances.get(token.clone()).unwrap_or(0),
+            committed: get_committed(env, treasury_id, &Some(token.clone())),
+            token: Some(token),
+            deployed,
+            deployed_value,
+        });
+    }
 
     result
 }
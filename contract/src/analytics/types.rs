@@ -1,4 +1,4 @@
-﻿use soroban_sdk::{contracttype, String};
+﻿use soroban_sdk::{contracttype, Address, String};
 
 /// Reporting period options for analytics queries
 #[contracttype]
@@ -57,6 +57,19 @@ pub struct TreasurySnapshot {
     pub snapshot_index: u32,
 }
 
+/// Liquid and vault-deployed holdings of one token (`None` for XLM).
+/// `deployed` is principal at cost; `deployed_value` includes accrued yield.
+/// `committed` is the part of `liquid` reserved for milestone projects.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenBalanceReport {
+    pub token: Option<Address>,
+    pub liquid: i128,
+    pub committed: i128,
+    pub deployed: i128,
+    pub deployed_value: i128,
+}
+
 /// Trend comparison between two periods (values in basis points, e.g. 1500 = +15%)
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
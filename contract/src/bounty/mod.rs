@@ -16,6 +16,7 @@
 /// | Submit work         | `(bounty, submitted)`    | `WorkSubmittedEvent`     |
 /// | Approve bounty      | `(bounty, approved)`     | `BountyApprovedEvent`    |
 /// | Release escrow      | `(bounty, released)`     | `EscrowReleasedEvent`    |
+/// | Set conversion      | `(bounty, updated)`      | `PayoutConversionUpdatedEvent` |
 /// | Cancel bounty       | `(bounty, cancelled)`    | `BountyCancelledEvent`   |
 /// | Expire bounty       | `(bounty, expired)`      | `BountyExpiredEvent`     |
 pub mod escrow;
@@ -23,20 +24,26 @@ pub mod storage;
 pub mod types;
 
 use crate::bounty::escrow::{lock_funds, release_funds};
-use crate::bounty::storage::{get_bounty, get_guild_bounties, get_next_bounty_id, store_bounty};
+use crate::bounty::storage::{
+    get_bounty, get_guild_bounties, get_next_bounty_id, get_payout_conversion,
+    set_payout_conversion, store_bounty,
+};
 use crate::bounty::types::{
     BountyApprovedEvent, BountyCancelledEvent, BountyClaimedEvent, BountyCreatedEvent,
-    BountyExpiredEvent, BountyFundedEvent, EscrowReleasedEvent, WorkSubmittedEvent,
+    BountyExpiredEvent, BountyFundedEvent, EscrowReleasedEvent, PayoutConversionUpdatedEvent,
+    WorkSubmittedEvent,
 };
 use crate::dispute::storage as dispute_storage;
 use crate::dispute::types::DisputeReference;
 use crate::events::emit::emit_event;
 use crate::events::topics::{
     ACT_APPROVED, ACT_CANCELLED, ACT_CLAIMED, ACT_CREATED, ACT_EXPIRED, ACT_FUNDED, ACT_RELEASED,
-    ACT_SUBMITTED, MOD_BOUNTY,
+    ACT_SUBMITTED, ACT_UPDATED, MOD_BOUNTY,
 };
 use crate::guild::membership::has_permission;
 use crate::guild::types::Role;
+use crate::interfaces::swapper::{convert_payout, validate_conversion, PayoutConversion};
+use crate::treasury::storage::get_treasury;
 use soroban_sdk::{Address, Env, String, Vec};
 
 pub use types::{Bounty, BountyStatus, PayoutSplit};
@@ -413,8 +420,15 @@ pub fn release_escrow(env: &Env, bounty_id: u64) -> bool {
     let claimer = bounty.claimer.clone().expect("No claimer for this bounty");
 
     if bounty.funded_amount > 0 {
-        let amount = bounty.funded_amount;
-        release_funds(env, &bounty.token, &claimer, amount);
+        // Pay in the claimer's desired asset when a conversion is configured
+        let (amount, token) = match get_payout_conversion(env, bounty_id) {
+            Some(conversion) => {
+                let amount = convert_payout(env, &conversion, bounty.funded_amount);
+                (amount, conversion.target_token)
+            }
+            None => (bounty.funded_amount, bounty.token.clone()),
+        };
+        release_funds(env, &token, &claimer, amount);
         bounty.funded_amount = 0;
         store_bounty(env, &bounty);
 
@@ -426,7 +440,7 @@ pub fn release_escrow(env: &Env, bounty_id: u64) -> bool {
                 bounty_id,
                 recipient: claimer,
                 amount,
-                token: bounty.token,
+                token,
             },
         );
     }
@@ -434,6 +448,63 @@ pub fn release_escrow(env: &Env, bounty_id: u64) -> bool {
     true
 }
 
+/// Convert the escrowed reward into another asset when it is released.
+///
+/// The source asset must be the bounty's escrowed token, and the swap runs
+/// through the swapper approved by one of the guild's treasuries. Only the
+/// creator or a guild admin may set or clear (`None`) the conversion.
+///
+/// # Events emitted
+/// - `(bounty, updated)` â†’ `PayoutConversionUpdatedEvent`
+pub fn set_bounty_payout_conversion(
+    env: &Env,
+    bounty_id: u64,
+    caller: Address,
+    conversion: Option<PayoutConversion>,
+) -> bool {
+    caller.require_auth();
+
+    let bounty = get_bounty(env, bounty_id).expect("Bounty not found");
+
+    let is_creator = bounty.creator == caller;
+    let is_admin = has_permission(env, bounty.guild_id, caller, Role::Admin);
+    if !is_creator && !is_admin {
+        panic!("Unauthorized: Only creator or guild admin can set conversion");
+    }
+    if matches!(
+        bounty.status,
+        BountyStatus::Cancelled | BountyStatus::Expired
+    ) {
+        panic!("Bounty is closed");
+    }
+
+    if let Some(ref c) = conversion {
+        validate_conversion(c);
+        if c.source_token != bounty.token {
+            panic!("Conversion source must be the bounty token");
+        }
+        let treasury = get_treasury(env, c.treasury_id).expect("Treasury not found");
+        if treasury.guild_id != bounty.guild_id {
+            panic!("Conversion treasury must belong to the bounty's guild");
+        }
+    }
+
+    set_payout_conversion(env, bounty_id, &conversion);
+
+    emit_event(
+        env,
+        MOD_BOUNTY,
+        ACT_UPDATED,
+        PayoutConversionUpdatedEvent {
+            bounty_id,
+            target_token: conversion.as_ref().map(|c| c.target_token.clone()),
+            min_rate: conversion.map(|c| c.min_rate).unwrap_or(0),
+        },
+    );
+
+    true
+}
+
 /// Cancel a bounty and refund escrowed funds to the creator
 ///
 /// # Events emitted
@@ -1,10 +1,12 @@
 ﻿use crate::bounty::types::Bounty;
+use crate::interfaces::swapper::PayoutConversion;
 use soroban_sdk::{symbol_short, Env, Map, Symbol, Vec};
 
 // Storage keys
 const BOUNTIES_KEY: Symbol = symbol_short!("bounties");
 const BOUNTY_CNT_KEY: Symbol = symbol_short!("b_cnt");
 const GUILD_BOUNTIES_KEY: Symbol = symbol_short!("g_bnties");
+const CONVERSIONS_KEY: Symbol = symbol_short!("b_conv");
 
 /// Initialize bounty storage
 #[allow(dead_code)]
@@ -98,3 +100,33 @@ pub fn get_guild_bounties(env: &Env, guild_id: u64) -> Vec<Bounty> {
     }
     result
 }
+
+/// Set or clear the payout conversion for a bounty
+pub fn set_payout_conversion(env: &Env, bounty_id: u64, conversion: &Option<PayoutConversion>) {
+    let mut conversions: Map<u64, PayoutConversion> = env
+        .storage()
+        .persistent()
+        .get(&CONVERSIONS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    match conversion {
+        Some(c) => conversions.set(bounty_id, c.clone()),
+        None => {
+            conversions.remove(bounty_id);
+        }
+    }
+    env.storage()
+        .persistent()
+        .set(&CONVERSIONS_KEY, &conversions);
+}
+
+/// Get the payout conversion for a bounty, if any
+pub fn get_payout_conversion(env: &Env, bounty_id: u64) -> Option<PayoutConversion> {
+    let conversions: Map<u64, PayoutConversion> = env
+        .storage()
+        .persistent()
+        .get(&CONVERSIONS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    conversions.get(bounty_id)
+}
@@ -8,6 +8,8 @@
 
 use crate::bounty::types::{BountyStatus, PayoutSplit};
 use crate::guild::types::Role;
+use crate::interfaces::mocks::{MockSwapper, MockSwapperClient};
+use crate::interfaces::swapper::{PayoutConversion, RATE_SCALE};
 use crate::InitializerProof;
 use crate::StellarGuildsContract;
 use crate::StellarGuildsContractClient;
@@ -971,6 +973,177 @@ fn test_release_escrow_success() {
     assert_eq!(claimer_balance, 100);
 }
 
+/// A guild treasury whose signers have approved a swapper paying this contract.
+fn setup_guild_swapper(
+    env: &Env,
+    client: &StellarGuildsContractClient<'_>,
+    contract_id: &Address,
+    guild_id: u64,
+    owner: &Address,
+) -> (u64, Address) {
+    let signer = Address::generate(env);
+    let mut signers = soroban_sdk::Vec::new(env);
+    signers.push_back(owner.clone());
+    signers.push_back(signer.clone());
+    let treasury_id = client.initialize_treasury(&guild_id, &signers, &1u32);
+
+    let swapper = env.register_contract(None, MockSwapper);
+    MockSwapperClient::new(env, &swapper).init(contract_id);
+    let tx_id = client.propose_swapper_change(
+        &treasury_id,
+        owner,
+        &swapper,
+        &String::from_str(env, "swapper"),
+    );
+    client.approve_transaction(&tx_id, &signer);
+    client.execute_transaction(&tx_id, owner);
+
+    (treasury_id, swapper)
+}
+
+fn setup_converted_bounty(
+    env: &Env,
+    client: &StellarGuildsContractClient<'_>,
+    contract_id: &Address,
+    owner: &Address,
+    claimer: &Address,
+    token: &Address,
+    payout_token: &Address,
+) -> (u64, Address) {
+    let funder = Address::generate(env);
+    let guild_id = setup_guild(client, env, owner);
+    let (treasury_id, swapper) = setup_guild_swapper(env, client, contract_id, guild_id, owner);
+    mint_tokens(env, token, &funder, 1000);
+    mint_tokens(env, payout_token, &swapper, 1000);
+
+    let bounty_id = client.create_bounty(
+        &guild_id,
+        owner,
+        &String::from_str(env, "Task"),
+        &String::from_str(env, "Description"),
+        &100i128,
+        token,
+        &2000u64,
+    );
+    client.fund_bounty(&bounty_id, &funder, &100i128);
+    client.approve_bounty(&bounty_id, owner, claimer);
+    client.claim_bounty(&bounty_id, claimer);
+    client.submit_work(
+        &bounty_id,
+        &String::from_str(env, "https://github.com/pr/123"),
+    );
+    client.approve_completion(&bounty_id, owner);
+
+    client.set_bounty_payout_conversion(
+        &bounty_id,
+        owner,
+        &Some(PayoutConversion {
+            source_token: token.clone(),
+            target_token: payout_token.clone(),
+            treasury_id,
+            min_rate: RATE_SCALE * 99 / 100,
+        }),
+    );
+
+    (bounty_id, swapper)
+}
+
+#[test]
+fn test_release_escrow_converts_to_claimer_asset() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+    let payout_token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let (bounty_id, swapper) = setup_converted_bounty(
+        &env,
+        &client,
+        &contract_id,
+        &owner,
+        &claimer,
+        &token,
+        &payout_token,
+    );
+
+    assert!(client.release_escrow(&bounty_id));
+    assert_eq!(get_token_balance(&env, &payout_token, &claimer), 100);
+    assert_eq!(get_token_balance(&env, &token, &claimer), 0);
+    assert_eq!(get_token_balance(&env, &token, &swapper), 100);
+    assert_eq!(get_token_balance(&env, &payout_token, &swapper), 900);
+}
+
+#[test]
+#[should_panic(expected = "slippage exceeded")]
+fn test_release_escrow_rejects_conversion_below_minimum_rate() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+    let payout_token = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let (bounty_id, swapper) = setup_converted_bounty(
+        &env,
+        &client,
+        &contract_id,
+        &owner,
+        &claimer,
+        &token,
+        &payout_token,
+    );
+
+    // Reports 100 out but delivers 98, below the 99% minimum rate
+    MockSwapperClient::new(&env, &swapper).set_shortfall(&2);
+    client.release_escrow(&bounty_id);
+}
+
+#[test]
+#[should_panic(expected = "Conversion source must be the bounty token")]
+fn test_bounty_conversion_source_must_match_escrow_token() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let token = create_mock_token(&env, &owner);
+    let other = create_mock_token(&env, &owner);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let guild_id = setup_guild(&client, &env, &owner);
+
+    let bounty_id = client.create_bounty(
+        &guild_id,
+        &owner,
+        &String::from_str(&env, "Task"),
+        &String::from_str(&env, "Description"),
+        &100i128,
+        &token,
+        &2000u64,
+    );
+
+    client.set_bounty_payout_conversion(
+        &bounty_id,
+        &owner,
+        &Some(PayoutConversion {
+            source_token: other,
+            target_token: token,
+            treasury_id: 1,
+            min_rate: RATE_SCALE,
+        }),
+    );
+}
+
 #[test]
 #[should_panic(expected = "Bounty is not completed")]
 fn test_release_escrow_not_completed_fails() {
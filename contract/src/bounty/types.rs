@@ -124,6 +124,16 @@ pub struct EscrowReleasedEvent {
     pub token: Address,
 }
 
+/// Event emitted when the payout conversion of a bounty changes
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PayoutConversionUpdatedEvent {
+    pub bounty_id: u64,
+    /// `None` when the conversion was cleared
+    pub target_token: Option<Address>,
+    pub min_rate: i128,
+}
+
 /// Event emitted when a bounty is cancelled
 #[contracttype]
 #[derive(Clone, Debug)]
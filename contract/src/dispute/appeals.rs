@@ -0,0 +1,165 @@
+use soroban_sdk::{Address, Env, Vec};
+
+use super::VOTING_PERIOD_SECONDS;
+use crate::bounty::escrow::{lock_funds, release_funds};
+use crate::dispute::jurors::draw_panel;
+use crate::dispute::storage;
+use crate::dispute::types::{
+    AppealBond, AppealConfig, AppealFiledEvent, Dispute, DisputeConfigUpdatedEvent, DisputeRound,
+    DisputeStatus, VoteDecision,
+};
+use crate::events::emit::emit_event;
+use crate::events::topics::{ACT_APPEALED, ACT_UPDATED, MOD_DISPUTE};
+use crate::guild::storage as guild_storage;
+
+const MAX_APPEAL_ROUNDS: u32 = 5;
+
+/// Enable or update appeals for a guild. Only the guild owner may call.
+pub fn set_appeal_config(env: &Env, guild_id: u64, caller: Address, config: AppealConfig) -> bool {
+    caller.require_auth();
+
+    let guild =
+        guild_storage::get_guild(env, guild_id).unwrap_or_else(|| panic!("guild not found"));
+    if caller != guild.owner {
+        panic!("only guild owner can configure appeals");
+    }
+
+    if config.max_rounds < 1 || config.max_rounds > MAX_APPEAL_ROUNDS {
+        panic!("invalid max rounds");
+    }
+    if config.base_bond <= 0 {
+        panic!("appeal bond must be positive");
+    }
+    if config.appeal_window_seconds == 0 {
+        panic!("invalid appeal window");
+    }
+
+    storage::set_appeal_config(env, guild_id, &config);
+
+    let event = DisputeConfigUpdatedEvent { guild_id };
+    emit_event(env, MOD_DISPUTE, ACT_UPDATED, event);
+
+    true
+}
+
+/// Whether a ruled dispute may still move to another round.
+pub fn can_appeal(env: &Env, dispute: &Dispute) -> bool {
+    match storage::get_appeal_config(env, dispute.guild_id) {
+        Some(config) => dispute.round + 1 < config.max_rounds,
+        None => false,
+    }
+}
+
+/// Bond required to appeal out of `round`: the base bond doubled per round.
+pub fn bond_for_round(config: &AppealConfig, round: u32) -> i128 {
+    config
+        .base_bond
+        .checked_mul(1i128 << round)
+        .expect("overflow")
+}
+
+/// Appeal a ruling during its appeal window.
+///
+/// Only the losing party may appeal (either party after a split). The
+/// appellant posts a bond, the current round's votes are archived, and a new
+/// voting round opens with a larger juror panel or higher member quorum.
+pub fn appeal_dispute(env: &Env, dispute_id: u64, appellant: Address) -> u32 {
+    appellant.require_auth();
+
+    let mut dispute = storage::get_dispute(env, dispute_id).expect("dispute not found");
+    if dispute.status != DisputeStatus::Appealable {
+        panic!("dispute not appealable");
+    }
+
+    let now = env.ledger().timestamp();
+    if now > dispute.appeal_deadline.unwrap_or(0) {
+        panic!("appeal window closed");
+    }
+
+    let config = storage::get_appeal_config(env, dispute.guild_id)
+        .unwrap_or_else(|| panic!("appeals disabled"));
+
+    let decision = super::resolution::decide_winner(
+        dispute.votes_for_plaintiff,
+        dispute.votes_for_defendant,
+        dispute.votes_split,
+    );
+    let may_appeal = match decision {
+        VoteDecision::FavorPlaintiff => appellant == dispute.defendant,
+        VoteDecision::FavorDefendant => appellant == dispute.plaintiff,
+        VoteDecision::Split => appellant == dispute.plaintiff || appellant == dispute.defendant,
+    };
+    if !may_appeal {
+        panic!("only the losing party can appeal");
+    }
+
+    let bond = bond_for_round(&config, dispute.round);
+    lock_funds(env, &config.bond_token, &appellant, bond);
+    storage::push_bond(
+        env,
+        dispute_id,
+        &AppealBond {
+            round: dispute.round,
+            appellant: appellant.clone(),
+            token: config.bond_token.clone(),
+            amount: bond,
+        },
+    );
+
+    storage::push_round(
+        env,
+        dispute_id,
+        &DisputeRound {
+            round: dispute.round,
+            panel: storage::get_panel(env, dispute_id).unwrap_or_else(|| Vec::new(env)),
+            votes: storage::get_votes(env, dispute_id),
+            votes_for_plaintiff: dispute.votes_for_plaintiff,
+            votes_for_defendant: dispute.votes_for_defendant,
+            votes_split: dispute.votes_split,
+            vote_count: dispute.vote_count,
+        },
+    );
+    storage::clear_votes(env, dispute_id);
+
+    dispute.round += 1;
+    dispute.status = DisputeStatus::Open;
+    dispute.voting_deadline = now + VOTING_PERIOD_SECONDS;
+    dispute.appeal_deadline = None;
+    dispute.resolved_at = None;
+    dispute.votes_for_plaintiff = 0;
+    dispute.votes_for_defendant = 0;
+    dispute.votes_split = 0;
+    dispute.vote_count = 0;
+    storage::store_dispute(env, &dispute);
+
+    draw_panel(env, &dispute);
+
+    let event = AppealFiledEvent {
+        dispute_id,
+        round: dispute.round,
+        appellant,
+        bond,
+    };
+    emit_event(env, MOD_DISPUTE, ACT_APPEALED, event);
+
+    dispute.round
+}
+
+/// Return or forfeit appeal bonds once the final ruling is known.
+///
+/// A bond is refunded when the final ruling favours its appellant or is a
+/// split; otherwise it is paid to the opposing party.
+pub fn settle_bonds(env: &Env, dispute: &Dispute, decision: &VoteDecision) {
+    for bond in storage::get_bonds(env, dispute.id).iter() {
+        let recipient = match decision {
+            VoteDecision::FavorPlaintiff if bond.appellant == dispute.defendant => {
+                dispute.plaintiff.clone()
+            }
+            VoteDecision::FavorDefendant if bond.appellant == dispute.plaintiff => {
+                dispute.defendant.clone()
+            }
+            _ => bond.appellant.clone(),
+        };
+        release_funds(env, &bond.token, &recipient, bond.amount);
+    }
+}
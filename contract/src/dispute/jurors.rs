@@ -0,0 +1,262 @@
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::bounty::escrow::{lock_funds, release_funds};
+use crate::dispute::storage;
+use crate::dispute::types::{
+    Dispute, DisputeConfigUpdatedEvent, Juror, JurorConfig, JurorSettledEvent,
+    JurorStakeChangedEvent, PanelDrawnEvent, VoteDecision,
+};
+use crate::events::emit::emit_event;
+use crate::events::topics::{
+    ACT_PANEL_DRAWN, ACT_SETTLED, ACT_STAKED, ACT_UNSTAKED, ACT_UPDATED, MOD_DISPUTE,
+};
+use crate::guild::storage as guild_storage;
+
+const MAX_PANEL_SIZE: u32 = 25;
+const BPS_DENOMINATOR: i128 = 10_000;
+
+/// Enable or update juror-pool mode for a guild. Only the guild owner may call.
+pub fn set_juror_config(env: &Env, guild_id: u64, caller: Address, config: JurorConfig) -> bool {
+    caller.require_auth();
+
+    let guild =
+        guild_storage::get_guild(env, guild_id).unwrap_or_else(|| panic!("guild not found"));
+    if caller != guild.owner {
+        panic!("only guild owner can configure jurors");
+    }
+
+    if config.panel_size == 0 || config.panel_size > MAX_PANEL_SIZE {
+        panic!("invalid panel size");
+    }
+    if config.min_stake <= 0 {
+        panic!("minimum stake must be positive");
+    }
+    if config.slash_bps as i128 > BPS_DENOMINATOR {
+        panic!("invalid slash rate");
+    }
+    if let Some(existing) = storage::get_juror_config(env, guild_id) {
+        if existing.stake_token != config.stake_token {
+            panic!("stake token cannot change");
+        }
+    }
+
+    storage::set_juror_config(env, guild_id, &config);
+
+    let event = DisputeConfigUpdatedEvent { guild_id };
+    emit_event(env, MOD_DISPUTE, ACT_UPDATED, event);
+
+    true
+}
+
+/// Stake tokens to join (or top up a position in) a guild's juror pool.
+pub fn stake(env: &Env, guild_id: u64, juror: Address, amount: i128) -> i128 {
+    juror.require_auth();
+
+    if amount <= 0 {
+        panic!("stake amount must be positive");
+    }
+
+    let config =
+        storage::get_juror_config(env, guild_id).unwrap_or_else(|| panic!("juror mode disabled"));
+
+    if guild_storage::get_member(env, guild_id, &juror).is_none() {
+        panic!("juror must be guild member");
+    }
+
+    let mut record = storage::get_juror(env, guild_id, &juror).unwrap_or(Juror {
+        address: juror.clone(),
+        guild_id,
+        stake: 0,
+        active_panels: 0,
+    });
+
+    lock_funds(env, &config.stake_token, &juror, amount);
+    record.stake = record.stake.checked_add(amount).expect("overflow");
+    storage::store_juror(env, &record);
+
+    let event = JurorStakeChangedEvent {
+        guild_id,
+        juror,
+        stake: record.stake,
+    };
+    emit_event(env, MOD_DISPUTE, ACT_STAKED, event);
+
+    record.stake
+}
+
+/// Withdraw stake from the juror pool. Jurors sitting on an unresolved panel
+/// cannot withdraw until that dispute is settled.
+pub fn unstake(env: &Env, guild_id: u64, juror: Address, amount: i128) -> i128 {
+    juror.require_auth();
+
+    if amount <= 0 {
+        panic!("unstake amount must be positive");
+    }
+
+    let config =
+        storage::get_juror_config(env, guild_id).unwrap_or_else(|| panic!("juror mode disabled"));
+    let mut record =
+        storage::get_juror(env, guild_id, &juror).unwrap_or_else(|| panic!("juror not found"));
+
+    if record.active_panels > 0 {
+        panic!("juror has active panels");
+    }
+    if amount > record.stake {
+        panic!("insufficient stake");
+    }
+
+    record.stake -= amount;
+    storage::store_juror(env, &record);
+    release_funds(env, &config.stake_token, &juror, amount);
+
+    let event = JurorStakeChangedEvent {
+        guild_id,
+        juror,
+        stake: record.stake,
+    };
+    emit_event(env, MOD_DISPUTE, ACT_UNSTAKED, event);
+
+    record.stake
+}
+
+/// Panel size for a dispute round: `panel_size` for the first ruling, then
+/// `2n + 1` jurors for every appeal.
+pub fn panel_size_for_round(config: &JurorConfig, round: u32) -> u32 {
+    let mut size = config.panel_size;
+    for _ in 0..round {
+        size = size.saturating_mul(2).saturating_add(1);
+    }
+    size
+}
+
+/// Draw a juror panel for a dispute round using the ledger PRNG.
+///
+/// Eligible jurors are current guild members with at least `min_stake`
+/// staked, excluding both parties. Does nothing if juror mode is disabled.
+/// If too few jurors are eligible the round falls back to member voting
+/// rather than blocking the dispute.
+pub fn draw_panel(env: &Env, dispute: &Dispute) {
+    let config = match storage::get_juror_config(env, dispute.guild_id) {
+        Some(c) => c,
+        None => return,
+    };
+
+    let mut eligible: Vec<Address> = Vec::new(env);
+    for address in storage::get_juror_pool(env, dispute.guild_id).iter() {
+        if address == dispute.plaintiff || address == dispute.defendant {
+            continue;
+        }
+        if guild_storage::get_member(env, dispute.guild_id, &address).is_none() {
+            continue;
+        }
+        if let Some(juror) = storage::get_juror(env, dispute.guild_id, &address) {
+            if juror.stake >= config.min_stake {
+                eligible.push_back(address);
+            }
+        }
+    }
+
+    let panel_size = panel_size_for_round(&config, dispute.round);
+    if eligible.len() < panel_size {
+        storage::remove_panel(env, dispute.id);
+        return;
+    }
+
+    env.prng().shuffle(&mut eligible);
+
+    let mut panel: Vec<Address> = Vec::new(env);
+    for address in eligible.iter().take(panel_size as usize) {
+        let mut juror = storage::get_juror(env, dispute.guild_id, &address).unwrap();
+        juror.active_panels = juror.active_panels.saturating_add(1);
+        storage::store_juror(env, &juror);
+        panel.push_back(address);
+    }
+
+    storage::store_panel(env, dispute.id, &panel);
+
+    let event = PanelDrawnEvent {
+        dispute_id: dispute.id,
+        jurors: panel,
+    };
+    emit_event(env, MOD_DISPUTE, ACT_PANEL_DRAWN, event);
+}
+
+/// Settle stakes for a dispute's panel once it closes.
+///
+/// Jurors who did not vote, or voted against `majority`, lose `slash_bps` of
+/// their stake. The slashed total is shared equally among jurors who voted
+/// with the majority as their fee. When `majority` is `None` (no quorum),
+/// every juror who voted is rewarded. If nobody qualifies for a fee, no one
+/// is slashed.
+pub fn settle_panel(env: &Env, dispute: &Dispute, majority: Option<VoteDecision>) {
+    let panel = match storage::get_panel(env, dispute.id) {
+        Some(p) => p,
+        None => return,
+    };
+    let config = match storage::get_juror_config(env, dispute.guild_id) {
+        Some(c) => c,
+        None => return,
+    };
+
+    let mut winners: Vec<Address> = Vec::new(env);
+    let mut losers: Vec<Address> = Vec::new(env);
+    for address in panel.iter() {
+        let aligned = match storage::get_vote(env, dispute.id, &address) {
+            Some(vote) => match &majority {
+                Some(decision) => vote.decision == *decision,
+                None => true,
+            },
+            None => false,
+        };
+        if aligned {
+            winners.push_back(address);
+        } else {
+            losers.push_back(address);
+        }
+    }
+
+    let mut slashed_total: i128 = 0;
+    for address in panel.iter() {
+        let mut juror = storage::get_juror(env, dispute.guild_id, &address).unwrap();
+        juror.active_panels = juror.active_panels.saturating_sub(1);
+
+        let mut slashed = 0;
+        if !winners.is_empty() && losers.contains(&address) {
+            slashed = juror.stake * (config.slash_bps as i128) / BPS_DENOMINATOR;
+            juror.stake -= slashed;
+            slashed_total += slashed;
+        }
+        storage::store_juror(env, &juror);
+
+        if slashed > 0 {
+            let event = JurorSettledEvent {
+                dispute_id: dispute.id,
+                juror: address,
+                reward: 0,
+                slashed,
+            };
+            emit_event(env, MOD_DISPUTE, ACT_SETTLED, event);
+        }
+    }
+
+    if winners.is_empty() {
+        return;
+    }
+
+    let share = slashed_total / winners.len() as i128;
+    let dust = slashed_total - share * winners.len() as i128;
+    for (i, address) in winners.iter().enumerate() {
+        let reward = if i == 0 { share + dust } else { share };
+        if reward > 0 {
+            release_funds(env, &config.stake_token, &address, reward);
+        }
+
+        let event = JurorSettledEvent {
+            dispute_id: dispute.id,
+            juror: address,
+            reward,
+            slashed: 0,
+        };
+        emit_event(env, MOD_DISPUTE, ACT_SETTLED, event);
+    }
+}
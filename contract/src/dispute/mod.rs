@@ -1,8 +1,14 @@
 ﻿//! Dispute Resolution Module
 //!
 //! Provides dispute creation, evidence submission, voting, and resolution
-//! for bounties and milestones with weighted guild voting.
+//! for bounties, milestones, subscription charges, payment pools and
+//! treasury transactions with weighted guild voting, or with a
+//! randomly drawn panel of staked jurors when juror mode is enabled.
+//! Guilds may also allow bonded appeals that re-open a dispute for further
+//! rounds before its ruling becomes final.
 
+pub mod appeals;
+pub mod jurors;
 pub mod resolution;
 pub mod storage;
 pub mod types;
@@ -15,12 +21,20 @@ use crate::bounty::types::BountyStatus;
 use crate::dispute::resolution as dispute_resolution;
 use crate::dispute::storage as dispute_storage;
 use crate::dispute::types::{
-    Dispute, DisputeCreatedEvent, DisputeReference, DisputeStatus, EvidenceSubmittedEvent,
+    AppealConfig, Dispute, DisputeCreatedEvent, DisputeReference, DisputeRound, DisputeStatus,
+    EvidenceSubmittedEvent, Juror, JurorConfig,
 };
 use crate::events::emit::emit_event;
 use crate::events::topics::{ACT_CREATED, ACT_EVIDENCE, MOD_DISPUTE};
+use crate::guild::storage as guild_storage;
 use crate::milestone::storage as milestone_storage;
 use crate::milestone::types::ProjectStatus;
+use crate::payment::storage as payment_storage;
+use crate::payment::types::DistributionStatus;
+use crate::subscription::revenue::last_charge_routed;
+use crate::subscription::storage as subscription_storage;
+use crate::treasury::storage as treasury_storage;
+use crate::treasury::types::TransactionStatus;
 
 const VOTING_PERIOD_SECONDS: u64 = 7 * 24 * 60 * 60;
 const MAX_REASON_LEN: u32 = 1024;
@@ -84,6 +98,148 @@ pub fn create_dispute(
         (None, None) => panic!("reference not found"),
     };
 
+    open_dispute(
+        env,
+        reference_type,
+        reference_id,
+        guild_id,
+        plaintiff,
+        defendant,
+        reason,
+        evidence_url,
+    )
+}
+
+/// Create a dispute over a subscription charge, payment pool or treasury
+/// transaction.
+///
+/// The defendant is derived from the reference: the plan creator for a
+/// subscription, the pool creator for a payment pool and the proposer for a
+/// treasury transaction. Subscriptions and treasury transactions are judged
+/// by the guild that owns them; payment pools are not guild-bound, so they
+/// are judged by `guild_id`, which must count the pool creator as a member.
+///
+/// While the dispute is open the referenced charge, distribution or
+/// execution is blocked.
+///
+/// # Arguments
+/// * `reference_type` - `Subscription`, `PaymentPool` or `TreasuryTransaction`
+/// * `reference_id` - Subscription, pool or transaction ID
+/// * `guild_id` - Guild that judges the dispute
+/// * `plaintiff` - Address opening the dispute
+/// * `reason` - Short reason for the dispute
+/// * `evidence_url` - Initial evidence URL from plaintiff
+pub fn create_operation_dispute(
+    env: &Env,
+    reference_type: DisputeReference,
+    reference_id: u64,
+    guild_id: u64,
+    plaintiff: Address,
+    reason: String,
+    evidence_url: String,
+) -> u64 {
+    plaintiff.require_auth();
+
+    if reason.is_empty() || reason.len() > MAX_REASON_LEN {
+        panic!("invalid reason length");
+    }
+
+    if evidence_url.is_empty() || evidence_url.len() > MAX_EVIDENCE_LEN {
+        panic!("invalid evidence url");
+    }
+
+    if guild_storage::get_guild(env, guild_id).is_none() {
+        panic!("guild not found");
+    }
+
+    let defendant = match reference_type {
+        DisputeReference::Subscription => {
+            let subscription = subscription_storage::get_subscription(env, reference_id)
+                .expect("subscription not found");
+            let plan =
+                subscription_storage::get_plan(env, subscription.plan_id).expect("plan not found");
+            if plan.guild_id != guild_id {
+                panic!("guild mismatch");
+            }
+            if plaintiff != subscription.subscriber {
+                panic!("only the subscriber can dispute a charge");
+            }
+            if subscription.last_payment_amount.unwrap_or(0) <= 0 {
+                panic!("no charge to dispute");
+            }
+            if last_charge_routed(env, reference_id) {
+                panic!("routed charges cannot be refunded");
+            }
+            plan.created_by
+        }
+        DisputeReference::PaymentPool => {
+            let pool = payment_storage::get_payment_pool(env, reference_id)
+                .expect("payment pool not found");
+            if pool.status != DistributionStatus::Pending
+                && pool.status != DistributionStatus::Failed
+            {
+                panic!("payment pool not disputable");
+            }
+            if guild_storage::get_member(env, guild_id, &pool.created_by).is_none() {
+                panic!("pool creator must be guild member");
+            }
+            if !payment_storage::recipient_exists_in_pool(env, reference_id, &plaintiff) {
+                panic!("only pool recipients can dispute shares");
+            }
+            pool.created_by
+        }
+        DisputeReference::TreasuryTransaction => {
+            let tx = treasury_storage::get_transaction(env, reference_id).expect("tx not found");
+            let treasury =
+                treasury_storage::get_treasury(env, tx.treasury_id).expect("treasury not found");
+            if treasury.guild_id != guild_id {
+                panic!("guild mismatch");
+            }
+            if !matches!(
+                tx.status,
+                TransactionStatus::Pending | TransactionStatus::Approved
+            ) || env.ledger().timestamp() > tx.expires_at
+            {
+                panic!("transaction not disputable");
+            }
+            if guild_storage::get_member(env, guild_id, &plaintiff).is_none() {
+                panic!("plaintiff must be guild member");
+            }
+            tx.proposer
+        }
+        DisputeReference::Bounty | DisputeReference::Milestone => {
+            panic!("use create_dispute for bounties and milestones")
+        }
+    };
+
+    if plaintiff == defendant {
+        panic!("plaintiff and defendant must differ");
+    }
+
+    open_dispute(
+        env,
+        reference_type,
+        reference_id,
+        guild_id,
+        plaintiff,
+        defendant,
+        reason,
+        evidence_url,
+    )
+}
+
+/// Store a new dispute, lock its reference and draw a juror panel if needed.
+#[allow(clippy::too_many_arguments)]
+fn open_dispute(
+    env: &Env,
+    reference_type: DisputeReference,
+    reference_id: u64,
+    guild_id: u64,
+    plaintiff: Address,
+    defendant: Address,
+    reason: String,
+    evidence_url: String,
+) -> u64 {
     if dispute_storage::is_reference_locked(env, &reference_type, reference_id) {
         panic!("dispute already active for reference");
     }
@@ -111,10 +267,13 @@ pub fn create_dispute(
         vote_count: 0,
         resolved_at: None,
         resolution_executed: false,
+        round: 0,
+        appeal_deadline: None,
     };
 
     dispute_storage::store_dispute(env, &dispute);
     dispute_storage::lock_reference(env, &reference_type, reference_id, dispute_id);
+    jurors::draw_panel(env, &dispute);
 
     let event = DisputeCreatedEvent {
         dispute_id,
@@ -141,7 +300,10 @@ pub fn submit_evidence(env: &Env, dispute_id: u64, party: Address, evidence_url:
     }
 
     let mut dispute = dispute_storage::get_dispute(env, dispute_id).expect("dispute not found");
-    if dispute.status == DisputeStatus::Resolved || dispute.status == DisputeStatus::Expired {
+    if dispute.status == DisputeStatus::Resolved
+        || dispute.status == DisputeStatus::Expired
+        || dispute.status == DisputeStatus::Appealable
+    {
         panic!("dispute closed");
     }
 
@@ -173,7 +335,18 @@ pub fn cast_vote(
     voter: Address,
     decision: crate::dispute::types::VoteDecision,
 ) -> bool {
-    voting::cast_vote(env, dispute_id, voter, decision)
+    voting::cast_vote(env, dispute_id, voter, decision, None)
+}
+
+/// Cast a `Split` vote proposing the plaintiff's share in percent.
+pub fn cast_split_vote(env: &Env, dispute_id: u64, voter: Address, split_percentage: u32) -> bool {
+    voting::cast_vote(
+        env,
+        dispute_id,
+        voter,
+        crate::dispute::types::VoteDecision::Split,
+        Some(split_percentage),
+    )
 }
 
 /// Calculate a voter's weight based on their guild role.
@@ -199,5 +372,45 @@ pub fn execute_resolution(
     dispute_resolution::execute_resolution(env, dispute_id)
 }
 
+/// Enable or update juror-pool mode for a guild.
+pub fn set_juror_config(env: &Env, guild_id: u64, caller: Address, config: JurorConfig) -> bool {
+    jurors::set_juror_config(env, guild_id, caller, config)
+}
+
+/// Stake tokens to become a juror in a guild. Returns the new stake.
+pub fn stake_juror(env: &Env, guild_id: u64, juror: Address, amount: i128) -> i128 {
+    jurors::stake(env, guild_id, juror, amount)
+}
+
+/// Withdraw juror stake. Returns the remaining stake.
+pub fn unstake_juror(env: &Env, guild_id: u64, juror: Address, amount: i128) -> i128 {
+    jurors::unstake(env, guild_id, juror, amount)
+}
+
+/// Fetch a juror record.
+pub fn get_juror(env: &Env, guild_id: u64, juror: Address) -> Juror {
+    dispute_storage::get_juror(env, guild_id, &juror).unwrap_or_else(|| panic!("juror not found"))
+}
+
+/// Fetch the juror panel drawn for a dispute (empty if voted by all members).
+pub fn get_dispute_panel(env: &Env, dispute_id: u64) -> soroban_sdk::Vec<Address> {
+    dispute_storage::get_panel(env, dispute_id).unwrap_or_else(|| soroban_sdk::Vec::new(env))
+}
+
+/// Enable or update appeals for a guild.
+pub fn set_appeal_config(env: &Env, guild_id: u64, caller: Address, config: AppealConfig) -> bool {
+    appeals::set_appeal_config(env, guild_id, caller, config)
+}
+
+/// Appeal a ruling during its appeal window. Returns the new round number.
+pub fn appeal_dispute(env: &Env, dispute_id: u64, appellant: Address) -> u32 {
+    appeals::appeal_dispute(env, dispute_id, appellant)
+}
+
+/// Fetch the archived rounds of an appealed dispute.
+pub fn get_dispute_rounds(env: &Env, dispute_id: u64) -> soroban_sdk::Vec<DisputeRound> {
+    dispute_storage::get_rounds(env, dispute_id)
+}
+
 #[cfg(test)]
 mod tests;
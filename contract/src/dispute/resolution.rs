@@ -3,30 +3,45 @@
 use crate::bounty::escrow::release_funds;
 use crate::bounty::storage as bounty_storage;
 use crate::bounty::types::BountyStatus;
+use crate::dispute::appeals::{can_appeal, settle_bonds};
+use crate::dispute::jurors::settle_panel;
 use crate::dispute::storage;
 use crate::dispute::types::{
-    DisputeReference, DisputeStatus, FundDistribution, Resolution, VoteDecision,
+    Dispute, DisputeReference, DisputeStatus, FundDistribution, Resolution, VoteDecision,
 };
 use crate::events::emit::emit_event;
 use crate::events::topics::{ACT_EXECUTED, ACT_EXPIRED, ACT_RESOLVED, MOD_DISPUTE};
 use crate::guild::storage as guild_storage;
 use crate::milestone::storage as milestone_storage;
+use crate::milestone::tracker::{complete_project, pay_milestone};
 use crate::milestone::types::{MilestoneStatus, ProjectStatus};
-use crate::treasury::execute_milestone_payment;
+use crate::payment::distribution::{block_disputed_pool, reopen_disputed_pool};
+use crate::subscription::lifecycle::refund_disputed_charge;
+use crate::subscription::storage as subscription_storage;
+use crate::treasury::management::{reject_disputed_transaction, reopen_disputed_transaction};
 
 const QUORUM_PERCENTAGE: u32 = 30;
-
-fn quorum_reached(env: &Env, guild_id: u64, vote_count: u32) -> bool {
-    let members = guild_storage::get_all_members(env, guild_id);
-    let total = members.len() as u32;
+const DEFAULT_SPLIT_PERCENTAGE: u32 = 50;
+/// Extra member quorum required for each appeal round.
+const APPEAL_QUORUM_STEP: u32 = 15;
+
+fn quorum_reached(env: &Env, dispute: &Dispute) -> bool {
+    let total = match storage::get_panel(env, dispute.id) {
+        Some(panel) => panel.len(),
+        None => guild_storage::get_all_members(env, dispute.guild_id).len(),
+    };
+    let vote_count = dispute.vote_count;
     if total == 0 {
         return false;
     }
 
-    vote_count.saturating_mul(100) / total >= QUORUM_PERCENTAGE
+    let required = QUORUM_PERCENTAGE
+        .saturating_add(dispute.round.saturating_mul(APPEAL_QUORUM_STEP))
+        .min(100);
+    vote_count.saturating_mul(100) / total >= required
 }
 
-fn decide_winner(
+pub(crate) fn decide_winner(
     votes_for_plaintiff: i128,
     votes_for_defendant: i128,
     votes_split: i128,
@@ -40,28 +55,130 @@ fn decide_winner(
     }
 }
 
-/// Tally votes for a dispute without mutating state.
-pub fn tally_votes(env: &Env, dispute_id: u64) -> Resolution {
-    let dispute = storage::get_dispute(env, dispute_id).expect("dispute not found");
-    let quorum = quorum_reached(env, dispute.guild_id, dispute.vote_count);
-
-    let winner = if quorum {
-        match decide_winner(
-            dispute.votes_for_plaintiff,
-            dispute.votes_for_defendant,
-            dispute.votes_split,
-        ) {
+/// Weighted median of the plaintiff shares proposed with `Split` votes in
+/// the current round. Votes without a proposal count as 50%.
+pub(crate) fn weighted_median_split(env: &Env, dispute_id: u64) -> u32 {
+    let mut proposals: Vec<(u32, i128)> = Vec::new(env);
+    let mut total_weight: i128 = 0;
+
+    for vote in storage::get_votes(env, dispute_id).iter() {
+        if vote.decision != VoteDecision::Split {
+            continue;
+        }
+        let pct = vote.split_percentage.unwrap_or(DEFAULT_SPLIT_PERCENTAGE);
+        let mut idx = proposals.len();
+        for (i, (existing, _)) in proposals.iter().enumerate() {
+            if pct < existing {
+                idx = i as u32;
+                break;
+            }
+        }
+        proposals.insert(idx, (pct, vote.weight));
+        total_weight += vote.weight;
+    }
+
+    let mut cumulative: i128 = 0;
+    for (pct, weight) in proposals.iter() {
+        cumulative += weight;
+        if cumulative * 2 >= total_weight {
+            return pct;
+        }
+    }
+
+    DEFAULT_SPLIT_PERCENTAGE
+}
+
+/// Plaintiff's share (0-100) under a ruling.
+fn plaintiff_share(env: &Env, dispute: &Dispute, decision: &VoteDecision) -> u32 {
+    match decision {
+        VoteDecision::FavorPlaintiff => 100,
+        VoteDecision::FavorDefendant => 0,
+        VoteDecision::Split => weighted_median_split(env, dispute.id),
+    }
+}
+
+/// Funds that a ruling on the dispute's reference can move. Payment pools and
+/// treasury transactions are blocked or released rather than paid out.
+fn disputed_amount(env: &Env, dispute: &Dispute) -> i128 {
+    match dispute.reference_type {
+        DisputeReference::Bounty => {
+            bounty_storage::get_bounty(env, dispute.reference_id)
+                .expect("bounty not found")
+                .funded_amount
+        }
+        DisputeReference::Milestone => {
+            milestone_storage::get_milestone(env, dispute.reference_id)
+                .expect("milestone not found")
+                .payment_amount
+        }
+        DisputeReference::Subscription => {
+            subscription_storage::get_subscription(env, dispute.reference_id)
+                .expect("subscription not found")
+                .last_payment_amount
+                .unwrap_or(0)
+        }
+        DisputeReference::PaymentPool | DisputeReference::TreasuryTransaction => 0,
+    }
+}
+
+/// Payouts implied by a ruling on the disputed amount. Zero legs are omitted.
+fn planned_distribution(
+    env: &Env,
+    dispute: &Dispute,
+    decision: &VoteDecision,
+) -> Vec<FundDistribution> {
+    let mut distributions = Vec::new(env);
+
+    let total = disputed_amount(env, dispute);
+    if total <= 0 {
+        return distributions;
+    }
+
+    let plaintiff_amt = total * plaintiff_share(env, dispute, decision) as i128 / 100;
+    // A subscription charge is only refunded; the rest stays collected.
+    let defendant_amt = if dispute.reference_type == DisputeReference::Subscription {
+        0
+    } else {
+        total - plaintiff_amt
+    };
+
+    if plaintiff_amt > 0 {
+        distributions.push_back(FundDistribution {
+            recipient: dispute.plaintiff.clone(),
+            amount: plaintiff_amt,
+        });
+    }
+    if defendant_amt > 0 {
+        distributions.push_back(FundDistribution {
+            recipient: dispute.defendant.clone(),
+            amount: defendant_amt,
+        });
+    }
+
+    distributions
+}
+
+fn build_resolution(env: &Env, dispute: &Dispute, quorum: bool) -> Resolution {
+    let decision = decide_winner(
+        dispute.votes_for_plaintiff,
+        dispute.votes_for_defendant,
+        dispute.votes_split,
+    );
+
+    let (winner, fund_distribution) = if quorum {
+        let winner = match decision {
             VoteDecision::FavorPlaintiff => Some(dispute.plaintiff.clone()),
             VoteDecision::FavorDefendant => Some(dispute.defendant.clone()),
             VoteDecision::Split => None,
-        }
+        };
+        (winner, planned_distribution(env, dispute, &decision))
     } else {
-        None
+        (None, Vec::new(env))
     };
 
     Resolution {
         winner,
-        fund_distribution: Vec::new(env),
+        fund_distribution,
         vote_count: dispute.vote_count,
         votes_for_plaintiff: dispute.votes_for_plaintiff,
         votes_for_defendant: dispute.votes_for_defendant,
@@ -70,7 +187,18 @@ pub fn tally_votes(env: &Env, dispute_id: u64) -> Resolution {
     }
 }
 
+/// Tally votes for a dispute without mutating state.
+pub fn tally_votes(env: &Env, dispute_id: u64) -> Resolution {
+    let dispute = storage::get_dispute(env, dispute_id).expect("dispute not found");
+    let quorum = quorum_reached(env, &dispute);
+    build_resolution(env, &dispute, quorum)
+}
+
 /// Resolve a dispute after the voting deadline and execute payouts.
+///
+/// When the guild allows appeals and rounds remain, a ruling first enters an
+/// appeal window with funds still locked; calling this again once the window
+/// has passed makes the ruling final and executes it.
 pub fn resolve_dispute(env: &Env, dispute_id: u64) -> Resolution {
     let mut dispute = storage::get_dispute(env, dispute_id).expect("dispute not found");
 
@@ -79,19 +207,45 @@ pub fn resolve_dispute(env: &Env, dispute_id: u64) -> Resolution {
     }
 
     let now = env.ledger().timestamp();
+
+    if dispute.status == DisputeStatus::Appealable {
+        if now <= dispute.appeal_deadline.unwrap_or(0) {
+            panic!("appeal window still open");
+        }
+        let resolution = build_resolution(env, &dispute, true);
+        return finalize_ruling(env, dispute, resolution);
+    }
+
     if now < dispute.voting_deadline {
         panic!("voting period still active");
     }
 
-    let mut resolution = tally_votes(env, dispute_id);
+    let resolution = tally_votes(env, dispute_id);
 
     if !resolution.quorum_reached {
+        settle_panel(env, &dispute, None);
+
+        // An appeal round without quorum leaves the previous ruling standing.
+        if let Some(previous) = storage::get_rounds(env, dispute_id).last() {
+            storage::clear_votes(env, dispute_id);
+            for vote in previous.votes.iter() {
+                storage::store_vote(env, &vote);
+            }
+            dispute.votes_for_plaintiff = previous.votes_for_plaintiff;
+            dispute.votes_for_defendant = previous.votes_for_defendant;
+            dispute.votes_split = previous.votes_split;
+            dispute.vote_count = previous.vote_count;
+            let resolution = build_resolution(env, &dispute, true);
+            return finalize_ruling(env, dispute, resolution);
+        }
+
         dispute.status = DisputeStatus::Expired;
         dispute.resolved_at = Some(now);
         storage::store_dispute(env, &dispute);
         storage::unlock_reference(env, &dispute.reference_type, dispute.reference_id);
 
         // Refund rules for expired disputes
+        release_operation(env, &dispute);
         if dispute.reference_type == DisputeReference::Bounty {
             let mut bounty =
                 bounty_storage::get_bounty(env, dispute.reference_id).expect("bounty not found");
@@ -112,8 +266,43 @@ pub fn resolve_dispute(env: &Env, dispute_id: u64) -> Resolution {
         return resolution;
     }
 
+    let majority = decide_winner(
+        dispute.votes_for_plaintiff,
+        dispute.votes_for_defendant,
+        dispute.votes_split,
+    );
+    settle_panel(env, &dispute, Some(majority));
+
+    if can_appeal(env, &dispute) {
+        let config = storage::get_appeal_config(env, dispute.guild_id).unwrap();
+        dispute.status = DisputeStatus::Appealable;
+        dispute.resolved_at = Some(now);
+        dispute.appeal_deadline = Some(now + config.appeal_window_seconds);
+        storage::store_dispute(env, &dispute);
+
+        let event = crate::dispute::types::DisputeResolvedEvent {
+            dispute_id,
+            status: dispute.status.clone(),
+        };
+        emit_event(env, MOD_DISPUTE, ACT_RESOLVED, event);
+
+        // Nothing is paid out until the ruling becomes final.
+        let mut resolution = resolution;
+        resolution.fund_distribution = Vec::new(env);
+        return resolution;
+    }
+
+    finalize_ruling(env, dispute, resolution)
+}
+
+/// Make the current ruling final: unlock the reference, settle appeal bonds
+/// and execute the fund distribution.
+fn finalize_ruling(env: &Env, mut dispute: Dispute, mut resolution: Resolution) -> Resolution {
+    let dispute_id = dispute.id;
+
     dispute.status = DisputeStatus::Resolved;
-    dispute.resolved_at = Some(now);
+    dispute.resolved_at = Some(env.ledger().timestamp());
+    dispute.appeal_deadline = None;
     storage::store_dispute(env, &dispute);
     storage::unlock_reference(env, &dispute.reference_type, dispute.reference_id);
 
@@ -123,6 +312,13 @@ pub fn resolve_dispute(env: &Env, dispute_id: u64) -> Resolution {
     };
     emit_event(env, MOD_DISPUTE, ACT_RESOLVED, event);
 
+    let decision = decide_winner(
+        dispute.votes_for_plaintiff,
+        dispute.votes_for_defendant,
+        dispute.votes_split,
+    );
+    settle_bonds(env, &dispute, &decision);
+
     let distributions = execute_resolution(env, dispute_id);
     resolution.fund_distribution = distributions;
 
@@ -147,44 +343,16 @@ pub fn execute_resolution(env: &Env, dispute_id: u64) -> Vec<FundDistribution> {
         dispute.votes_split,
     );
 
-    let mut distributions = Vec::new(env);
+    let distributions = planned_distribution(env, &dispute, &decision);
 
     match dispute.reference_type {
         DisputeReference::Bounty => {
             let mut bounty =
                 bounty_storage::get_bounty(env, dispute.reference_id).expect("bounty not found");
 
-            let total = bounty.funded_amount;
-            if total > 0 {
-                match decision {
-                    VoteDecision::FavorPlaintiff => {
-                        release_funds(env, &bounty.token, &dispute.plaintiff, total);
-                        distributions.push_back(FundDistribution {
-                            recipient: dispute.plaintiff.clone(),
-                            amount: total,
-                        });
-                    }
-                    VoteDecision::FavorDefendant => {
-                        release_funds(env, &bounty.token, &dispute.defendant, total);
-                        distributions.push_back(FundDistribution {
-                            recipient: dispute.defendant.clone(),
-                            amount: total,
-                        });
-                    }
-                    VoteDecision::Split => {
-                        let half = total / 2;
-                        let remainder = total - half;
-                        release_funds(env, &bounty.token, &dispute.plaintiff, half);
-                        release_funds(env, &bounty.token, &dispute.defendant, remainder);
-                        distributions.push_back(FundDistribution {
-                            recipient: dispute.plaintiff.clone(),
-                            amount: half,
-                        });
-                        distributions.push_back(FundDistribution {
-                            recipient: dispute.defendant.clone(),
-                            amount: remainder,
-                        });
-                    }
+            if bounty.funded_amount > 0 {
+                for d in distributions.iter() {
+                    release_funds(env, &bounty.token, &d.recipient, d.amount);
                 }
 
                 bounty.funded_amount = 0;
@@ -199,15 +367,6 @@ pub fn execute_resolution(env: &Env, dispute_id: u64) -> Vec<FundDistribution> {
 
             let total = milestone.payment_amount;
             if total > 0 {
-                let (plaintiff_amt, defendant_amt) = match decision {
-                    VoteDecision::FavorPlaintiff => (total, 0),
-                    VoteDecision::FavorDefendant => (0, total),
-                    VoteDecision::Split => {
-                        let half = total / 2;
-                        (half, total - half)
-                    }
-                };
-
                 let new_released = project
                     .released_amount
                     .checked_add(total)
@@ -216,31 +375,8 @@ pub fn execute_resolution(env: &Env, dispute_id: u64) -> Vec<FundDistribution> {
                     panic!("project budget exceeded");
                 }
 
-                if plaintiff_amt > 0 {
-                    execute_milestone_payment(
-                        env,
-                        project.treasury_id,
-                        project.token.clone(),
-                        dispute.plaintiff.clone(),
-                        plaintiff_amt,
-                    );
-                    distributions.push_back(FundDistribution {
-                        recipient: dispute.plaintiff.clone(),
-                        amount: plaintiff_amt,
-                    });
-                }
-                if defendant_amt > 0 {
-                    execute_milestone_payment(
-                        env,
-                        project.treasury_id,
-                        project.token.clone(),
-                        dispute.defendant.clone(),
-                        defendant_amt,
-                    );
-                    distributions.push_back(FundDistribution {
-                        recipient: dispute.defendant.clone(),
-                        amount: defendant_amt,
-                    });
+                for d in distributions.iter() {
+                    pay_milestone(env, &project, d.recipient.clone(), d.amount);
                 }
 
                 project.released_amount = new_released;
@@ -267,11 +403,22 @@ pub fn execute_resolution(env: &Env, dispute_id: u64) -> Vec<FundDistribution> {
                 }
 
                 if all_done && project.status != ProjectStatus::Completed {
-                    project.status = ProjectStatus::Completed;
-                    milestone_storage::store_project(env, &project);
+                    complete_project(env, &mut project);
                 }
             }
         }
+        DisputeReference::Subscription => {
+            for d in distributions.iter() {
+                refund_disputed_charge(env, dispute.reference_id, d.amount);
+            }
+        }
+        DisputeReference::PaymentPool | DisputeReference::TreasuryTransaction => {
+            if plaintiff_share(env, &dispute, &decision) > 50 {
+                block_operation(env, &dispute);
+            } else {
+                release_operation(env, &dispute);
+            }
+        }
     }
 
     dispute.resolution_executed = true;
@@ -282,3 +429,28 @@ pub fn execute_resolution(env: &Env, dispute_id: u64) -> Vec<FundDistribution> {
 
     distributions
 }
+
+/// Stop a disputed payment pool or treasury transaction for good.
+fn block_operation(env: &Env, dispute: &Dispute) {
+    match dispute.reference_type {
+        DisputeReference::PaymentPool => block_disputed_pool(env, dispute.reference_id),
+        DisputeReference::TreasuryTransaction => reject_disputed_transaction(
+            env,
+            dispute.reference_id,
+            dispute.plaintiff.clone(),
+            dispute.reason.clone(),
+        ),
+        _ => {}
+    }
+}
+
+/// Let a disputed payment pool or treasury transaction be run again.
+fn release_operation(env: &Env, dispute: &Dispute) {
+    match dispute.reference_type {
+        DisputeReference::PaymentPool => reopen_disputed_pool(env, dispute.reference_id),
+        DisputeReference::TreasuryTransaction => {
+            reopen_disputed_transaction(env, dispute.reference_id)
+        }
+        _ => {}
+    }
+}
@@ -1,11 +1,20 @@
-﻿use soroban_sdk::{symbol_short, Address, Env, Map, Symbol};
+﻿use soroban_sdk::{symbol_short, Address, Env, Map, Symbol, Vec};
 
-use crate::dispute::types::{Dispute, DisputeReference, Vote};
+use crate::dispute::types::{
+    AppealBond, AppealConfig, Dispute, DisputeReference, DisputeRound, Juror, JurorConfig, Vote,
+};
 
 const DISPUTES_KEY: Symbol = symbol_short!("dsp_all");
 const DISPUTE_COUNTER_KEY: Symbol = symbol_short!("dsp_cnt");
 const DISPUTE_VOTES_KEY: Symbol = symbol_short!("dsp_vot");
 const REF_LOCKS_KEY: Symbol = symbol_short!("dsp_ref");
+const JUROR_CONFIG_KEY: Symbol = symbol_short!("dsp_jcfg");
+const JURORS_KEY: Symbol = symbol_short!("dsp_jur");
+const JUROR_POOL_KEY: Symbol = symbol_short!("dsp_pool");
+const PANELS_KEY: Symbol = symbol_short!("dsp_pnl");
+const APPEAL_CONFIG_KEY: Symbol = symbol_short!("dsp_acfg");
+const ROUNDS_KEY: Symbol = symbol_short!("dsp_rnds");
+const BONDS_KEY: Symbol = symbol_short!("dsp_bond");
 
 /// Get the next dispute ID and increment the counter.
 pub fn get_next_dispute_id(env: &Env) -> u64 {
@@ -74,6 +83,36 @@ pub fn get_vote(env: &Env, dispute_id: u64, voter: &Address) -> Option<Vote> {
     dispute_votes.get(voter.clone())
 }
 
+/// Fetch all votes cast in the current round of a dispute.
+pub fn get_votes(env: &Env, dispute_id: u64) -> Vec<Vote> {
+    let votes_map: Map<u64, Map<Address, Vote>> = env
+        .storage()
+        .persistent()
+        .get(&DISPUTE_VOTES_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    votes_map
+        .get(dispute_id)
+        .map(|v| v.values())
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Remove the current round's votes for a dispute, e.g. when it is appealed.
+pub fn clear_votes(env: &Env, dispute_id: u64) {
+    let mut votes_map: Map<u64, Map<Address, Vote>> = env
+        .storage()
+        .persistent()
+        .get(&DISPUTE_VOTES_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    if votes_map.contains_key(dispute_id) {
+        votes_map.remove(dispute_id);
+        env.storage()
+            .persistent()
+            .set(&DISPUTE_VOTES_KEY, &votes_map);
+    }
+}
+
 /// Check whether a reference is locked by an active dispute.
 pub fn is_reference_locked(
     env: &Env,
@@ -119,3 +158,183 @@ pub fn unlock_reference(env: &Env, reference_type: &DisputeReference, reference_
         env.storage().persistent().set(&REF_LOCKS_KEY, &locks);
     }
 }
+
+/// Fetch the juror-pool configuration for a guild, if juror mode is enabled.
+pub fn get_juror_config(env: &Env, guild_id: u64) -> Option<JurorConfig> {
+    let configs: Map<u64, JurorConfig> = env
+        .storage()
+        .persistent()
+        .get(&JUROR_CONFIG_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    configs.get(guild_id)
+}
+
+/// Persist the juror-pool configuration for a guild.
+pub fn set_juror_config(env: &Env, guild_id: u64, config: &JurorConfig) {
+    let mut configs: Map<u64, JurorConfig> = env
+        .storage()
+        .persistent()
+        .get(&JUROR_CONFIG_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    configs.set(guild_id, config.clone());
+    env.storage().persistent().set(&JUROR_CONFIG_KEY, &configs);
+}
+
+/// Fetch a juror record by guild and address.
+pub fn get_juror(env: &Env, guild_id: u64, address: &Address) -> Option<Juror> {
+    let jurors: Map<(u64, Address), Juror> = env
+        .storage()
+        .persistent()
+        .get(&JURORS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    jurors.get((guild_id, address.clone()))
+}
+
+/// Persist a juror record and index it in the guild's juror pool.
+pub fn store_juror(env: &Env, juror: &Juror) {
+    let mut jurors: Map<(u64, Address), Juror> = env
+        .storage()
+        .persistent()
+        .get(&JURORS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    jurors.set((juror.guild_id, juror.address.clone()), juror.clone());
+    env.storage().persistent().set(&JURORS_KEY, &jurors);
+
+    let mut pools: Map<u64, Vec<Address>> = env
+        .storage()
+        .persistent()
+        .get(&JUROR_POOL_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    let mut pool = pools.get(juror.guild_id).unwrap_or_else(|| Vec::new(env));
+    if !pool.iter().any(|a| a == juror.address) {
+        pool.push_back(juror.address.clone());
+        pools.set(juror.guild_id, pool);
+        env.storage().persistent().set(&JUROR_POOL_KEY, &pools);
+    }
+}
+
+/// List every address that has ever staked as a juror in a guild.
+pub fn get_juror_pool(env: &Env, guild_id: u64) -> Vec<Address> {
+    let pools: Map<u64, Vec<Address>> = env
+        .storage()
+        .persistent()
+        .get(&JUROR_POOL_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    pools.get(guild_id).unwrap_or_else(|| Vec::new(env))
+}
+
+/// Persist the juror panel drawn for a dispute.
+pub fn store_panel(env: &Env, dispute_id: u64, panel: &Vec<Address>) {
+    let mut panels: Map<u64, Vec<Address>> = env
+        .storage()
+        .persistent()
+        .get(&PANELS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    panels.set(dispute_id, panel.clone());
+    env.storage().persistent().set(&PANELS_KEY, &panels);
+}
+
+/// Drop the juror panel for a dispute so the round is decided by members.
+pub fn remove_panel(env: &Env, dispute_id: u64) {
+    let mut panels: Map<u64, Vec<Address>> = env
+        .storage()
+        .persistent()
+        .get(&PANELS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    if panels.contains_key(dispute_id) {
+        panels.remove(dispute_id);
+        env.storage().persistent().set(&PANELS_KEY, &panels);
+    }
+}
+
+/// Fetch the juror panel for a dispute, if it was decided by jurors.
+pub fn get_panel(env: &Env, dispute_id: u64) -> Option<Vec<Address>> {
+    let panels: Map<u64, Vec<Address>> = env
+        .storage()
+        .persistent()
+        .get(&PANELS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    panels.get(dispute_id)
+}
+
+/// Fetch the appeal configuration for a guild, if appeals are enabled.
+pub fn get_appeal_config(env: &Env, guild_id: u64) -> Option<AppealConfig> {
+    let configs: Map<u64, AppealConfig> = env
+        .storage()
+        .persistent()
+        .get(&APPEAL_CONFIG_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    configs.get(guild_id)
+}
+
+/// Persist the appeal configuration for a guild.
+pub fn set_appeal_config(env: &Env, guild_id: u64, config: &AppealConfig) {
+    let mut configs: Map<u64, AppealConfig> = env
+        .storage()
+        .persistent()
+        .get(&APPEAL_CONFIG_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    configs.set(guild_id, config.clone());
+    env.storage().persistent().set(&APPEAL_CONFIG_KEY, &configs);
+}
+
+/// Append an archived round to a dispute's history.
+pub fn push_round(env: &Env, dispute_id: u64, round: &DisputeRound) {
+    let mut rounds: Map<u64, Vec<DisputeRound>> = env
+        .storage()
+        .persistent()
+        .get(&ROUNDS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    let mut list = rounds.get(dispute_id).unwrap_or_else(|| Vec::new(env));
+    list.push_back(round.clone());
+    rounds.set(dispute_id, list);
+    env.storage().persistent().set(&ROUNDS_KEY, &rounds);
+}
+
+/// Fetch the archived rounds of a dispute, oldest first.
+pub fn get_rounds(env: &Env, dispute_id: u64) -> Vec<DisputeRound> {
+    let rounds: Map<u64, Vec<DisputeRound>> = env
+        .storage()
+        .persistent()
+        .get(&ROUNDS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    rounds.get(dispute_id).unwrap_or_else(|| Vec::new(env))
+}
+
+/// Record an appeal bond posted against a dispute.
+pub fn push_bond(env: &Env, dispute_id: u64, bond: &AppealBond) {
+    let mut bonds: Map<u64, Vec<AppealBond>> = env
+        .storage()
+        .persistent()
+        .get(&BONDS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    let mut list = bonds.get(dispute_id).unwrap_or_else(|| Vec::new(env));
+    list.push_back(bond.clone());
+    bonds.set(dispute_id, list);
+    env.storage().persistent().set(&BONDS_KEY, &bonds);
+}
+
+/// Fetch the appeal bonds posted against a dispute.
+pub fn get_bonds(env: &Env, dispute_id: u64) -> Vec<AppealBond> {
+    let bonds: Map<u64, Vec<AppealBond>> = env
+        .storage()
+        .persistent()
+        .get(&BONDS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    bonds.get(dispute_id).unwrap_or_else(|| Vec::new(env))
+}
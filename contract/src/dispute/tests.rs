@@ -1,5 +1,7 @@
 //! Dispute Resolution Contract Tests
-use crate::dispute::types::VoteDecision;
+use crate::dispute::types::{
+    AppealConfig, DisputeReference, DisputeStatus, JurorConfig, VoteDecision,
+};
 use crate::guild::types::Role;
 use crate::InitializerProof;
 use crate::StellarGuildsContract;
@@ -140,6 +142,7 @@ fn test_create_dispute_milestone_success() {
         description: String::from_str(&env, "First milestone"),
         payment_amount: 100,
         deadline: 2000,
+        prerequisites: Vec::new(&env),
     });
 
     client.create_project(
@@ -161,6 +164,73 @@ fn test_create_dispute_milestone_success() {
     assert_eq!(dispute_id, 1);
 }
 
+#[test]
+fn test_milestone_ruling_pays_from_project_reservation() {
+    let env = setup_env();
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, owner, admin, member, contributor) = setup_guild_with_members(&client, &env);
+
+    let mut signers = Vec::new(&env);
+    signers.push_back(owner.clone());
+    let treasury_id = client.initialize_treasury(&guild_id, &signers, &1u32);
+    client.deposit_treasury(&treasury_id, &owner, &100i128, &None);
+
+    let mut milestones: Vec<crate::milestone::types::MilestoneInput> = Vec::new(&env);
+    milestones.push_back(crate::milestone::types::MilestoneInput {
+        title: String::from_str(&env, "Milestone 1"),
+        description: String::from_str(&env, "First milestone"),
+        payment_amount: 100,
+        deadline: 1_000_000,
+        prerequisites: Vec::new(&env),
+    });
+    let project_id = client.create_project(
+        &guild_id,
+        &contributor,
+        &milestones,
+        &100i128,
+        &treasury_id,
+        &None,
+        &false,
+    );
+
+    // The whole treasury balance is reserved for the project
+    client.approve_project_funding(&project_id, &owner);
+    assert_eq!(
+        client
+            .get_treasury_balance_detail(&treasury_id, &None)
+            .committed,
+        100
+    );
+
+    let reason = String::from_str(&env, "Milestone dispute");
+    let evidence = String::from_str(&env, "https://example.com/evidence");
+    let dispute_id = client.create_dispute(&1u64, &contributor, &owner, &reason, &evidence);
+
+    client.cast_dispute_vote(&dispute_id, &admin, &VoteDecision::FavorPlaintiff);
+    client.cast_dispute_vote(&dispute_id, &member, &VoteDecision::FavorPlaintiff);
+
+    set_ledger_timestamp(&env, 1000 + 7 * 24 * 60 * 60 + 1);
+    let resolution = client.resolve_dispute(&dispute_id);
+    assert_eq!(resolution.winner, Some(contributor.clone()));
+
+    assert!(client.get_milestone(&1u64).is_payment_released);
+    assert_eq!(
+        client
+            .get_project_funding(&project_id)
+            .unwrap()
+            .reserved_amount,
+        0
+    );
+    let detail = client.get_treasury_balance_detail(&treasury_id, &None);
+    assert_eq!(detail.liquid, 0);
+    assert_eq!(detail.committed, 0);
+}
+
 #[test]
 #[should_panic(expected = "only parties can submit evidence")]
 fn test_submit_evidence_non_party_fails() {
@@ -446,3 +516,619 @@ fn test_dispute_status_updates_after_resolution() {
     let dispute = client.tally_dispute_votes(&dispute_id);
     assert_eq!(dispute.vote_count, 2);
 }
+
+fn setup_juror_pool(
+    client: &StellarGuildsContractClient<'_>,
+    env: &Env,
+    guild_id: u64,
+    owner: &Address,
+    token: &Address,
+    count: u32,
+) -> Vec<Address> {
+    client.set_juror_config(
+        &guild_id,
+        owner,
+        &JurorConfig {
+            stake_token: token.clone(),
+            min_stake: 500,
+            panel_size: 3,
+            slash_bps: 1000,
+        },
+    );
+
+    let mut jurors = Vec::new(env);
+    for _ in 0..count {
+        let juror = Address::generate(env);
+        client.add_member(&guild_id, &juror, &Role::Member, owner);
+        mint_tokens(env, token, &juror, 1000);
+        client.stake_juror(&guild_id, &juror, &1000);
+        jurors.push_back(juror);
+    }
+    jurors
+}
+
+#[test]
+fn test_juror_panel_drawn_from_staked_pool() {
+    let env = setup_env();
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, owner, _admin, _member, contributor) = setup_guild_with_members(&client, &env);
+    let token = create_mock_token(&env, &owner);
+    let jurors = setup_juror_pool(&client, &env, guild_id, &owner, &token, 5);
+    let bounty_id = create_funded_bounty(&client, &env, guild_id, &owner, &owner, &token);
+
+    let reason = String::from_str(&env, "Dispute reason");
+    let evidence = String::from_str(&env, "ipfs://evidence");
+    let dispute_id = client.create_dispute(&bounty_id, &contributor, &owner, &reason, &evidence);
+
+    let panel = client.get_dispute_panel(&dispute_id);
+    assert_eq!(panel.len(), 3);
+    for juror in panel.iter() {
+        assert!(jurors.contains(&juror));
+        assert_eq!(client.get_juror(&guild_id, &juror).active_panels, 1);
+    }
+}
+
+#[test]
+fn test_small_juror_pool_falls_back_to_member_voting() {
+    let env = setup_env();
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, owner, admin, _member, contributor) = setup_guild_with_members(&client, &env);
+    let token = create_mock_token(&env, &owner);
+    let jurors = setup_juror_pool(&client, &env, guild_id, &owner, &token, 2);
+    let bounty_id = create_funded_bounty(&client, &env, guild_id, &owner, &owner, &token);
+
+    let reason = String::from_str(&env, "Dispute reason");
+    let evidence = String::from_str(&env, "ipfs://evidence");
+    let dispute_id = client.create_dispute(&bounty_id, &contributor, &owner, &reason, &evidence);
+
+    assert_eq!(client.get_dispute_panel(&dispute_id).len(), 0);
+    for juror in jurors.iter() {
+        assert_eq!(client.get_juror(&guild_id, &juror).active_panels, 0);
+    }
+
+    assert!(client.cast_dispute_vote(&dispute_id, &admin, &VoteDecision::FavorPlaintiff));
+}
+
+#[test]
+#[should_panic(expected = "voter not on juror panel")]
+fn test_non_panel_member_cannot_vote() {
+    let env = setup_env();
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, owner, admin, _member, contributor) = setup_guild_with_members(&client, &env);
+    let token = create_mock_token(&env, &owner);
+    setup_juror_pool(&client, &env, guild_id, &owner, &token, 3);
+    let bounty_id = create_funded_bounty(&client, &env, guild_id, &owner, &owner, &token);
+
+    let reason = String::from_str(&env, "Dispute reason");
+    let evidence = String::from_str(&env, "ipfs://evidence");
+    let dispute_id = client.create_dispute(&bounty_id, &contributor, &owner, &reason, &evidence);
+
+    client.cast_dispute_vote(&dispute_id, &admin, &VoteDecision::FavorPlaintiff);
+}
+
+#[test]
+fn test_juror_rewards_and_slashing_on_resolution() {
+    let env = setup_env();
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, owner, _admin, _member, contributor) = setup_guild_with_members(&client, &env);
+    let token = create_mock_token(&env, &owner);
+    let jurors = setup_juror_pool(&client, &env, guild_id, &owner, &token, 3);
+    let bounty_id = create_funded_bounty(&client, &env, guild_id, &owner, &owner, &token);
+
+    let reason = String::from_str(&env, "Dispute reason");
+    let evidence = String::from_str(&env, "ipfs://evidence");
+    let dispute_id = client.create_dispute(&bounty_id, &contributor, &owner, &reason, &evidence);
+
+    let j1 = jurors.get(0).unwrap();
+    let j2 = jurors.get(1).unwrap();
+    let j3 = jurors.get(2).unwrap();
+    client.cast_dispute_vote(&dispute_id, &j1, &VoteDecision::FavorPlaintiff);
+    client.cast_dispute_vote(&dispute_id, &j2, &VoteDecision::FavorPlaintiff);
+    // j3 does not vote and is slashed
+
+    let tally = client.tally_dispute_votes(&dispute_id);
+    assert_eq!(tally.votes_for_plaintiff, 2);
+    assert!(tally.quorum_reached);
+
+    set_ledger_timestamp(&env, 1000 + 7 * 24 * 60 * 60 + 1);
+    let resolution = client.resolve_dispute(&dispute_id);
+    assert_eq!(resolution.winner, Some(contributor.clone()));
+
+    // 10% of j3's 1000 stake is shared between j1 and j2
+    assert_eq!(client.get_juror(&guild_id, &j3).stake, 900);
+    assert_eq!(get_token_balance(&env, &token, &j1), 50);
+    assert_eq!(get_token_balance(&env, &token, &j2), 50);
+
+    for juror in jurors.iter() {
+        assert_eq!(client.get_juror(&guild_id, &juror).active_panels, 0);
+    }
+    assert_eq!(client.unstake_juror(&guild_id, &j3, &900), 0);
+    assert_eq!(get_token_balance(&env, &token, &j3), 900);
+}
+
+#[test]
+#[should_panic(expected = "juror has active panels")]
+fn test_juror_cannot_unstake_while_empanelled() {
+    let env = setup_env();
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, owner, _admin, _member, contributor) = setup_guild_with_members(&client, &env);
+    let token = create_mock_token(&env, &owner);
+    let jurors = setup_juror_pool(&client, &env, guild_id, &owner, &token, 3);
+    let bounty_id = create_funded_bounty(&client, &env, guild_id, &owner, &owner, &token);
+
+    let reason = String::from_str(&env, "Dispute reason");
+    let evidence = String::from_str(&env, "ipfs://evidence");
+    client.create_dispute(&bounty_id, &contributor, &owner, &reason, &evidence);
+
+    client.unstake_juror(&guild_id, &jurors.get(0).unwrap(), &100);
+}
+
+fn enable_appeals(
+    client: &StellarGuildsContractClient<'_>,
+    guild_id: u64,
+    owner: &Address,
+    token: &Address,
+) {
+    client.set_appeal_config(
+        &guild_id,
+        owner,
+        &AppealConfig {
+            bond_token: token.clone(),
+            base_bond: 10,
+            appeal_window_seconds: 24 * 60 * 60,
+            max_rounds: 2,
+        },
+    );
+}
+
+#[test]
+fn test_appeal_reverses_ruling_and_refunds_bond() {
+    let env = setup_env();
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, owner, admin, member, contributor) = setup_guild_with_members(&client, &env);
+    let member2 = Address::generate(&env);
+    client.add_member(&guild_id, &member2, &Role::Member, &owner);
+
+    let token = create_mock_token(&env, &owner);
+    enable_appeals(&client, guild_id, &owner, &token);
+    let bounty_id = create_funded_bounty(&client, &env, guild_id, &owner, &owner, &token);
+    mint_tokens(&env, &token, &contributor, 10);
+
+    let reason = String::from_str(&env, "Dispute reason");
+    let evidence = String::from_str(&env, "ipfs://evidence");
+    let dispute_id = client.create_dispute(&bounty_id, &contributor, &owner, &reason, &evidence);
+
+    client.cast_dispute_vote(&dispute_id, &admin, &VoteDecision::FavorDefendant);
+    client.cast_dispute_vote(&dispute_id, &member, &VoteDecision::FavorDefendant);
+
+    let mut now = 1000 + 7 * 24 * 60 * 60 + 1;
+    set_ledger_timestamp(&env, now);
+    let resolution = client.resolve_dispute(&dispute_id);
+    assert_eq!(resolution.winner, Some(owner.clone()));
+    assert_eq!(resolution.fund_distribution.len(), 0);
+    assert_eq!(
+        client.get_dispute(&dispute_id).status,
+        DisputeStatus::Appealable
+    );
+    assert_eq!(get_token_balance(&env, &token, &contract_id), 100);
+
+    assert_eq!(client.appeal_dispute(&dispute_id, &contributor), 1);
+    assert_eq!(get_token_balance(&env, &token, &contributor), 0);
+
+    let rounds = client.get_dispute_rounds(&dispute_id);
+    assert_eq!(rounds.len(), 1);
+    assert_eq!(rounds.get(0).unwrap().votes.len(), 2);
+    assert_eq!(rounds.get(0).unwrap().votes_for_defendant, 7);
+
+    // Round 1 needs 45% of members; prior voters may vote again.
+    client.cast_dispute_vote(&dispute_id, &admin, &VoteDecision::FavorPlaintiff);
+    client.cast_dispute_vote(&dispute_id, &member, &VoteDecision::FavorPlaintiff);
+    client.cast_dispute_vote(&dispute_id, &member2, &VoteDecision::FavorPlaintiff);
+
+    now += 7 * 24 * 60 * 60 + 1;
+    set_ledger_timestamp(&env, now);
+    let resolution = client.resolve_dispute(&dispute_id);
+    assert_eq!(resolution.winner, Some(contributor.clone()));
+    assert_eq!(
+        client.get_dispute(&dispute_id).status,
+        DisputeStatus::Resolved
+    );
+
+    // Bounty reward plus the refunded bond
+    assert_eq!(get_token_balance(&env, &token, &contributor), 110);
+}
+
+#[test]
+fn test_unappealed_ruling_finalizes_after_window() {
+    let env = setup_env();
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, owner, admin, member, contributor) = setup_guild_with_members(&client, &env);
+    let token = create_mock_token(&env, &owner);
+    enable_appeals(&client, guild_id, &owner, &token);
+    let bounty_id = create_funded_bounty(&client, &env, guild_id, &owner, &owner, &token);
+
+    let reason = String::from_str(&env, "Dispute reason");
+    let evidence = String::from_str(&env, "ipfs://evidence");
+    let dispute_id = client.create_dispute(&bounty_id, &contributor, &owner, &reason, &evidence);
+
+    client.cast_dispute_vote(&dispute_id, &admin, &VoteDecision::FavorPlaintiff);
+    client.cast_dispute_vote(&dispute_id, &member, &VoteDecision::FavorPlaintiff);
+
+    let deadline = 1000 + 7 * 24 * 60 * 60 + 1;
+    set_ledger_timestamp(&env, deadline);
+    client.resolve_dispute(&dispute_id);
+    assert_eq!(get_token_balance(&env, &token, &contributor), 0);
+
+    set_ledger_timestamp(&env, deadline + 24 * 60 * 60 + 1);
+    let resolution = client.resolve_dispute(&dispute_id);
+    assert_eq!(resolution.fund_distribution.len(), 1);
+    assert_eq!(get_token_balance(&env, &token, &contributor), 100);
+}
+
+#[test]
+#[should_panic(expected = "only the losing party can appeal")]
+fn test_winning_party_cannot_appeal() {
+    let env = setup_env();
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, owner, admin, member, contributor) = setup_guild_with_members(&client, &env);
+    let token = create_mock_token(&env, &owner);
+    enable_appeals(&client, guild_id, &owner, &token);
+    let bounty_id = create_funded_bounty(&client, &env, guild_id, &owner, &owner, &token);
+
+    let reason = String::from_str(&env, "Dispute reason");
+    let evidence = String::from_str(&env, "ipfs://evidence");
+    let dispute_id = client.create_dispute(&bounty_id, &contributor, &owner, &reason, &evidence);
+
+    client.cast_dispute_vote(&dispute_id, &admin, &VoteDecision::FavorPlaintiff);
+    client.cast_dispute_vote(&dispute_id, &member, &VoteDecision::FavorPlaintiff);
+
+    set_ledger_timestamp(&env, 1000 + 7 * 24 * 60 * 60 + 1);
+    client.resolve_dispute(&dispute_id);
+
+    client.appeal_dispute(&dispute_id, &contributor);
+}
+
+#[test]
+fn test_split_ruling_uses_weighted_median_share() {
+    let env = setup_env();
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, owner, admin, member, contributor) = setup_guild_with_members(&client, &env);
+    let member2 = Address::generate(&env);
+    client.add_member(&guild_id, &member2, &Role::Member, &owner);
+
+    let token = create_mock_token(&env, &owner);
+    let bounty_id = create_funded_bounty(&client, &env, guild_id, &owner, &owner, &token);
+
+    let reason = String::from_str(&env, "Dispute reason");
+    let evidence = String::from_str(&env, "ipfs://evidence");
+    let dispute_id = client.create_dispute(&bounty_id, &contributor, &owner, &reason, &evidence);
+
+    // Weights: admin 5, member 2, member2 2 -> median share is admin's 20%
+    client.cast_dispute_split_vote(&dispute_id, &admin, &20);
+    client.cast_dispute_split_vote(&dispute_id, &member, &70);
+    client.cast_dispute_split_vote(&dispute_id, &member2, &40);
+
+    let tally = client.tally_dispute_votes(&dispute_id);
+    assert_eq!(tally.winner, None);
+    assert_eq!(tally.fund_distribution.len(), 2);
+    assert_eq!(tally.fund_distribution.get(0).unwrap().amount, 20);
+    assert_eq!(tally.fund_distribution.get(1).unwrap().amount, 80);
+
+    let before_defendant = get_token_balance(&env, &token, &owner);
+    set_ledger_timestamp(&env, 1000 + 7 * 24 * 60 * 60 + 1);
+    let resolution = client.resolve_dispute(&dispute_id);
+    assert_eq!(resolution.fund_distribution, tally.fund_distribution);
+
+    assert_eq!(get_token_balance(&env, &token, &contributor), 20);
+    assert_eq!(
+        get_token_balance(&env, &token, &owner) - before_defendant,
+        80
+    );
+}
+
+#[test]
+#[should_panic(expected = "invalid split percentage")]
+fn test_split_percentage_above_hundred_fails() {
+    let env = setup_env();
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, owner, admin, _member, contributor) = setup_guild_with_members(&client, &env);
+    let token = create_mock_token(&env, &owner);
+    let bounty_id = create_funded_bounty(&client, &env, guild_id, &owner, &owner, &token);
+
+    let reason = String::from_str(&env, "Dispute reason");
+    let evidence = String::from_str(&env, "ipfs://evidence");
+    let dispute_id = client.create_dispute(&bounty_id, &contributor, &owner, &reason, &evidence);
+
+    client.cast_dispute_split_vote(&dispute_id, &admin, &101);
+}
+
+fn subscribe_and_charge(
+    client: &StellarGuildsContractClient<'_>,
+    env: &Env,
+    guild_id: u64,
+    owner: &Address,
+    subscriber: &Address,
+    token: &Address,
+) -> u64 {
+    let plan_id = client.create_subscription_plan(
+        &guild_id,
+        &String::from_str(env, "Pro"),
+        &String::from_str(env, "Pro plan"),
+        &crate::subscription::types::MembershipTier::Premium,
+        &100i128,
+        &Some(token.clone()),
+        &crate::subscription::types::BillingCycle::Monthly,
+        &Vec::new(env),
+        owner,
+    );
+    mint_tokens(env, token, subscriber, 1000);
+    let subscription_id = client.subscribe(&plan_id, subscriber, &true);
+    client.process_subscription_payment(&subscription_id);
+    subscription_id
+}
+
+#[test]
+fn test_subscription_charge_dispute_refunds_subscriber() {
+    let env = setup_env();
+    // Revenue periods look back a full billing cycle from now.
+    set_ledger_timestamp(&env, 5_000_000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, owner, admin, member, contributor) = setup_guild_with_members(&client, &env);
+    let token = create_mock_token(&env, &owner);
+    let subscription_id =
+        subscribe_and_charge(&client, &env, guild_id, &owner, &contributor, &token);
+    assert_eq!(get_token_balance(&env, &token, &contributor), 900);
+
+    let dispute_id = client.create_operation_dispute(
+        &DisputeReference::Subscription,
+        &subscription_id,
+        &guild_id,
+        &contributor,
+        &String::from_str(&env, "Charged for a cancelled service"),
+        &String::from_str(&env, "ipfs://evidence"),
+    );
+
+    // Further charges are blocked while the dispute is open.
+    assert!(client
+        .try_process_subscription_payment(&subscription_id)
+        .is_err());
+
+    client.cast_dispute_vote(&dispute_id, &admin, &VoteDecision::FavorPlaintiff);
+    client.cast_dispute_vote(&dispute_id, &member, &VoteDecision::FavorPlaintiff);
+
+    set_ledger_timestamp(&env, 5_000_000 + 7 * 24 * 60 * 60 + 1);
+    let resolution = client.resolve_dispute(&dispute_id);
+
+    assert_eq!(resolution.fund_distribution.len(), 1);
+    assert_eq!(get_token_balance(&env, &token, &contributor), 1000);
+    let subscription = client.get_subscription(&subscription_id);
+    assert_eq!(subscription.last_payment_amount, Some(0));
+
+    // Charging resumes once the dispute is closed.
+    assert!(client.process_subscription_payment(&subscription_id));
+}
+
+#[test]
+fn test_routed_subscription_charge_cannot_be_refunded() {
+    let env = setup_env();
+    set_ledger_timestamp(&env, 5_000_000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, owner, _admin, _member, contributor) = setup_guild_with_members(&client, &env);
+    let token = create_mock_token(&env, &owner);
+    let subscription_id =
+        subscribe_and_charge(&client, &env, guild_id, &owner, &contributor, &token);
+
+    // The next charge is routed to the platform in full
+    let plan_id = client.get_subscription(&subscription_id).plan_id;
+    client.set_subscription_revenue_split(&plan_id, &0, &0, &0, &0, &10_000, &owner);
+    let cycle = crate::subscription::types::BillingCycle::Monthly.duration_seconds();
+    client.approve_subscription_billing(&subscription_id, &contributor, &100, &10_000);
+    set_ledger_timestamp(&env, 5_000_000 + cycle);
+    assert!(client.process_subscription_payment(&subscription_id));
+    assert_eq!(get_token_balance(&env, &token, &contributor), 800);
+    assert_eq!(get_token_balance(&env, &token, &contract_id), 100);
+
+    let result = client.try_create_operation_dispute(
+        &DisputeReference::Subscription,
+        &subscription_id,
+        &guild_id,
+        &contributor,
+        &String::from_str(&env, "Charged for a cancelled service"),
+        &String::from_str(&env, "ipfs://evidence"),
+    );
+    assert!(result.is_err());
+
+    assert_eq!(get_token_balance(&env, &token, &contributor), 800);
+    assert_eq!(get_token_balance(&env, &token, &contract_id), 100);
+    assert_eq!(
+        client
+            .get_subscription(&subscription_id)
+            .last_payment_amount,
+        Some(100)
+    );
+}
+
+#[test]
+fn test_payment_pool_dispute_upheld_allows_rerun() {
+    let env = setup_env();
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, owner, admin, member, contributor) = setup_guild_with_members(&client, &env);
+    let token = create_mock_token(&env, &owner);
+    mint_tokens(&env, &token, &contract_id, 100);
+
+    let pool_id = client.create_payment_pool(
+        &100i128,
+        &Some(token.clone()),
+        &crate::payment::types::DistributionRule::Percentage,
+        &owner,
+    );
+    client.add_recipient(&pool_id, &member, &60u32, &owner);
+    client.add_recipient(&pool_id, &contributor, &40u32, &owner);
+
+    let dispute_id = client.create_operation_dispute(
+        &DisputeReference::PaymentPool,
+        &pool_id,
+        &guild_id,
+        &contributor,
+        &String::from_str(&env, "My share should be larger"),
+        &String::from_str(&env, "ipfs://evidence"),
+    );
+
+    assert!(client.try_execute_distribution(&pool_id, &owner).is_err());
+
+    client.cast_dispute_vote(&dispute_id, &admin, &VoteDecision::FavorDefendant);
+    client.cast_dispute_vote(&dispute_id, &member, &VoteDecision::FavorDefendant);
+
+    set_ledger_timestamp(&env, 1000 + 7 * 24 * 60 * 60 + 1);
+    let resolution = client.resolve_dispute(&dispute_id);
+    assert_eq!(resolution.fund_distribution.len(), 0);
+
+    assert!(client.execute_distribution(&pool_id, &owner));
+    assert_eq!(get_token_balance(&env, &token, &contributor), 40);
+}
+
+#[test]
+fn test_treasury_transaction_dispute_blocks_execution() {
+    let env = setup_env();
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, owner, admin, member, contributor) = setup_guild_with_members(&client, &env);
+    let token = create_mock_token(&env, &owner);
+    mint_tokens(&env, &token, &owner, 500);
+
+    let mut signers = Vec::new(&env);
+    signers.push_back(owner.clone());
+    let treasury_id = client.initialize_treasury(&guild_id, &signers, &1u32);
+    client.deposit_treasury(&treasury_id, &owner, &500i128, &Some(token.clone()));
+
+    let tx_id = client.propose_withdrawal(
+        &treasury_id,
+        &owner,
+        &owner,
+        &200i128,
+        &Some(token.clone()),
+        &String::from_str(&env, "Expenses"),
+    );
+
+    let dispute_id = client.create_operation_dispute(
+        &DisputeReference::TreasuryTransaction,
+        &tx_id,
+        &guild_id,
+        &contributor,
+        &String::from_str(&env, "Withdrawal to the proposer"),
+        &String::from_str(&env, "ipfs://evidence"),
+    );
+
+    assert!(client.try_approve_transaction(&tx_id, &owner).is_err());
+
+    client.cast_dispute_vote(&dispute_id, &admin, &VoteDecision::FavorPlaintiff);
+    client.cast_dispute_vote(&dispute_id, &member, &VoteDecision::FavorPlaintiff);
+
+    set_ledger_timestamp(&env, 1000 + 7 * 24 * 60 * 60 + 1);
+    client.resolve_dispute(&dispute_id);
+
+    let history = client.get_transaction_history(&treasury_id, &10u32);
+    let tx = history.iter().find(|t| t.id == tx_id).unwrap();
+    assert_eq!(tx.status, crate::treasury::TransactionStatus::Rejected);
+    assert_eq!(tx.reason, String::from_str(&env, "Expenses"));
+    let rejections = client.get_transaction_rejections(&tx_id);
+    assert_eq!(rejections.len(), 1);
+    assert_eq!(rejections.get(0).unwrap().signer, contributor);
+    assert_eq!(
+        rejections.get(0).unwrap().reason,
+        String::from_str(&env, "Withdrawal to the proposer")
+    );
+    assert!(client.try_execute_transaction(&tx_id, &owner).is_err());
+}
+
+#[test]
+#[should_panic(expected = "only the subscriber can dispute a charge")]
+fn test_subscription_dispute_requires_subscriber() {
+    let env = setup_env();
+    set_ledger_timestamp(&env, 5_000_000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let (guild_id, owner, _admin, member, contributor) = setup_guild_with_members(&client, &env);
+    let token = create_mock_token(&env, &owner);
+    let subscription_id =
+        subscribe_and_charge(&client, &env, guild_id, &owner, &contributor, &token);
+
+    client.create_operation_dispute(
+        &DisputeReference::Subscription,
+        &subscription_id,
+        &guild_id,
+        &member,
+        &String::from_str(&env, "Not my charge"),
+        &String::from_str(&env, "ipfs://evidence"),
+    );
+}
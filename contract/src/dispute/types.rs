@@ -5,6 +5,12 @@
 pub enum DisputeReference {
     Bounty,
     Milestone,
+    /// The last charge taken from a subscription.
+    Subscription,
+    /// The recipient shares of a payment pool awaiting distribution.
+    PaymentPool,
+    /// A treasury transaction awaiting execution.
+    TreasuryTransaction,
 }
 
 #[contracttype]
@@ -12,6 +18,8 @@ pub enum DisputeReference {
 pub enum DisputeStatus {
     Open,
     Voting,
+    /// Ruled on, but the losing party may still appeal until `appeal_deadline`.
+    Appealable,
     Resolved,
     Expired,
 }
@@ -45,6 +53,9 @@ pub struct Dispute {
     pub vote_count: u32,
     pub resolved_at: Option<u64>,
     pub resolution_executed: bool,
+    /// Current round, starting at 0 and increased by each appeal.
+    pub round: u32,
+    pub appeal_deadline: Option<u64>,
 }
 
 #[contracttype]
@@ -55,6 +66,8 @@ pub struct Vote {
     pub decision: VoteDecision,
     pub weight: i128,
     pub timestamp: u64,
+    /// Plaintiff's proposed share (0-100) for `Split` votes.
+    pub split_percentage: Option<u32>,
 }
 
 #[contracttype]
@@ -64,6 +77,62 @@ pub struct FundDistribution {
     pub amount: i128,
 }
 
+/// Juror-pool settings for a guild. When set, each new dispute is decided by
+/// a pseudo-randomly drawn panel of staked jurors instead of all members.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JurorConfig {
+    pub stake_token: Address,
+    pub min_stake: i128,
+    pub panel_size: u32,
+    /// Share of stake (basis points) lost by jurors who miss or oppose the majority.
+    pub slash_bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Juror {
+    pub address: Address,
+    pub guild_id: u64,
+    pub stake: i128,
+    /// Number of unresolved disputes this juror is empanelled on.
+    pub active_panels: u32,
+}
+
+/// Appeal settings for a guild. Without one, disputes are final on resolution.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AppealConfig {
+    pub bond_token: Address,
+    /// Bond for the first appeal; doubles with every further round.
+    pub base_bond: i128,
+    pub appeal_window_seconds: u64,
+    /// Total rounds allowed, including the original ruling.
+    pub max_rounds: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AppealBond {
+    pub round: u32,
+    pub appellant: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
+/// Archived tally and votes of a round that was appealed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeRound {
+    pub round: u32,
+    pub panel: Vec<Address>,
+    pub votes: Vec<Vote>,
+    pub votes_for_plaintiff: i128,
+    pub votes_for_defendant: i128,
+    pub votes_split: i128,
+    pub vote_count: u32,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Resolution {
@@ -123,3 +192,42 @@ pub struct ResolutionExecutedEvent {
 pub struct DisputeExpiredEvent {
     pub dispute_id: u64,
 }
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeConfigUpdatedEvent {
+    pub guild_id: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JurorStakeChangedEvent {
+    pub guild_id: u64,
+    pub juror: Address,
+    pub stake: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PanelDrawnEvent {
+    pub dispute_id: u64,
+    pub jurors: Vec<Address>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JurorSettledEvent {
+    pub dispute_id: u64,
+    pub juror: Address,
+    pub reward: i128,
+    pub slashed: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AppealFiledEvent {
+    pub dispute_id: u64,
+    pub round: u32,
+    pub appellant: Address,
+    pub bond: i128,
+}
@@ -21,17 +21,33 @@ pub fn calculate_vote_weight(env: &Env, guild_id: u64, voter: &soroban_sdk::Addr
 }
 
 /// Cast a weighted vote for a dispute.
+///
+/// `Split` votes may carry the plaintiff's proposed share; the ruling uses the
+/// weighted median of all proposals (50% for votes without one).
 pub fn cast_vote(
     env: &Env,
     dispute_id: u64,
     voter: soroban_sdk::Address,
     decision: VoteDecision,
+    split_percentage: Option<u32>,
 ) -> bool {
     voter.require_auth();
 
+    if let Some(pct) = split_percentage {
+        if decision != VoteDecision::Split {
+            panic!("split percentage only valid for split votes");
+        }
+        if pct > 100 {
+            panic!("invalid split percentage");
+        }
+    }
+
     let mut dispute = storage::get_dispute(env, dispute_id).expect("dispute not found");
 
-    if dispute.status == DisputeStatus::Resolved || dispute.status == DisputeStatus::Expired {
+    if dispute.status == DisputeStatus::Resolved
+        || dispute.status == DisputeStatus::Expired
+        || dispute.status == DisputeStatus::Appealable
+    {
         panic!("dispute is closed");
     }
 
@@ -52,7 +68,16 @@ pub fn cast_vote(
     let _member = guild_storage::get_member(env, dispute.guild_id, &voter)
         .unwrap_or_else(|| panic!("voter must be guild member"));
 
-    let weight = calculate_vote_weight(env, dispute.guild_id, &voter) as i128;
+    // Panel disputes: only drawn jurors vote, one vote each.
+    let weight = match storage::get_panel(env, dispute_id) {
+        Some(panel) => {
+            if !panel.contains(&voter) {
+                panic!("voter not on juror panel");
+            }
+            1
+        }
+        None => calculate_vote_weight(env, dispute.guild_id, &voter) as i128,
+    };
 
     let vote = Vote {
         voter: voter.clone(),
@@ -60,6 +85,7 @@ pub fn cast_vote(
         decision: decision.clone(),
         weight,
         timestamp: now,
+        split_percentage,
     };
 
     storage::store_vote(env, &vote);
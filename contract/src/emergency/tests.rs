@@ -5,7 +5,7 @@ use crate::multisig::storage as multisig_storage;
 use crate::multisig::types::{MultiSigOperation, OperationStatus, OperationType};
 use crate::StellarGuildsContract;
 use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
-use soroban_sdk::{Address, Env, String, Vec};
+use soroban_sdk::{Address, BytesN, Env, String, Vec};
 
 fn set_timestamp(env: &Env, timestamp: u64) {
     env.ledger().set(LedgerInfo {
@@ -32,6 +32,7 @@ fn store_emergency_op(
         account_id: 1,
         op_type,
         description: String::from_str(env, "emergency"),
+        payload_hash: BytesN::from_array(env, &[0u8; 32]),
         proposer: proposer.clone(),
         signatures: Vec::new(env),
         nonce: 0,
@@ -85,6 +85,7 @@ pub const ACT_PROPOSED: &str = "proposed";
 
 pub const ACT_STARTED: &str = "started";
 pub const ACT_COMPLETED: &str = "completed";
+pub const ACT_AUTO_APPROVED: &str = "auto_approved";
 
 // =========== Reputation-specific actions ===========
 
@@ -96,6 +97,11 @@ pub const ACT_BADGE_EARNED: &str = "badge_earned";
 pub const ACT_EVIDENCE: &str = "evidence";
 pub const ACT_VOTE_CAST: &str = "vote_cast";
 pub const ACT_RESOLVED: &str = "resolved";
+pub const ACT_STAKED: &str = "staked";
+pub const ACT_UNSTAKED: &str = "unstaked";
+pub const ACT_PANEL_DRAWN: &str = "panel_drawn";
+pub const ACT_SETTLED: &str = "settled";
+pub const ACT_APPEALED: &str = "appealed";
 
 // =========== Subscription-specific actions ===========
 
@@ -106,6 +112,8 @@ pub const ACT_PAYMENT_PROCESSED: &str = "payment_ok";
 pub const ACT_PAYMENT_FAILED: &str = "payment_fail";
 pub const ACT_PAYMENT_RETRIED: &str = "payment_retry";
 pub const ACT_GRACE_STARTED: &str = "grace_started";
+pub const ACT_REFUNDED: &str = "refunded";
+pub const ACT_REDEEMED: &str = "redeemed";
 
 // =========== Multisig-specific actions ===========
 
@@ -3,7 +3,7 @@ use crate::events::topics::{ACT_EXECUTED, MOD_GOVERNANCE};
 use soroban_sdk::{Address, Env};
 
 use crate::governance::proposals::get_proposal as load_proposal;
-use crate::governance::storage::store_proposal;
+use crate::governance::storage::{get_optimistic_config, get_spend_data, store_proposal};
 use crate::governance::types::{
     ExecutionPayload, Proposal, ProposalExecutedEvent, ProposalStatus, ProposalType,
 };
@@ -36,6 +36,15 @@ pub fn execute_proposal(env: &Env, proposal_id: u64, executor: Address) -> bool
         }
     }
 
+    // Re-check the optimistic spend cap in case it was lowered during the window.
+    if proposal.is_optimistic {
+        if let Some(spend) = get_spend_data(env, proposal_id) {
+            if spend.amount > get_optimistic_config(env, proposal.guild_id).max_spend_amount {
+                panic!("spend amount exceeds optimistic cap");
+            }
+        }
+    }
+
     let success = match (&proposal.proposal_type, &proposal.execution_payload) {
         (ProposalType::TreasurySpend, ExecutionPayload::TreasurySpend) => {
             // High-security action: Relies on the new multisig flow.
@@ -5,11 +5,13 @@ pub mod types;
 pub mod voting;
 
 pub use types::{
-    ExecutionPayload, GovernanceConfig, Proposal, ProposalStatus, ProposalType, VoteDecision,
+    ExecutionPayload, GovernanceConfig, OptimisticConfig, Proposal, ProposalStatus, ProposalType,
+    TreasurySpendData, VoteDecision,
 };
 
 pub use proposals::{
-    cancel_proposal, create_proposal, get_active_proposals, get_proposal, update_governance_config,
+    cancel_proposal, create_optimistic_proposal, create_proposal, get_active_proposals,
+    get_optimistic_config, get_proposal, update_governance_config, update_optimistic_config,
 };
 
 pub use voting::{delegate_vote, finalize_proposal, undelegate_vote, vote};
@@ -3,12 +3,13 @@ use crate::events::topics::{ACT_CANCELLED, ACT_PROPOSED, ACT_UPDATED, MOD_GOVERN
 use soroban_sdk::{Address, Env, String, Vec};
 
 use crate::governance::storage::{
-    get_config, get_guild_proposals, get_next_proposal_id, get_proposal as load_proposal,
-    set_config, store_proposal,
+    get_config, get_guild_proposals, get_next_proposal_id,
+    get_optimistic_config as load_optimistic_config, get_proposal as load_proposal, set_config,
+    set_optimistic_config, store_proposal, store_spend_data,
 };
 use crate::governance::types::{
-    ExecutionPayload, GovernanceConfig, GovernanceConfigUpdatedEvent, Proposal,
-    ProposalCreatedEvent, ProposalStatus, ProposalType,
+    ExecutionPayload, GovernanceConfig, GovernanceConfigUpdatedEvent, OptimisticConfig, Proposal,
+    ProposalCreatedEvent, ProposalStatus, ProposalType, TreasurySpendData,
 };
 use crate::guild::storage as guild_storage;
 use crate::guild::types::Member;
@@ -47,6 +48,16 @@ fn ensure_guild_member(env: &Env, guild_id: u64, address: &Address) {
     }
 }
 
+fn payload_for_type(proposal_type: &ProposalType) -> ExecutionPayload {
+    match proposal_type {
+        ProposalType::TreasurySpend => ExecutionPayload::TreasurySpend,
+        ProposalType::AddMember => ExecutionPayload::AddMember,
+        ProposalType::RemoveMember => ExecutionPayload::RemoveMember,
+        ProposalType::RuleChange => ExecutionPayload::RuleChange,
+        ProposalType::GeneralDecision => ExecutionPayload::GeneralDecision,
+    }
+}
+
 pub fn create_proposal(
     env: &Env,
     guild_id: u64,
@@ -55,6 +66,83 @@ pub fn create_proposal(
     title: String,
     description: String,
     execution_payload: ExecutionPayload,
+) -> u64 {
+    store_new_proposal(
+        env,
+        guild_id,
+        proposer,
+        proposal_type,
+        title,
+        description,
+        execution_payload,
+        false,
+    )
+}
+
+/// Create a proposal that passes at the end of its voting window unless
+/// vetoed. Only proposal types enabled in the guild's `OptimisticConfig`
+/// are accepted, and treasury spends must stay within `max_spend_amount`.
+pub fn create_optimistic_proposal(
+    env: &Env,
+    guild_id: u64,
+    proposer: Address,
+    proposal_type: ProposalType,
+    title: String,
+    description: String,
+    spend: Option<TreasurySpendData>,
+) -> u64 {
+    let opt_cfg = load_optimistic_config(env, guild_id);
+    if !opt_cfg.allowed_types.iter().any(|t| t == proposal_type) {
+        panic!("proposal type not allowed for optimistic governance");
+    }
+
+    match (&proposal_type, &spend) {
+        (ProposalType::TreasurySpend, Some(data)) => {
+            if data.amount <= 0 {
+                panic!("spend amount must be positive");
+            }
+            if data.amount > opt_cfg.max_spend_amount {
+                panic!("spend amount exceeds optimistic cap");
+            }
+        }
+        (ProposalType::TreasurySpend, None) => {
+            panic!("treasury spend data required");
+        }
+        (_, Some(_)) => {
+            panic!("spend data only valid for treasury spend proposals");
+        }
+        _ => {}
+    }
+
+    let payload = payload_for_type(&proposal_type);
+    let id = store_new_proposal(
+        env,
+        guild_id,
+        proposer,
+        proposal_type,
+        title,
+        description,
+        payload,
+        true,
+    );
+
+    if let Some(data) = spend {
+        store_spend_data(env, id, &data);
+    }
+
+    id
+}
+
+#[allow(clippy::too_many_arguments)]
+fn store_new_proposal(
+    env: &Env,
+    guild_id: u64,
+    proposer: Address,
+    proposal_type: ProposalType,
+    title: String,
+    description: String,
+    execution_payload: ExecutionPayload,
+    is_optimistic: bool,
 ) -> u64 {
     proposer.require_auth();
 
@@ -100,6 +188,7 @@ pub fn create_proposal(
         execution_payload,
         passed_at: None,
         executed_at: None,
+        is_optimistic,
     };
 
     store_proposal(env, &proposal);
@@ -190,3 +279,46 @@ pub fn update_governance_config(
 
     true
 }
+
+pub fn update_optimistic_config(
+    env: &Env,
+    guild_id: u64,
+    caller: Address,
+    config: OptimisticConfig,
+) -> bool {
+    // only guild owner can update config
+    let guild =
+        guild_storage::get_guild(env, guild_id).unwrap_or_else(|| panic!("guild not found"));
+
+    if caller != guild.owner {
+        panic!("only guild owner can update governance config");
+    }
+    caller.require_auth();
+
+    if config.veto_threshold_percentage == 0 || config.veto_threshold_percentage > 100 {
+        panic!("invalid veto threshold");
+    }
+    if config.max_spend_amount < 0 {
+        panic!("invalid spend cap");
+    }
+    // membership changes always need a full vote
+    for proposal_type in config.allowed_types.iter() {
+        if !matches!(
+            proposal_type,
+            ProposalType::TreasurySpend | ProposalType::GeneralDecision | ProposalType::RuleChange
+        ) {
+            panic!("proposal type cannot be optimistic");
+        }
+    }
+
+    set_optimistic_config(env, guild_id, &config);
+
+    let event = GovernanceConfigUpdatedEvent { guild_id };
+    emit_event(env, MOD_GOVERNANCE, ACT_UPDATED, event);
+
+    true
+}
+
+pub fn get_optimistic_config(env: &Env, guild_id: u64) -> OptimisticConfig {
+    load_optimistic_config(env, guild_id)
+}
@@ -1,6 +1,8 @@
 ﻿use soroban_sdk::{symbol_short, Address, Env, Map, Symbol, Vec};
 
-use crate::governance::types::{GovernanceConfig, Proposal, Vote};
+use crate::governance::types::{
+    GovernanceConfig, OptimisticConfig, Proposal, TreasurySpendData, Vote,
+};
 
 const PROPOSALS_KEY: Symbol = symbol_short!("g_props");
 const PROPOSAL_COUNTER_KEY: Symbol = symbol_short!("g_pcnt");
@@ -11,6 +13,9 @@ const VOTES_KEY: Symbol = symbol_short!("g_votes");
 const DELEGATIONS_KEY: Symbol = symbol_short!("g_deleg");
 
 const GOV_CONFIG_KEY: Symbol = symbol_short!("g_conf");
+const OPTIMISTIC_CONFIG_KEY: Symbol = symbol_short!("g_optc");
+
+const SPEND_DATA_KEY: Symbol = symbol_short!("g_spend");
 
 pub fn get_next_proposal_id(env: &Env) -> u64 {
     let current: u64 = env
@@ -183,3 +188,49 @@ pub fn set_config(env: &Env, guild_id: u64, config: &GovernanceConfig) {
     configs.set(guild_id, config.clone());
     env.storage().persistent().set(&GOV_CONFIG_KEY, &configs);
 }
+
+pub fn get_optimistic_config(env: &Env, guild_id: u64) -> OptimisticConfig {
+    let configs: Map<u64, OptimisticConfig> = env
+        .storage()
+        .persistent()
+        .get(&OPTIMISTIC_CONFIG_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    configs
+        .get(guild_id)
+        .unwrap_or_else(|| OptimisticConfig::default(env))
+}
+
+pub fn set_optimistic_config(env: &Env, guild_id: u64, config: &OptimisticConfig) {
+    let mut configs: Map<u64, OptimisticConfig> = env
+        .storage()
+        .persistent()
+        .get(&OPTIMISTIC_CONFIG_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    configs.set(guild_id, config.clone());
+    env.storage()
+        .persistent()
+        .set(&OPTIMISTIC_CONFIG_KEY, &configs);
+}
+
+pub fn store_spend_data(env: &Env, proposal_id: u64, data: &TreasurySpendData) {
+    let mut spends: Map<u64, TreasurySpendData> = env
+        .storage()
+        .persistent()
+        .get(&SPEND_DATA_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    spends.set(proposal_id, data.clone());
+    env.storage().persistent().set(&SPEND_DATA_KEY, &spends);
+}
+
+pub fn get_spend_data(env: &Env, proposal_id: u64) -> Option<TreasurySpendData> {
+    let spends: Map<u64, TreasurySpendData> = env
+        .storage()
+        .persistent()
+        .get(&SPEND_DATA_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    spends.get(proposal_id)
+}
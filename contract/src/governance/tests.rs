@@ -1,8 +1,8 @@
 #[cfg(test)]
 mod tests {
     use crate::governance::types::{
-        ExecutionPayload, GovernanceConfig, Proposal, ProposalStatus, ProposalType, Vote,
-        VoteDecision,
+        ExecutionPayload, GovernanceConfig, OptimisticConfig, Proposal, ProposalStatus,
+        ProposalType, TreasurySpendData, Vote, VoteDecision,
     };
     use crate::governance::{proposals, storage};
     use crate::guild::types::Role;
@@ -10,7 +10,7 @@ mod tests {
     use crate::StellarGuildsContract;
     use crate::StellarGuildsContractClient;
     use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
-    use soroban_sdk::{Address, Env, String};
+    use soroban_sdk::{Address, Env, String, Vec};
 
     fn setup_env() -> Env {
         let env = Env::default();
@@ -246,6 +246,7 @@ mod tests {
                 execution_payload: ExecutionPayload::GeneralDecision,
                 passed_at: None,
                 executed_at: None,
+                is_optimistic: false,
             };
 
             storage::store_proposal(&env, &proposal);
@@ -392,4 +393,174 @@ mod tests {
             },
         );
     }
+
+    fn enable_optimistic(
+        env: &Env,
+        client: &StellarGuildsContractClient<'_>,
+        guild_id: u64,
+        owner: &Address,
+    ) {
+        let mut allowed_types = Vec::new(env);
+        allowed_types.push_back(ProposalType::GeneralDecision);
+        allowed_types.push_back(ProposalType::TreasurySpend);
+        client.update_optimistic_config(
+            &guild_id,
+            owner,
+            &OptimisticConfig {
+                allowed_types,
+                veto_threshold_percentage: 20,
+                max_spend_amount: 1_000,
+            },
+        );
+    }
+
+    #[test]
+    fn test_optimistic_proposal_passes_without_votes() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, _admin, member, contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+        enable_optimistic(&env, &client, guild_id, &owner);
+
+        let proposal_id = client.create_optimistic_proposal(
+            &guild_id,
+            &member,
+            &ProposalType::TreasurySpend,
+            &String::from_str(&env, "Routine spend"),
+            &String::from_str(&env, "Pay for hosting"),
+            &Some(TreasurySpendData {
+                treasury_id: 1,
+                amount: 500,
+                token: None,
+                recipient: Address::generate(&env),
+                reason: String::from_str(&env, "hosting"),
+            }),
+        );
+
+        let proposal = client.get_proposal(&proposal_id);
+        assert!(proposal.is_optimistic);
+        assert_eq!(proposal.execution_payload, ExecutionPayload::TreasurySpend);
+
+        // A single contributor veto (weight 1 of 18) stays under the 20% threshold.
+        client.vote(&proposal_id, &contributor, &VoteDecision::Against);
+        set_ledger_timestamp(&env, proposal.voting_end + 1);
+        assert!(client.execute_proposal(&proposal_id, &member));
+        assert_eq!(
+            client.get_proposal(&proposal_id).status,
+            ProposalStatus::Executed
+        );
+    }
+
+    #[test]
+    fn test_optimistic_proposal_rejected_when_vetoed() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (guild_id, admin, member, _contributor) =
+            setup_guild_with_members(&env, &client, &owner);
+        enable_optimistic(&env, &client, guild_id, &owner);
+
+        let proposal_id = client.create_optimistic_proposal(
+            &guild_id,
+            &member,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Routine"),
+            &String::from_str(&env, "Routine decision"),
+            &None,
+        );
+
+        client.vote(&proposal_id, &admin, &VoteDecision::Against);
+
+        let proposal = client.get_proposal(&proposal_id);
+        set_ledger_timestamp(&env, proposal.voting_end + 1);
+
+        let status = client.finalize_proposal(&proposal_id);
+        assert_eq!(status, ProposalStatus::Rejected);
+        assert_eq!(client.get_proposal(&proposal_id).votes_against, 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "spend amount exceeds optimistic cap")]
+    fn test_optimistic_spend_over_cap_rejected() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let guild_id = setup_guild(&client, &env, &owner);
+        enable_optimistic(&env, &client, guild_id, &owner);
+
+        client.create_optimistic_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::TreasurySpend,
+            &String::from_str(&env, "Large spend"),
+            &String::from_str(&env, "Too large"),
+            &Some(TreasurySpendData {
+                treasury_id: 1,
+                amount: 1_001,
+                token: None,
+                recipient: Address::generate(&env),
+                reason: String::from_str(&env, "large"),
+            }),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "proposal type not allowed for optimistic governance")]
+    fn test_optimistic_proposal_requires_enabled_type() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let guild_id = setup_guild(&client, &env, &owner);
+
+        client.create_optimistic_proposal(
+            &guild_id,
+            &owner,
+            &ProposalType::GeneralDecision,
+            &String::from_str(&env, "Not enabled"),
+            &String::from_str(&env, "Optimistic mode is off by default"),
+            &None,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "proposal type cannot be optimistic")]
+    fn test_optimistic_config_rejects_membership_types() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let guild_id = setup_guild(&client, &env, &owner);
+
+        let mut allowed_types = Vec::new(&env);
+        allowed_types.push_back(ProposalType::GeneralDecision);
+        allowed_types.push_back(ProposalType::AddMember);
+        client.update_optimistic_config(
+            &guild_id,
+            &owner,
+            &OptimisticConfig {
+                allowed_types,
+                veto_threshold_percentage: 20,
+                max_spend_amount: 1_000,
+            },
+        );
+    }
 }
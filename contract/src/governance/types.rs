@@ -1,4 +1,4 @@
-﻿use soroban_sdk::{contracttype, Address, String};
+﻿use soroban_sdk::{contracttype, Address, Env, String, Vec};
 
 use crate::guild::types::Role;
 
@@ -52,6 +52,30 @@ impl GovernanceConfig {
     }
 }
 
+/// Settings for optimistic proposals, which pass at the end of their voting
+/// window unless the weight of `Against` votes (vetoes) exceeds the threshold.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OptimisticConfig {
+    /// Proposal types that may be created optimistically. Empty disables the mode;
+    /// membership changes are never allowed.
+    pub allowed_types: Vec<ProposalType>,
+    /// Percentage of total voting weight that vetoes must exceed to reject.
+    pub veto_threshold_percentage: u32,
+    /// Maximum amount an optimistic `TreasurySpend` proposal may request.
+    pub max_spend_amount: i128,
+}
+
+impl OptimisticConfig {
+    pub fn default(env: &Env) -> Self {
+        Self {
+            allowed_types: Vec::new(env),
+            veto_threshold_percentage: 10,
+            max_spend_amount: 0,
+        }
+    }
+}
+
 /// Simple execution payload for Soroban compatibility.
 /// Complex payloads are stored as serialized strings or handled externally.
 #[contracttype]
@@ -124,6 +148,8 @@ pub struct Proposal {
     pub execution_payload: ExecutionPayload,
     pub passed_at: Option<u64>,
     pub executed_at: Option<u64>,
+    /// Passes at `voting_end` unless vetoed; see [`OptimisticConfig`].
+    pub is_optimistic: bool,
 }
 
 #[contracttype]
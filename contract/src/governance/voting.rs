@@ -3,8 +3,8 @@ use crate::events::topics::{ACT_DELEGATED, ACT_FINALIZED, ACT_UPDATED, ACT_VOTED
 use soroban_sdk::{Address, Env};
 
 use crate::governance::storage::{
-    get_all_votes, get_config, get_delegate, get_proposal as load_proposal, remove_delegation,
-    set_delegation, store_proposal, store_vote,
+    get_all_votes, get_config, get_delegate, get_optimistic_config, get_proposal as load_proposal,
+    remove_delegation, set_delegation, store_proposal, store_vote,
 };
 use crate::governance::types::role_weight;
 use crate::governance::types::{
@@ -193,7 +193,20 @@ pub fn finalize_proposal(env: &Env, proposal_id: u64) -> ProposalStatus {
     proposal.votes_against = against_weight;
     proposal.votes_abstain = abstain_weight;
 
-    if total_votes_weight < quorum_threshold {
+    if proposal.is_optimistic {
+        // Optimistic proposals skip quorum: they pass unless vetoes exceed the threshold.
+        let opt_cfg = get_optimistic_config(env, proposal.guild_id);
+        let veto_threshold: i128 =
+            (total_possible_weight * (opt_cfg.veto_threshold_percentage as i128)) / 100;
+        if against_weight > veto_threshold {
+            proposal.status = ProposalStatus::Rejected;
+        } else {
+            proposal.status = ProposalStatus::Passed;
+            if proposal.passed_at.is_none() {
+                proposal.passed_at = Some(now);
+            }
+        }
+    } else if total_votes_weight < quorum_threshold {
         proposal.status = ProposalStatus::Rejected;
     } else {
         let counted = for_weight + against_weight;
@@ -7,6 +7,7 @@ use crate::guild::types::{
     Guild, GuildCreatedEvent, GuildJoinedEvent, Member, MemberAddedEvent, MemberRemovedEvent, Role,
     RoleUpdatedEvent,
 };
+use crate::subscription::types::SubscriptionStatus;
 use soroban_sdk::{Address, Env, String, Vec};
 
 const PERMISSION_UPDATE_INFO: u32 = 1 << 0;
@@ -428,6 +429,17 @@ pub fn has_permission(env: &Env, guild_id: u64, address: Address, required_role:
     }
 }
 
+/// Whether `address` holds a live (active or grace period) subscription to one of the guild's plans
+pub fn has_active_subscription(env: &Env, guild_id: u64, address: Address) -> bool {
+    match crate::subscription::storage::get_user_subscription(env, &address, guild_id) {
+        Some(sub) => matches!(
+            sub.status,
+            SubscriptionStatus::Active | SubscriptionStatus::GracePeriod
+        ),
+        None => false,
+    }
+}
+
 pub fn reque_permissions(
     env: &Env,
     member: &Member,
@@ -0,0 +1,123 @@
+//! Test doubles for the external contracts the guild contract calls into.
+
+pub use swapper::{MockSwapper, MockSwapperClient};
+pub use vault::{MockVault, MockVaultClient};
+
+mod swapper {
+    use soroban_sdk::{contract, contractimpl, symbol_short, token, Address, Env, Symbol};
+
+    use crate::interfaces::swapper::TokenSwapperTrait;
+
+    const PAYEE: Symbol = symbol_short!("payee");
+    const SHORTFALL: Symbol = symbol_short!("shortfall");
+
+    /// Swaps 1:1 out of its own token balances, paying the output to a fixed
+    /// payee. A shortfall makes it deliver less than it reports.
+    #[contract]
+    pub struct MockSwapper;
+
+    #[contractimpl]
+    impl MockSwapper {
+        pub fn init(env: Env, payee: Address) {
+            env.storage().instance().set(&PAYEE, &payee);
+        }
+
+        pub fn set_shortfall(env: Env, shortfall: i128) {
+            env.storage().instance().set(&SHORTFALL, &shortfall);
+        }
+    }
+
+    #[contractimpl]
+    impl TokenSwapperTrait for MockSwapper {
+        fn get_quote(_env: Env, _from_asset: Address, _to_asset: Address, amount: i128) -> i128 {
+            amount
+        }
+
+        fn execute_swap(env: Env, _from: Address, to: Address, amount: i128) -> i128 {
+            let payee: Address = env.storage().instance().get(&PAYEE).unwrap();
+            let shortfall: i128 = env.storage().instance().get(&SHORTFALL).unwrap_or(0);
+            token::Client::new(&env, &to).transfer(
+                &env.current_contract_address(),
+                &payee,
+                &(amount - shortfall),
+            );
+            amount
+        }
+    }
+}
+
+mod vault {
+    use soroban_sdk::{contract, contractimpl, symbol_short, token, Address, Env, Map, Symbol};
+
+    use crate::interfaces::yield_vault::YieldVaultTrait;
+
+    const ASSET: Symbol = symbol_short!("asset");
+    const BALANCES: Symbol = symbol_short!("balances");
+    const SHORTFALL: Symbol = symbol_short!("shortfall");
+
+    /// Holds one asset for its depositors. A shortfall makes `withdraw` send less
+    /// than it reports.
+    #[contract]
+    pub struct MockVault;
+
+    fn balances(env: &Env) -> Map<Address, i128> {
+        env.storage()
+            .instance()
+            .get(&BALANCES)
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    #[contractimpl]
+    impl MockVault {
+        pub fn init(env: Env, asset: Address) {
+            env.storage().instance().set(&ASSET, &asset);
+        }
+
+        /// Credit `amount` of yield to `owner`. The asset backing it must be
+        /// minted to the vault separately.
+        pub fn accrue(env: Env, owner: Address, amount: i128) {
+            let mut map = balances(&env);
+            let current = map.get(owner.clone()).unwrap_or(0);
+            map.set(owner, current + amount);
+            env.storage().instance().set(&BALANCES, &map);
+        }
+
+        pub fn set_shortfall(env: Env, shortfall: i128) {
+            env.storage().instance().set(&SHORTFALL, &shortfall);
+        }
+    }
+
+    #[contractimpl]
+    impl YieldVaultTrait for MockVault {
+        fn deposit(env: Env, from: Address, amount: i128) -> i128 {
+            let mut map = balances(&env);
+            let current = map.get(from.clone()).unwrap_or(0);
+            map.set(from, current + amount);
+            env.storage().instance().set(&BALANCES, &map);
+            amount
+        }
+
+        fn withdraw(env: Env, to: Address, amount: i128) -> i128 {
+            let mut map = balances(&env);
+            let current = map.get(to.clone()).unwrap_or(0);
+            if amount > current {
+                panic!("insufficient vault balance");
+            }
+            map.set(to.clone(), current - amount);
+            env.storage().instance().set(&BALANCES, &map);
+
+            let asset: Address = env.storage().instance().get(&ASSET).unwrap();
+            let shortfall: i128 = env.storage().instance().get(&SHORTFALL).unwrap_or(0);
+            token::Client::new(&env, &asset).transfer(
+                &env.current_contract_address(),
+                &to,
+                &(amount - shortfall),
+            );
+            amount
+        }
+
+        fn balance_of(env: Env, owner: Address) -> i128 {
+            balances(&env).get(owner).unwrap_or(0)
+        }
+    }
+}
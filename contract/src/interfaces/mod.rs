@@ -4,11 +4,14 @@ pub mod dispute;
 pub mod governance;
 pub mod guild;
 pub mod milestone;
+#[cfg(test)]
+pub mod mocks;
 pub mod payment;
 pub mod reputation;
 pub mod subscription;
 pub mod swapper;
 pub mod treasury;
+pub mod yield_vault;
 
 #[cfg(test)]
 mod tests;
@@ -1,4 +1,9 @@
-use soroban_sdk::{contractclient, Address, Env};
+use soroban_sdk::{contractclient, contracttype, token::Client as TokenClient, Address, Env};
+
+use crate::treasury::storage::get_swapper;
+
+/// Fixed-point scale of a conversion's `min_rate`.
+pub const RATE_SCALE: i128 = 10_000_000;
 
 #[contractclient(name = "TokenSwapperClient")]
 pub trait TokenSwapperTrait {
@@ -6,6 +11,75 @@ pub trait TokenSwapperTrait {
     fn execute_swap(env: Env, from: Address, to: Address, amount: i128) -> i128;
 }
 
+/// How a payout is converted before it reaches the recipient.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayoutConversion {
+    /// Asset the payer funds the payout from.
+    pub source_token: Address,
+    /// Asset the recipient is paid in.
+    pub target_token: Address,
+    /// Treasury whose signer-approved swapper runs the conversion.
+    pub treasury_id: u64,
+    /// Least target units accepted per `RATE_SCALE` source units.
+    pub min_rate: i128,
+}
+
+/// The swapper the conversion's treasury has approved.
+pub fn conversion_swapper(env: &Env, conversion: &PayoutConversion) -> Address {
+    get_swapper(env, conversion.treasury_id).expect("no swapper registered")
+}
+
+/// Quote `amount` of `from_asset` in `to_asset` through the conversion's swapper.
+pub fn quote(
+    env: &Env,
+    conversion: &PayoutConversion,
+    from_asset: &Address,
+    to_asset: &Address,
+    amount: i128,
+) -> i128 {
+    TokenSwapperClient::new(env, &conversion_swapper(env, conversion))
+        .get_quote(from_asset, to_asset, &amount)
+}
+
+pub fn validate_conversion(conversion: &PayoutConversion) {
+    if conversion.source_token == conversion.target_token {
+        panic!("conversion tokens must differ");
+    }
+    if conversion.min_rate <= 0 {
+        panic!("minimum rate must be positive");
+    }
+}
+
+/// Swap `amount` of the conversion's source token, held by this contract, into
+/// its target token through the conversion treasury's swapper.
+///
+/// The swapper receives the source tokens before `execute_swap` is called and
+/// must send the target tokens back to this contract. The output is what this
+/// contract's balance actually grew by; it must reach the conversion's minimum
+/// rate. Returns the target amount received.
+pub fn convert_payout(env: &Env, conversion: &PayoutConversion, amount: i128) -> i128 {
+    let swapper = conversion_swapper(env, conversion);
+    let this = env.current_contract_address();
+    let target = TokenClient::new(env, &conversion.target_token);
+    let before = target.balance(&this);
+
+    TokenClient::new(env, &conversion.source_token).transfer(&this, &swapper, &amount);
+    TokenSwapperClient::new(env, &swapper).execute_swap(
+        &conversion.source_token,
+        &conversion.target_token,
+        &amount,
+    );
+
+    let amount_out = target.balance(&this) - before;
+    let min_out = amount * conversion.min_rate / RATE_SCALE;
+    if amount_out <= 0 || amount_out < min_out {
+        panic!("slippage exceeded");
+    }
+
+    amount_out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
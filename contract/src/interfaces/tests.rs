@@ -12,7 +12,9 @@ mod tests {
     use crate::payment::types::DistributionStatus;
     use crate::reputation::types::ReputationProfile;
     use crate::subscription::types::{MembershipTier, Subscription, SubscriptionStatus};
-    use crate::treasury::types::{Transaction, TransactionStatus, TransactionType, Treasury};
+    use crate::treasury::types::{
+        Transaction, TransactionStatus, TransactionType, Treasury, VelocityDecision,
+    };
     use soroban_sdk::testutils::Address as _;
     use soroban_sdk::{contract, contractimpl, Address, Env, Map, String, Vec};
 
@@ -104,6 +106,8 @@ mod tests {
                 vote_count: 0,
                 resolved_at: None,
                 resolution_executed: false,
+                round: 0,
+                appeal_deadline: None,
             }
         }
 
@@ -128,6 +132,7 @@ mod tests {
                 execution_payload: ExecutionPayload::GeneralDecision,
                 passed_at: None,
                 executed_at: None,
+                is_optimistic: false,
             }
         }
 
@@ -151,6 +156,7 @@ mod tests {
                 last_updated_at: 1,
                 version: 1,
                 is_payment_released: false,
+                prerequisites: Vec::new(&env),
             }
         }
 
@@ -187,6 +193,9 @@ mod tests {
                 auto_renew: true,
                 cancelled_at: None,
                 cancellation_reason: None,
+                trial_ends_at: None,
+                coupon_code: None,
+                discount_cycles_remaining: 0,
             }
         }
 
@@ -234,6 +243,7 @@ mod tests {
                     created_at: 1,
                     expires_at: 2,
                     reason: String::from_str(&env, "seed"),
+                    velocity_decision: VelocityDecision::WithinLimits,
                 }],
             )
         }
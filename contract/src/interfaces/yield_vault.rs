@@ -0,0 +1,46 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+/// A single-asset yield vault the treasury can deploy idle funds into.
+///
+/// Callers transfer the vault's asset to it before calling `deposit`, which
+/// credits `from` and returns the amount credited. `withdraw` sends up to
+/// `amount` of the asset back to `to` and returns the amount sent.
+/// `balance_of` reports the current asset value held for `owner`, including
+/// any accrued yield.
+#[contractclient(name = "YieldVaultClient")]
+pub trait YieldVaultTrait {
+    fn deposit(env: Env, from: Address, amount: i128) -> i128;
+    fn withdraw(env: Env, to: Address, amount: i128) -> i128;
+    fn balance_of(env: Env, owner: Address) -> i128;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interfaces::mocks::{MockVault, MockVaultClient};
+    use soroban_sdk::{testutils::Address as _, token};
+
+    #[test]
+    fn test_mock_vault() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let asset = env
+            .register_stellar_asset_contract_v2(admin.clone())
+            .address();
+        let vault_id = env.register_contract(None, MockVault);
+        MockVaultClient::new(&env, &vault_id).init(&asset);
+        let client = YieldVaultClient::new(&env, &vault_id);
+
+        let owner = Address::generate(&env);
+        token::StellarAssetClient::new(&env, &asset).mint(&vault_id, &1000);
+
+        assert_eq!(client.deposit(&owner, &1000), 1000);
+        assert_eq!(client.balance_of(&owner), 1000);
+
+        assert_eq!(client.withdraw(&owner, &400), 400);
+        assert_eq!(client.balance_of(&owner), 600);
+        assert_eq!(token::Client::new(&env, &asset).balance(&owner), 400);
+    }
+}
@@ -1,15 +1,16 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, Address, Env, String, Vec};
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Symbol, Val, Vec};
 
 mod events;
 mod guild;
 mod integration;
 mod interfaces;
+use interfaces::swapper::PayoutConversion;
 mod utils;
 use guild::membership::{
-    add_member, create_guild, get_all_members, get_member, has_permission, is_member, join_guild,
-    remove_member, reque_permissions as guild_reque_permissions,
+    add_member, create_guild, get_all_members, get_member, has_active_subscription, has_permission,
+    is_member, join_guild, remove_member, reque_permissions as guild_reque_permissions,
     update_guild_info as guild_update_guild_info, update_role,
 };
 use guild::storage;
@@ -19,23 +20,37 @@ mod bounty;
 use bounty::{
     approve_bounty, approve_completion, cancel_bounty, claim_bounty, claim_payout, create_bounty,
     expire_bounty, fund_bounty, get_bounty_data, get_guild_bounties_list, release_escrow,
-    submit_work, Bounty, PayoutSplit,
+    set_bounty_payout_conversion, submit_work, Bounty, PayoutSplit,
 };
 
 mod treasury;
 use treasury::{
-    approve_transaction as core_approve_transaction, deposit as core_deposit,
-    emergency_pause as core_emergency_pause, execute_transaction as core_execute_transaction,
-    get_balance as core_get_balance, get_transaction_history as core_get_transaction_history,
+    approve_scheduled_payment as core_approve_scheduled_payment,
+    approve_transaction as core_approve_transaction,
+    cancel_scheduled_payment as core_cancel_scheduled_payment,
+    cancel_transaction as core_cancel_transaction, configure_budget as core_configure_budget,
+    deposit as core_deposit, emergency_pause as core_emergency_pause,
+    execute_transaction as core_execute_transaction, get_balance as core_get_balance,
+    get_balance_detail as core_get_balance_detail,
+    get_transaction_history as core_get_transaction_history,
     grant_allowance as core_grant_allowance, initialize_treasury as core_initialize_treasury,
-    propose_withdrawal as core_propose_withdrawal, set_budget as core_set_budget, Transaction,
+    process_scheduled_payments as core_process_scheduled_payments,
+    propose_batch_withdrawal as core_propose_batch_withdrawal,
+    propose_scheduled_payment as core_propose_scheduled_payment, propose_swap as core_propose_swap,
+    propose_swapper_change as core_propose_swapper_change,
+    propose_vault_deposit as core_propose_vault_deposit,
+    propose_vault_withdrawal as core_propose_vault_withdrawal,
+    propose_withdrawal as core_propose_withdrawal, reject_transaction as core_reject_transaction,
+    set_budget as core_set_budget, set_vault_allocation as core_set_vault_allocation,
+    set_velocity_limit as core_set_velocity_limit, Transaction,
 };
 
 mod analytics;
 use analytics::{
-    compute_budget_utilization, compute_category_breakdown, compute_forecast,
-    compute_spending_summary, compute_trend, get_snapshots, store_snapshot, BudgetUtilization,
-    CategoryBreakdown, SpendingForecast, SpendingSummary, SpendingTrend, TreasurySnapshot,
+    compute_balance_report, compute_budget_utilization, compute_category_breakdown,
+    compute_forecast, compute_spending_summary, compute_trend, get_snapshots, store_snapshot,
+    BudgetUtilization, CategoryBreakdown, SpendingForecast, SpendingSummary, SpendingTrend,
+    TokenBalanceReport, TreasurySnapshot,
 };
 
 mod reputation;
@@ -48,22 +63,32 @@ use reputation::{
 
 mod governance;
 use governance::{
-    cancel_proposal as gov_cancel_proposal, create_proposal as gov_create_proposal,
-    delegate_vote as gov_delegate_vote, execute_proposal as gov_execute_proposal,
-    finalize_proposal as gov_finalize_proposal, get_active_proposals as gov_get_active_proposals,
-    get_proposal as gov_get_proposal, undelegate_vote as gov_undelegate_vote,
-    update_governance_config as gov_update_governance_config, vote as gov_vote, ExecutionPayload,
-    GovernanceConfig, Proposal, ProposalStatus, ProposalType, VoteDecision,
+    cancel_proposal as gov_cancel_proposal,
+    create_optimistic_proposal as gov_create_optimistic_proposal,
+    create_proposal as gov_create_proposal, delegate_vote as gov_delegate_vote,
+    execute_proposal as gov_execute_proposal, finalize_proposal as gov_finalize_proposal,
+    get_active_proposals as gov_get_active_proposals,
+    get_optimistic_config as gov_get_optimistic_config, get_proposal as gov_get_proposal,
+    undelegate_vote as gov_undelegate_vote,
+    update_governance_config as gov_update_governance_config,
+    update_optimistic_config as gov_update_optimistic_config, vote as gov_vote, ExecutionPayload,
+    GovernanceConfig, OptimisticConfig, Proposal, ProposalStatus, ProposalType, TreasurySpendData,
+    VoteDecision,
 };
 
 mod milestone;
 use milestone::{
     add_milestone as ms_add_milestone, approve_milestone as ms_approve_milestone,
-    cancel_project as ms_cancel_project, create_project as ms_create_project,
-    extend_milestone_deadline as ms_extend_deadline, get_milestone_view as ms_get_milestone,
-    get_project_progress as ms_get_progress, reject_milestone as ms_reject_milestone,
-    release_milestone_payment as ms_release_payment, start_milestone as ms_start_milestone,
-    submit_milestone as ms_submit_milestone, Milestone, MilestoneInput,
+    approve_project_funding as ms_approve_project_funding,
+    auto_approve_milestone as ms_auto_approve_milestone, cancel_project as ms_cancel_project,
+    create_project as ms_create_project, extend_milestone_deadline as ms_extend_deadline,
+    get_milestone_view as ms_get_milestone, get_project_progress as ms_get_progress,
+    get_project_progress_detail as ms_get_progress_detail, reject_milestone as ms_reject_milestone,
+    release_milestone_payment as ms_release_payment, review_milestone as ms_review_milestone,
+    set_project_payout_conversion as ms_set_payout_conversion,
+    set_review_policy as ms_set_review_policy, set_review_sla as ms_set_review_sla,
+    start_milestone as ms_start_milestone, submit_milestone as ms_submit_milestone, Milestone,
+    MilestoneInput,
 };
 
 mod payment;
@@ -77,24 +102,35 @@ use payment::{
 
 mod subscription;
 use subscription::{
-    cancel_subscription as sub_cancel_subscription, change_tier as sub_change_tier,
+    approve_billing as sub_approve_billing, cancel_subscription as sub_cancel_subscription,
+    change_tier as sub_change_tier, create_coupon as sub_create_coupon,
     create_plan as sub_create_plan, days_until_billing as sub_days_until_billing,
+    get_revenue_report as sub_get_revenue_report,
     get_subscription_status as sub_get_subscription_status,
     is_subscription_active as sub_is_subscription_active,
     pause_subscription as sub_pause_subscription,
     process_due_subscriptions as sub_process_due_subscriptions,
     process_payment as sub_process_payment, resume_subscription as sub_resume_subscription,
-    retry_payment as sub_retry_payment, subscribe as sub_subscribe, BillingCycle, MembershipTier,
-    ProrationResult, Subscription, SubscriptionChange, SubscriptionError, SubscriptionPlan,
-    SubscriptionStatus,
+    retry_payment as sub_retry_payment, set_plan_role as sub_set_plan_role,
+    set_plan_trial as sub_set_plan_trial, set_revenue_split as sub_set_revenue_split,
+    subscribe as sub_subscribe, BillingCycle, Coupon, DiscountKind, MembershipTier,
+    ProrationResult, RevenueReport, RevenueSplit, Subscription, SubscriptionChange,
+    SubscriptionError, SubscriptionPlan, SubscriptionStatus,
 };
 
 mod dispute;
 use dispute::{
-    calculate_vote_weight as dispute_calculate_vote_weight, cast_vote as dispute_cast_vote,
-    create_dispute as dispute_create_dispute, execute_resolution as dispute_execute_resolution,
-    resolve_dispute as dispute_resolve_dispute, submit_evidence as dispute_submit_evidence,
-    tally_votes as dispute_tally_votes,
+    appeal_dispute as dispute_appeal_dispute,
+    calculate_vote_weight as dispute_calculate_vote_weight,
+    cast_split_vote as dispute_cast_split_vote, cast_vote as dispute_cast_vote,
+    create_dispute as dispute_create_dispute,
+    create_operation_dispute as dispute_create_operation_dispute,
+    execute_resolution as dispute_execute_resolution, get_dispute_panel as dispute_get_panel,
+    get_dispute_rounds as dispute_get_rounds, get_juror as dispute_get_juror,
+    resolve_dispute as dispute_resolve_dispute, set_appeal_config as dispute_set_appeal_config,
+    set_juror_config as dispute_set_juror_config, stake_juror as dispute_stake_juror,
+    submit_evidence as dispute_submit_evidence, tally_votes as dispute_tally_votes,
+    unstake_juror as dispute_unstake_juror,
 };
 
 mod allowance;
@@ -118,25 +154,32 @@ use multisig::{
     // Signing aliases
     ms_cancel_operation as internal_cancel_operation,
     ms_check_and_expire as internal_check_and_expire,
+    ms_consume_executed_operation as internal_consume_executed_operation,
     ms_emergency_expire_operation as internal_emergency_expire_operation,
     ms_emergency_extend_timeout as internal_emergency_extend_timeout,
     ms_execute_operation as internal_execute_operation,
+    ms_execute_with_signatures as internal_execute_with_signatures,
     ms_freeze_account as internal_freeze_account,
     // Policy aliases
     ms_get_operation_policy as internal_get_operation_policy,
+    ms_get_operation_result as internal_get_operation_result,
     ms_get_operation_status as internal_get_operation_status,
     ms_get_pending_operations as internal_get_pending_operations,
     ms_get_safe_account as internal_get_safe_account,
     ms_list_accounts_by_owner as internal_list_accounts_by_owner,
+    ms_payload_hash as internal_payload_hash,
+    ms_propose_contract_call as internal_propose_contract_call,
     ms_propose_operation as internal_propose_operation,
     ms_register_account as internal_register_account,
     ms_remove_signer as internal_remove_signer,
-    ms_require_executed_operation as internal_require_executed_operation,
     ms_reset_operation_policy as internal_reset_operation_policy,
     ms_rotate_signer as internal_rotate_signer,
+    ms_set_allowed_call as internal_set_allowed_call,
     ms_set_operation_policy as internal_set_operation_policy,
+    ms_set_signer_key as internal_set_signer_key,
 
     ms_sign_operation as internal_sign_operation,
+    ms_signing_digest as internal_signing_digest,
     ms_sweep_expired_operations as internal_sweep_expired_operations,
 
     ms_unfreeze_account as internal_unfreeze_account,
@@ -145,8 +188,10 @@ use multisig::{
     // Types
     MultiSigAccount,
     MultiSigOperation,
+    OperationPayload,
     OperationPolicy,
     OperationType,
+    SignerSignature,
 };
 
 mod upgrade;
@@ -560,6 +605,19 @@ impl StellarGuildsContract {
         is_member(&env, guild_id, address)
     }
 
+    /// Check if an address holds an active or grace period subscription to
+    /// one of the guild's plans
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    /// * `address` - The address to check
+    ///
+    /// # Returns
+    /// true if the subscription is live, false otherwise
+    pub fn has_active_guild_subscription(env: Env, guild_id: u64, address: Address) -> bool {
+        has_active_subscription(&env, guild_id, address)
+    }
+
     /// Join an existing guild as a member
     ///
     /// The caller must sign the transaction. They will be added with
@@ -646,6 +704,7 @@ impl StellarGuildsContract {
                     9 => "TransferFailed",
                     10 => "ArithmeticOverflow",
                     11 => "InvalidAmount",
+                    12 => "DisputeActive",
                     _ => "Unknown error",
                 };
                 panic!("{}", msg);
@@ -675,6 +734,7 @@ impl StellarGuildsContract {
                     9 => "TransferFailed",
                     10 => "ArithmeticOverflow",
                     11 => "InvalidAmount",
+                    12 => "DisputeActive",
                     _ => "Unknown error",
                 };
                 panic!("{}", msg);
@@ -698,6 +758,7 @@ impl StellarGuildsContract {
                     9 => "TransferFailed",
                     10 => "ArithmeticOverflow",
                     11 => "InvalidAmount",
+                    12 => "DisputeActive",
                     _ => "Unknown error",
                 };
                 panic!("{}", msg);
@@ -721,6 +782,7 @@ impl StellarGuildsContract {
                     9 => "TransferFailed",
                     10 => "ArithmeticOverflow",
                     11 => "InvalidAmount",
+                    12 => "DisputeActive",
                     _ => "Unknown error",
                 };
                 panic!("{}", msg);
@@ -744,6 +806,7 @@ impl StellarGuildsContract {
                     9 => "TransferFailed",
                     10 => "ArithmeticOverflow",
                     11 => "InvalidAmount",
+                    12 => "DisputeActive",
                     _ => "Unknown error",
                 };
                 panic!("{}", msg);
@@ -767,6 +830,7 @@ impl StellarGuildsContract {
                     9 => "TransferFailed",
                     10 => "ArithmeticOverflow",
                     11 => "InvalidAmount",
+                    12 => "DisputeActive",
                     _ => "Unknown error",
                 };
                 panic!("{}", msg);
@@ -798,6 +862,7 @@ impl StellarGuildsContract {
                     9 => "TransferFailed",
                     10 => "ArithmeticOverflow",
                     11 => "InvalidAmount",
+                    12 => "DisputeActive",
                     _ => "Unknown error",
                 };
                 panic!("{}", msg);
@@ -848,6 +913,40 @@ impl StellarGuildsContract {
         )
     }
 
+    /// Create a dispute over a subscription charge, payment pool or
+    /// treasury transaction. The referenced operation is blocked until the
+    /// dispute closes.
+    ///
+    /// # Arguments
+    /// * `reference_type` - `Subscription`, `PaymentPool` or `TreasuryTransaction`
+    /// * `reference_id` - Subscription, pool or transaction ID
+    /// * `guild_id` - Guild that judges the dispute
+    /// * `plaintiff` - Address opening the dispute
+    /// * `reason` - Dispute reason
+    /// * `evidence_url` - Initial evidence URL
+    ///
+    /// # Returns
+    /// The ID of the newly created dispute
+    pub fn create_operation_dispute(
+        env: Env,
+        reference_type: dispute::types::DisputeReference,
+        reference_id: u64,
+        guild_id: u64,
+        plaintiff: Address,
+        reason: String,
+        evidence_url: String,
+    ) -> u64 {
+        dispute_create_operation_dispute(
+            &env,
+            reference_type,
+            reference_id,
+            guild_id,
+            plaintiff,
+            reason,
+            evidence_url,
+        )
+    }
+
     /// Submit evidence for an active dispute
     pub fn submit_evidence(
         env: Env,
@@ -868,6 +967,16 @@ impl StellarGuildsContract {
         dispute_cast_vote(&env, dispute_id, voter, decision)
     }
 
+    /// Cast a Split vote proposing the plaintiff's share (0-100 percent)
+    pub fn cast_dispute_split_vote(
+        env: Env,
+        dispute_id: u64,
+        voter: Address,
+        split_percentage: u32,
+    ) -> bool {
+        dispute_cast_split_vote(&env, dispute_id, voter, split_percentage)
+    }
+
     /// Calculate voting weight for a guild member
     pub fn calculate_dispute_vote_weight(env: Env, guild_id: u64, voter: Address) -> u32 {
         dispute_calculate_vote_weight(&env, guild_id, voter)
@@ -896,6 +1005,56 @@ impl StellarGuildsContract {
             .unwrap_or_else(|| panic!("dispute not found"))
     }
 
+    /// Enable or update juror-pool mode for a guild (owner only)
+    pub fn set_juror_config(
+        env: Env,
+        guild_id: u64,
+        caller: Address,
+        config: dispute::types::JurorConfig,
+    ) -> bool {
+        dispute_set_juror_config(&env, guild_id, caller, config)
+    }
+
+    /// Stake tokens to join a guild's juror pool
+    pub fn stake_juror(env: Env, guild_id: u64, juror: Address, amount: i128) -> i128 {
+        dispute_stake_juror(&env, guild_id, juror, amount)
+    }
+
+    /// Withdraw juror stake when not sitting on an active panel
+    pub fn unstake_juror(env: Env, guild_id: u64, juror: Address, amount: i128) -> i128 {
+        dispute_unstake_juror(&env, guild_id, juror, amount)
+    }
+
+    /// Get a juror's stake record
+    pub fn get_juror(env: Env, guild_id: u64, juror: Address) -> dispute::types::Juror {
+        dispute_get_juror(&env, guild_id, juror)
+    }
+
+    /// Get the juror panel drawn for a dispute
+    pub fn get_dispute_panel(env: Env, dispute_id: u64) -> Vec<Address> {
+        dispute_get_panel(&env, dispute_id)
+    }
+
+    /// Enable or update dispute appeals for a guild (owner only)
+    pub fn set_appeal_config(
+        env: Env,
+        guild_id: u64,
+        caller: Address,
+        config: dispute::types::AppealConfig,
+    ) -> bool {
+        dispute_set_appeal_config(&env, guild_id, caller, config)
+    }
+
+    /// Appeal a dispute ruling by posting a bond; opens a new voting round
+    pub fn appeal_dispute(env: Env, dispute_id: u64, appellant: Address) -> u32 {
+        dispute_appeal_dispute(&env, dispute_id, appellant)
+    }
+
+    /// Get the archived votes and tallies of each appealed round
+    pub fn get_dispute_rounds(env: Env, dispute_id: u64) -> Vec<dispute::types::DisputeRound> {
+        dispute_get_rounds(&env, dispute_id)
+    }
+
     // ============ Treasury Functions ============
 
     /// Initialize a new treasury for a guild
@@ -968,6 +1127,33 @@ impl StellarGuildsContract {
         )
     }
 
+    /// Propose paying several recipients in one withdrawal
+    ///
+    /// # Arguments
+    /// * `treasury_id` - The ID of the treasury
+    /// * `proposer` - Address proposing the batch
+    /// * `legs` - Recipient, token and amount of each payment
+    /// * `reason` - Reason for the batch
+    ///
+    /// # Returns
+    /// The ID of the proposed transaction
+    pub fn propose_batch_withdrawal(
+        env: Env,
+        treasury_id: u64,
+        proposer: Address,
+        legs: Vec<treasury::types::BatchLeg>,
+        reason: String,
+    ) -> u64 {
+        core_propose_batch_withdrawal(&env, treasury_id, proposer, legs, reason)
+    }
+
+    /// Get the legs of a batch withdrawal and, once executed, the transaction
+    /// recorded for each leg
+    pub fn get_batch_withdrawal(env: Env, tx_id: u64) -> treasury::types::BatchWithdrawal {
+        crate::treasury::storage::get_batch(&env, tx_id)
+            .unwrap_or_else(|| panic!("batch not found"))
+    }
+
     /// Approve a proposed transaction
     ///
     /// # Arguments
@@ -980,6 +1166,40 @@ impl StellarGuildsContract {
         core_approve_transaction(&env, tx_id, approver)
     }
 
+    /// Vote to reject a pending transaction
+    ///
+    /// # Arguments
+    /// * `tx_id` - The ID of the transaction to reject
+    /// * `signer` - Signer voting to reject
+    /// * `reason` - Reason for the rejection
+    ///
+    /// # Returns
+    /// `true` if the vote was recorded
+    pub fn reject_transaction(env: Env, tx_id: u64, signer: Address, reason: String) -> bool {
+        core_reject_transaction(&env, tx_id, signer, reason)
+    }
+
+    /// Withdraw a pending transaction
+    ///
+    /// # Arguments
+    /// * `tx_id` - The ID of the transaction to withdraw
+    /// * `proposer` - The transaction's proposer
+    /// * `reason` - Reason for withdrawing it
+    ///
+    /// # Returns
+    /// `true` if the transaction was cancelled
+    pub fn cancel_transaction(env: Env, tx_id: u64, proposer: Address, reason: String) -> bool {
+        core_cancel_transaction(&env, tx_id, proposer, reason)
+    }
+
+    /// Get the rejection votes cast on a transaction, with their reasons
+    pub fn get_transaction_rejections(
+        env: Env,
+        tx_id: u64,
+    ) -> Vec<treasury::types::TransactionRejection> {
+        crate::treasury::storage::get_rejections(&env, tx_id)
+    }
+
     /// Execute an approved transaction
     ///
     /// # Arguments
@@ -1014,6 +1234,44 @@ impl StellarGuildsContract {
         core_set_budget(&env, treasury_id, caller, category, amount, period_seconds)
     }
 
+    /// Configure carry-over, hierarchy and mode for a treasury budget
+    ///
+    /// # Arguments
+    /// * `treasury_id` - The ID of the treasury
+    /// * `category` - Budget category name
+    /// * `carry_over_cap` - Most unused allocation carried into the next period (0 disables)
+    /// * `parent` - Parent category whose budget this category's spend also counts against
+    /// * `mode` - `Hard` to fail on overspend, `Soft` to allow it with approval from all signers
+    /// * `caller` - Address making the request (must be signer)
+    ///
+    /// # Returns
+    /// `true` if the budget was configured
+    pub fn configure_budget(
+        env: Env,
+        treasury_id: u64,
+        category: String,
+        carry_over_cap: i128,
+        parent: Option<String>,
+        mode: treasury::types::BudgetMode,
+        caller: Address,
+    ) -> bool {
+        core_configure_budget(
+            &env,
+            treasury_id,
+            caller,
+            category,
+            carry_over_cap,
+            parent,
+            mode,
+        )
+    }
+
+    /// Get a treasury budget, including its current period's spend
+    pub fn get_budget(env: Env, treasury_id: u64, category: String) -> treasury::types::Budget {
+        crate::treasury::storage::get_budget(&env, treasury_id, &category)
+            .unwrap_or_else(|| panic!("budget not found"))
+    }
+
     /// Get treasury balance for a token
     ///
     /// # Arguments
@@ -1021,11 +1279,27 @@ impl StellarGuildsContract {
     /// * `token` - Token address (None for XLM)
     ///
     /// # Returns
-    /// The balance amount
+    /// The liquid balance amount, excluding funds deployed to yield vaults
     pub fn get_treasury_balance(env: Env, treasury_id: u64, token: Option<Address>) -> i128 {
         core_get_balance(&env, treasury_id, token)
     }
 
+    /// Get treasury balance for a token, split into liquid and deployed funds
+    ///
+    /// # Arguments
+    /// * `treasury_id` - The ID of the treasury
+    /// * `token` - Token address (None for XLM)
+    ///
+    /// # Returns
+    /// `TreasuryBalance` with the liquid balance and the principal deployed to vaults
+    pub fn get_treasury_balance_detail(
+        env: Env,
+        treasury_id: u64,
+        token: Option<Address>,
+    ) -> treasury::types::TreasuryBalance {
+        core_get_balance_detail(&env, treasury_id, token)
+    }
+
     pub fn get_treasury(env: Env, treasury_id: u64) -> treasury::types::Treasury {
         crate::treasury::storage::get_treasury(&env, treasury_id)
             .unwrap_or_else(|| panic!("treasury not found"))
@@ -1075,6 +1349,242 @@ impl StellarGuildsContract {
         )
     }
 
+    /// Propose the swapper contract a treasury trades through
+    ///
+    /// # Arguments
+    /// * `treasury_id` - The ID of the treasury
+    /// * `proposer` - Signer proposing the change
+    /// * `swapper` - Contract implementing `TokenSwapperTrait`
+    /// * `reason` - Reason for the change
+    ///
+    /// # Returns
+    /// The ID of the proposed transaction
+    pub fn propose_swapper_change(
+        env: Env,
+        treasury_id: u64,
+        proposer: Address,
+        swapper: Address,
+        reason: String,
+    ) -> u64 {
+        core_propose_swapper_change(&env, treasury_id, proposer, swapper, reason)
+    }
+
+    /// Propose swapping one held token for another
+    ///
+    /// # Arguments
+    /// * `treasury_id` - The ID of the treasury
+    /// * `proposer` - Signer proposing the swap
+    /// * `from_token` - Token to sell
+    /// * `to_token` - Token to buy
+    /// * `amount` - Amount of `from_token` to sell
+    /// * `min_amount_out` - Minimum amount of `to_token` accepted
+    /// * `reason` - Reason for the swap
+    ///
+    /// # Returns
+    /// The ID of the proposed swap transaction
+    #[allow(clippy::too_many_arguments)]
+    pub fn propose_swap(
+        env: Env,
+        treasury_id: u64,
+        proposer: Address,
+        from_token: Address,
+        to_token: Address,
+        amount: i128,
+        min_amount_out: i128,
+        reason: String,
+    ) -> u64 {
+        core_propose_swap(
+            &env,
+            treasury_id,
+            proposer,
+            from_token,
+            to_token,
+            amount,
+            min_amount_out,
+            reason,
+        )
+    }
+
+    /// Get the output side of a swap transaction
+    pub fn get_swap_order(env: Env, tx_id: u64) -> treasury::types::SwapOrder {
+        crate::treasury::storage::get_swap_order(&env, tx_id)
+            .unwrap_or_else(|| panic!("swap order not found"))
+    }
+
+    /// Whitelist a yield vault for a token or update its allocation cap
+    ///
+    /// # Arguments
+    /// * `treasury_id` - The ID of the treasury
+    /// * `vault` - Contract implementing `YieldVaultTrait`
+    /// * `token` - The vault's underlying token
+    /// * `max_allocation_bps` - Cap on principal as a share of total token holdings (0 removes the vault from the whitelist)
+    /// * `owner` - Treasury owner making the request
+    ///
+    /// # Returns
+    /// `true` if the vault was updated
+    pub fn set_vault_allocation(
+        env: Env,
+        treasury_id: u64,
+        vault: Address,
+        token: Address,
+        max_allocation_bps: u32,
+        owner: Address,
+    ) -> bool {
+        core_set_vault_allocation(&env, treasury_id, owner, vault, token, max_allocation_bps)
+    }
+
+    /// Propose deploying treasury funds into a whitelisted yield vault
+    ///
+    /// # Arguments
+    /// * `treasury_id` - The ID of the treasury
+    /// * `proposer` - Signer proposing the deposit
+    /// * `vault` - Whitelisted vault to deposit into
+    /// * `amount` - Amount of the vault's token to deploy
+    /// * `reason` - Reason for the deposit
+    ///
+    /// # Returns
+    /// The ID of the proposed transaction
+    pub fn propose_vault_deposit(
+        env: Env,
+        treasury_id: u64,
+        proposer: Address,
+        vault: Address,
+        amount: i128,
+        reason: String,
+    ) -> u64 {
+        core_propose_vault_deposit(&env, treasury_id, proposer, vault, amount, reason)
+    }
+
+    /// Propose recalling treasury funds from a yield vault
+    ///
+    /// # Arguments
+    /// * `treasury_id` - The ID of the treasury
+    /// * `proposer` - Signer proposing the withdrawal
+    /// * `vault` - Vault holding the treasury's position
+    /// * `amount` - Amount to recall, which may include accrued yield
+    /// * `reason` - Reason for the withdrawal
+    ///
+    /// # Returns
+    /// The ID of the proposed transaction
+    pub fn propose_vault_withdrawal(
+        env: Env,
+        treasury_id: u64,
+        proposer: Address,
+        vault: Address,
+        amount: i128,
+        reason: String,
+    ) -> u64 {
+        core_propose_vault_withdrawal(&env, treasury_id, proposer, vault, amount, reason)
+    }
+
+    /// Get the whitelisted vaults of a treasury and the principal deployed to each
+    pub fn get_vault_allocations(
+        env: Env,
+        treasury_id: u64,
+    ) -> Vec<treasury::types::VaultAllocation> {
+        crate::treasury::storage::get_vault_allocations(&env, treasury_id).values()
+    }
+
+    /// Set a rolling-window velocity limit on treasury outflows
+    ///
+    /// # Arguments
+    /// * `treasury_id` - The ID of the treasury
+    /// * `scope` - Whether the window covers the whole treasury, each proposing signer or each recipient
+    /// * `max_amount` - Maximum outflow within the window (0 removes the limit)
+    /// * `token` - Token whose outflows the limit counts (None for native XLM)
+    /// * `window_seconds` - Length of the rolling window in seconds
+    /// * `action` - Escalate to all signers or block when exceeded
+    /// * `owner` - Treasury owner making the request
+    ///
+    /// # Returns
+    /// `true` if the limit was updated successfully
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_velocity_limit(
+        env: Env,
+        treasury_id: u64,
+        scope: treasury::types::VelocityScope,
+        max_amount: i128,
+        token: Option<Address>,
+        window_seconds: u64,
+        action: treasury::types::VelocityAction,
+        owner: Address,
+    ) -> bool {
+        core_set_velocity_limit(
+            &env,
+            treasury_id,
+            owner,
+            scope,
+            max_amount,
+            token,
+            window_seconds,
+            action,
+        )
+    }
+
+    /// Get the velocity limits configured for a treasury
+    pub fn get_velocity_limits(env: Env, treasury_id: u64) -> Vec<treasury::types::VelocityLimit> {
+        crate::treasury::storage::get_velocity_limits(&env, treasury_id)
+    }
+
+    /// Propose a recurring payment from a treasury
+    ///
+    /// # Arguments
+    /// * `treasury_id` - The ID of the treasury
+    /// * `proposer` - Signer proposing the schedule (counts as the first approval)
+    /// * `terms` - Recipient, amount, token, interval, start/end, occurrence cap and budget category
+    ///
+    /// # Returns
+    /// The ID of the new schedule
+    pub fn propose_scheduled_payment(
+        env: Env,
+        treasury_id: u64,
+        proposer: Address,
+        terms: treasury::types::ScheduleTerms,
+    ) -> u64 {
+        core_propose_scheduled_payment(&env, treasury_id, proposer, terms)
+    }
+
+    /// Approve a pending or active scheduled payment
+    ///
+    /// # Arguments
+    /// * `schedule_id` - The ID of the schedule
+    /// * `approver` - Treasury signer approving the schedule
+    ///
+    /// # Returns
+    /// `true` if the approval was recorded
+    pub fn approve_scheduled_payment(env: Env, schedule_id: u64, approver: Address) -> bool {
+        core_approve_scheduled_payment(&env, schedule_id, approver)
+    }
+
+    /// Cancel a pending or active scheduled payment
+    ///
+    /// # Arguments
+    /// * `schedule_id` - The ID of the schedule
+    /// * `signer` - Treasury signer cancelling the schedule
+    ///
+    /// # Returns
+    /// `true` if the schedule was cancelled
+    pub fn cancel_scheduled_payment(env: Env, schedule_id: u64, signer: Address) -> bool {
+        core_cancel_scheduled_payment(&env, schedule_id, signer)
+    }
+
+    /// Keeper entrypoint: pay up to `max_count` due scheduled payments
+    ///
+    /// # Arguments
+    /// * `max_count` - Maximum number of due schedules to process
+    ///
+    /// # Returns
+    /// The number of payments made
+    pub fn process_scheduled_payments(env: Env, max_count: u32) -> u32 {
+        core_process_scheduled_payments(&env, max_count)
+    }
+
+    /// Get a scheduled payment by ID
+    pub fn get_scheduled_payment(env: Env, schedule_id: u64) -> treasury::types::ScheduledPayment {
+        crate::treasury::storage::get_schedule(&env, schedule_id)
+            .unwrap_or_else(|| panic!("schedule not found"))
+    }
+
     /// Emergency pause treasury operations
     ///
     /// # Arguments
@@ -1242,6 +1752,14 @@ impl StellarGuildsContract {
         compute_category_breakdown(&env, treasury_id, period_start, period_end)
     }
 
+    /// Get liquid and vault-deployed holdings for every token of a treasury.
+    ///
+    /// # Returns
+    /// `Vec<TokenBalanceReport>` with XLM first, then each token held or deployed
+    pub fn get_balance_report(env: Env, treasury_id: u64) -> Vec<TokenBalanceReport> {
+        compute_balance_report(&env, treasury_id)
+    }
+
     /// Compare spending between two time periods.
     ///
     /// # Returns
@@ -1422,9 +1940,11 @@ impl StellarGuildsContract {
     /// * `amount` - Payment amount for this milestone
     /// * `deadline` - Deadline timestamp
     /// * `caller` - Address making the request (must be guild admin)
+    /// * `prerequisites` - Milestones of this project that must be approved first
     ///
     /// # Returns
     /// The ID of the newly created milestone
+    #[allow(clippy::too_many_arguments)]
     pub fn add_milestone(
         env: Env,
         project_id: u64,
@@ -1433,6 +1953,7 @@ impl StellarGuildsContract {
         amount: i128,
         deadline: u64,
         caller: Address,
+        prerequisites: Vec<u64>,
     ) -> u64 {
         ms_add_milestone(
             &env,
@@ -1442,6 +1963,7 @@ impl StellarGuildsContract {
             amount,
             deadline,
             caller,
+            prerequisites,
         )
     }
 
@@ -1473,7 +1995,7 @@ impl StellarGuildsContract {
     ///
     /// # Arguments
     /// * `milestone_id` - The ID of the milestone
-    /// * `approver` - Address of the approver (must be guild admin)
+    /// * `approver` - Guild admin, or a project reviewer when reviewers are set
     ///
     /// # Returns
     /// `true` if successful
@@ -1485,7 +2007,7 @@ impl StellarGuildsContract {
     ///
     /// # Arguments
     /// * `milestone_id` - The ID of the milestone
-    /// * `approver` - Address of the approver (must be guild admin)
+    /// * `approver` - Guild admin, or a project reviewer when reviewers are set
     /// * `reason` - Reason for rejection
     ///
     /// # Returns
@@ -1499,6 +2021,93 @@ impl StellarGuildsContract {
         ms_reject_milestone(&env, milestone_id, approver, reason)
     }
 
+    /// Review the current version of a submitted milestone
+    ///
+    /// # Arguments
+    /// * `milestone_id` - The ID of the milestone
+    /// * `reviewer` - Project reviewer, or guild admin when no reviewers are set
+    /// * `approve` - `true` to approve, `false` to reject
+    /// * `comment` - Review comment (required when rejecting)
+    ///
+    /// # Returns
+    /// `true` if the review was recorded
+    pub fn review_milestone(
+        env: Env,
+        milestone_id: u64,
+        reviewer: Address,
+        approve: bool,
+        comment: String,
+    ) -> bool {
+        ms_review_milestone(&env, milestone_id, reviewer, approve, comment)
+    }
+
+    /// Set the reviewer set and approval quorum for a project's milestones
+    ///
+    /// # Arguments
+    /// * `project_id` - The ID of the project
+    /// * `caller` - Guild admin making the request
+    /// * `reviewers` - Reviewer addresses (empty clears the policy)
+    /// * `required_approvals` - Approvals needed per milestone version
+    ///
+    /// # Returns
+    /// `true` if successful
+    pub fn set_milestone_reviewers(
+        env: Env,
+        project_id: u64,
+        caller: Address,
+        reviewers: Vec<Address>,
+        required_approvals: u32,
+    ) -> bool {
+        ms_set_review_policy(&env, project_id, caller, reviewers, required_approvals)
+    }
+
+    /// Set the review SLA after which a silent submission can be auto-approved
+    ///
+    /// # Arguments
+    /// * `project_id` - The ID of the project
+    /// * `caller` - Guild admin making the request
+    /// * `sla_seconds` - Review window after submission (0 clears it)
+    ///
+    /// # Returns
+    /// `true` if successful
+    pub fn set_milestone_review_sla(
+        env: Env,
+        project_id: u64,
+        caller: Address,
+        sla_seconds: u64,
+    ) -> bool {
+        ms_set_review_sla(&env, project_id, caller, sla_seconds)
+    }
+
+    /// Auto-approve and pay a submission left unreviewed past the project's SLA
+    ///
+    /// # Arguments
+    /// * `milestone_id` - The ID of the milestone
+    /// * `caller` - Any address triggering the approval
+    ///
+    /// # Returns
+    /// `true` if the milestone was approved
+    pub fn auto_approve_milestone(env: Env, milestone_id: u64, caller: Address) -> bool {
+        ms_auto_approve_milestone(&env, milestone_id, caller)
+    }
+
+    /// Get the reviewer set for a project's milestones
+    pub fn get_milestone_reviewers(
+        env: Env,
+        project_id: u64,
+    ) -> Option<milestone::types::ReviewPolicy> {
+        crate::milestone::storage::get_review_policy(&env, project_id)
+    }
+
+    /// Get the reviews, with comments and rejection reasons, for one milestone version
+    pub fn get_milestone_reviews(
+        env: Env,
+        milestone_id: u64,
+        version: u32,
+    ) -> Vec<milestone::types::MilestoneReview> {
+        crate::milestone::storage::get_reviews(&env, milestone_id, version)
+    }
+
     /// Get project progress statistics
     ///
     /// # Arguments
@@ -1510,6 +2119,20 @@ impl StellarGuildsContract {
         ms_get_progress(&env, project_id)
     }
 
+    /// Get project progress along with milestones blocked on prerequisites
+    ///
+    /// # Arguments
+    /// * `project_id` - The ID of the project
+    ///
+    /// # Returns
+    /// Completed, total and percentage counts plus blocked milestone IDs
+    pub fn get_project_progress_detail(
+        env: Env,
+        project_id: u64,
+    ) -> milestone::types::ProjectProgress {
+        ms_get_progress_detail(&env, project_id)
+    }
+
     /// Get milestone details
     ///
     /// # Arguments
@@ -1532,6 +2155,24 @@ impl StellarGuildsContract {
         ms_release_payment(&env, milestone_id)
     }
 
+    /// Convert a project's milestone payments into another asset on release
+    ///
+    /// # Arguments
+    /// * `project_id` - The ID of the project
+    /// * `caller` - Guild admin making the request
+    /// * `conversion` - Source and target assets, the project treasury and a minimum rate (None clears it)
+    ///
+    /// # Returns
+    /// `true` if successful
+    pub fn set_project_payout_conversion(
+        env: Env,
+        project_id: u64,
+        caller: Address,
+        conversion: Option<PayoutConversion>,
+    ) -> bool {
+        ms_set_payout_conversion(&env, project_id, caller, conversion)
+    }
+
     /// Extend the deadline of a milestone
     ///
     /// # Arguments
@@ -1550,6 +2191,32 @@ impl StellarGuildsContract {
         ms_extend_deadline(&env, milestone_id, new_deadline, caller)
     }
 
+    /// Approve reserving treasury funds for a project
+    ///
+    /// # Arguments
+    /// * `project_id` - The ID of the project
+    /// * `signer` - Signer of the project's treasury
+    ///
+    /// # Returns
+    /// `true` if successful; funds are committed once the treasury threshold is met
+    pub fn approve_project_funding(env: Env, project_id: u64, signer: Address) -> bool {
+        ms_approve_project_funding(&env, project_id, signer)
+    }
+
+    /// Get the funding reservation for a project
+    ///
+    /// # Arguments
+    /// * `project_id` - The ID of the project
+    ///
+    /// # Returns
+    /// The approvals and remaining reserved amount, if funding was requested
+    pub fn get_project_funding(
+        env: Env,
+        project_id: u64,
+    ) -> Option<milestone::types::ProjectFunding> {
+        crate::milestone::storage::get_funding(&env, project_id)
+    }
+
     /// Cancel a project
     ///
     /// # Arguments
@@ -1707,6 +2374,68 @@ impl StellarGuildsContract {
         gov_update_governance_config(&env, guild_id, caller, config)
     }
 
+    /// Create an optimistic proposal that passes at the end of its voting
+    /// window unless vetoes (`Against` votes) exceed the guild's veto threshold
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    /// * `proposer` - Address of the proposer
+    /// * `proposal_type` - Type of the proposal (must be enabled for optimistic mode)
+    /// * `title` - Proposal title
+    /// * `description` - Detailed description
+    /// * `spend` - Spend details, required for `TreasurySpend` proposals
+    ///
+    /// # Returns
+    /// The ID of the newly created proposal
+    pub fn create_optimistic_proposal(
+        env: Env,
+        guild_id: u64,
+        proposer: Address,
+        proposal_type: ProposalType,
+        title: String,
+        description: String,
+        spend: Option<TreasurySpendData>,
+    ) -> u64 {
+        gov_create_optimistic_proposal(
+            &env,
+            guild_id,
+            proposer,
+            proposal_type,
+            title,
+            description,
+            spend,
+        )
+    }
+
+    /// Update optimistic governance configuration
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    /// * `caller` - Address making the request (must be owner)
+    /// * `config` - New optimistic configuration
+    ///
+    /// # Returns
+    /// `true` if successful
+    pub fn update_optimistic_config(
+        env: Env,
+        guild_id: u64,
+        caller: Address,
+        config: OptimisticConfig,
+    ) -> bool {
+        gov_update_optimistic_config(&env, guild_id, caller, config)
+    }
+
+    /// Get the optimistic governance configuration of a guild
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    ///
+    /// # Returns
+    /// The guild's OptimisticConfig
+    pub fn get_optimistic_config(env: Env, guild_id: u64) -> OptimisticConfig {
+        gov_get_optimistic_config(&env, guild_id)
+    }
+
     // ============ Bounty Escrow Functions ============
 
     /// Create a new bounty
@@ -1817,6 +2546,24 @@ impl StellarGuildsContract {
         release_escrow(&env, bounty_id)
     }
 
+    /// Convert a bounty's escrowed reward into another asset on release
+    ///
+    /// # Arguments
+    /// * `bounty_id` - The ID of the bounty
+    /// * `caller` - Bounty creator or guild admin
+    /// * `conversion` - Target asset, guild treasury and minimum rate; the source must be the bounty token (None clears it)
+    ///
+    /// # Returns
+    /// `true` if successful
+    pub fn set_bounty_payout_conversion(
+        env: Env,
+        bounty_id: u64,
+        caller: Address,
+        conversion: Option<PayoutConversion>,
+    ) -> bool {
+        set_bounty_payout_conversion(&env, bounty_id, caller, conversion)
+    }
+
     /// Cancel a bounty and refund escrowed funds
     ///
     /// # Arguments
@@ -1905,9 +2652,17 @@ impl StellarGuildsContract {
         }
     }
 
-    /// Add a new signer to a multi-sig account (owner only).
-    pub fn ms_add_signer(env: Env, account_id: u64, new_signer: Address, caller: Address) -> bool {
-        match internal_add_signer(&env, account_id, new_signer, caller) {
+    /// Add a signer with a signing weight, or re-weight an existing one, and
+    /// set the new threshold (owner only).
+    pub fn ms_add_signer(
+        env: Env,
+        account_id: u64,
+        new_signer: Address,
+        weight: u32,
+        caller: Address,
+        new_threshold: u32,
+    ) -> bool {
+        match internal_add_signer(&env, account_id, new_signer, weight, caller, new_threshold) {
             Ok(()) => true,
             Err(e) => panic!("ms_add_signer error: {}", e as u32),
         }
@@ -1927,21 +2682,45 @@ impl StellarGuildsContract {
         }
     }
 
-    /// Atomically replace a compromised signer key with a new one (owner only).
+    /// Atomically replace a compromised signer key with a new one and set the
+    /// new threshold (owner only).
     pub fn ms_rotate_signer(
         env: Env,
         account_id: u64,
         old_signer: Address,
         new_signer: Address,
+        weight: u32,
         caller: Address,
+        new_threshold: u32,
     ) -> bool {
-        match internal_rotate_signer(&env, account_id, old_signer, new_signer, caller) {
+        match internal_rotate_signer(
+            &env,
+            account_id,
+            old_signer,
+            new_signer,
+            weight,
+            caller,
+            new_threshold,
+        ) {
             Ok(()) => true,
             Err(e) => panic!("ms_rotate_signer error: {}", e as u32),
         }
     }
 
-    /// Update the signing threshold for an account (owner only).
+    /// Register the caller's ed25519 public key for off-chain signing.
+    pub fn ms_set_signer_key(
+        env: Env,
+        account_id: u64,
+        signer: Address,
+        public_key: BytesN<32>,
+    ) -> bool {
+        match internal_set_signer_key(&env, account_id, signer, public_key) {
+            Ok(()) => true,
+            Err(e) => panic!("ms_set_signer_key error: {}", e),
+        }
+    }
+
+    /// Update the signing threshold, a sum of signer weights (owner only).
     pub fn ms_update_threshold(
         env: Env,
         account_id: u64,
@@ -1986,19 +2765,72 @@ impl StellarGuildsContract {
     // Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬ Multi-Sig Operations Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬Ã¢â€â‚¬
 
     /// Propose a new operation requiring multi-sig approval.
+    ///
+    /// `payload_hash` commits the operation to exact call arguments; see
+    /// `ms_hash_payload`.
     pub fn ms_propose_operation(
         env: Env,
         account_id: u64,
         operation_type: OperationType,
         description: String,
+        payload_hash: BytesN<32>,
         proposer: Address,
     ) -> u64 {
-        match internal_propose_operation(&env, account_id, operation_type, description, proposer) {
+        match internal_propose_operation(
+            &env,
+            account_id,
+            operation_type,
+            description,
+            payload_hash,
+            proposer,
+        ) {
             Ok(id) => id,
             Err(e) => panic!("ms_propose_operation error: {}", e as u32),
         }
     }
 
+    /// Allow or disallow multisig contract invocations of `function` on
+    /// `target` (contract admin only). Token transfer, approve and burn
+    /// functions can never be allowed.
+    pub fn ms_set_allowed_call(
+        env: Env,
+        target: Address,
+        function: Symbol,
+        allowed: bool,
+        caller: Address,
+    ) -> bool {
+        match internal_set_allowed_call(&env, target, function, allowed, caller) {
+            Ok(()) => true,
+            Err(e) => panic!("ms_set_allowed_call error: {}", e),
+        }
+    }
+
+    /// Propose a contract invocation that this contract performs once the
+    /// operation is executed. The account must be bound to a guild or owned
+    /// by the contract admin, and the call must be allowed by the admin.
+    pub fn ms_propose_contract_call(
+        env: Env,
+        account_id: u64,
+        description: String,
+        target: Address,
+        function: Symbol,
+        args: Vec<Val>,
+        proposer: Address,
+    ) -> u64 {
+        match internal_propose_contract_call(
+            &env,
+            account_id,
+            description,
+            target,
+            function,
+            args,
+            proposer,
+        ) {
+            Ok(id) => id,
+            Err(e) => panic!("ms_propose_contract_call error: {}", e),
+        }
+    }
+
     /// Submit a signature for a pending operation.
     pub fn ms_sign_operation(env: Env, operation_id: u64, signer: Address) -> u32 {
         match internal_sign_operation(&env, operation_id, signer) {
@@ -2007,7 +2839,8 @@ impl StellarGuildsContract {
         }
     }
 
-    /// Execute a fully-signed operation.
+    /// Execute a fully-signed operation. Contract invocations are performed
+    /// here and their return value recorded.
     pub fn ms_execute_operation(env: Env, operation_id: u64, executor: Address) -> bool {
         match internal_execute_operation(&env, operation_id, executor) {
             Ok(()) => true,
@@ -2015,8 +2848,36 @@ impl StellarGuildsContract {
         }
     }
 
+    /// Digest that signers sign off-chain for `ms_execute_with_signatures`.
+    pub fn ms_signing_digest(env: Env, operation_id: u64) -> BytesN<32> {
+        match internal_signing_digest(&env, operation_id) {
+            Ok(digest) => digest,
+            Err(e) => panic!("ms_signing_digest error: {}", e),
+        }
+    }
+
+    /// Verify a batch of off-chain ed25519 signatures, add them to the
+    /// operation and execute it.
+    pub fn ms_execute_with_signatures(
+        env: Env,
+        operation_id: u64,
+        signatures: Vec<SignerSignature>,
+        submitter: Address,
+    ) -> bool {
+        match internal_execute_with_signatures(&env, operation_id, signatures, submitter) {
+            Ok(()) => true,
+            Err(e) => panic!("ms_execute_with_signatures error: {}", e),
+        }
+    }
+
+    /// Compute the payload hash to commit to when proposing a gated operation.
+    pub fn ms_hash_payload(env: Env, payload: OperationPayload) -> BytesN<32> {
+        internal_payload_hash(&env, &payload)
+    }
+
     /// Execute a treasury withdrawal proposal only after a multisig treasury operation is executed.
     /// This preserves backward compatibility while enabling strict multisig-gated flows.
+    /// The operation must commit to these exact arguments and is consumed on use.
     pub fn ms_propose_treasury_withdrawal(
         env: Env,
         multisig_operation_id: u64,
@@ -2027,10 +2888,17 @@ impl StellarGuildsContract {
         token: Option<Address>,
         reason: String,
     ) -> u64 {
-        if let Err(e) = internal_require_executed_operation(
+        let payload = OperationPayload::TreasuryWithdrawal(
+            treasury_id,
+            recipient.clone(),
+            amount,
+            token.clone(),
+        );
+        if let Err(e) = internal_consume_executed_operation(
             &env,
             multisig_operation_id,
             OperationType::TreasuryWithdrawal,
+            &payload,
         ) {
             panic!("ms_propose_treasury_withdrawal gate error: {}", e as u32);
         }
@@ -2046,17 +2914,19 @@ impl StellarGuildsContract {
         )
     }
 
-    /// Execute a governance proposal only after an executed governance multisig operation.
+    /// Execute a governance proposal only after an executed governance multisig operation
+    /// that committed to `proposal_id`. The operation is consumed on use.
     pub fn ms_execute_governance_proposal(
         env: Env,
         multisig_operation_id: u64,
         proposal_id: u64,
         executor: Address,
     ) -> bool {
-        if let Err(e) = internal_require_executed_operation(
+        if let Err(e) = internal_consume_executed_operation(
             &env,
             multisig_operation_id,
             OperationType::GovernanceUpdate,
+            &OperationPayload::GovernanceExecution(proposal_id),
         ) {
             panic!("ms_execute_governance_proposal gate error: {}", e as u32);
         }
@@ -2092,6 +2962,11 @@ impl StellarGuildsContract {
         }
     }
 
+    /// Return value recorded for an executed contract invocation, if any.
+    pub fn ms_get_operation_result(env: Env, operation_id: u64) -> Option<Val> {
+        internal_get_operation_result(&env, operation_id)
+    }
+
     /// List all currently pending (non-expired) operations for an account.
     pub fn ms_get_pending_ops(env: Env, account_id: u64) -> Vec<MultiSigOperation> {
         internal_get_pending_operations(&env, account_id)
@@ -2228,12 +3103,204 @@ impl StellarGuildsContract {
     /// The ID of the newly created subscription
     pub fn subscribe(env: Env, plan_id: u64, subscriber: Address, auto_renew: bool) -> u64 {
         subscriber.require_auth();
-        match sub_subscribe(&env, plan_id, subscriber, auto_renew) {
+        match sub_subscribe(&env, plan_id, subscriber, auto_renew, None) {
+            Ok(id) => id,
+            Err(e) => panic!("subscribe error: {}", e as u32),
+        }
+    }
+
+    /// Subscribe to a plan redeeming a discount code
+    ///
+    /// # Arguments
+    /// * `plan_id` - ID of the plan to subscribe to
+    /// * `subscriber` - Address subscribing
+    /// * `auto_renew` - Whether to auto-renew
+    /// * `coupon_code` - Discount code created for the plan
+    ///
+    /// # Returns
+    /// The ID of the newly created subscription
+    pub fn subscribe_with_coupon(
+        env: Env,
+        plan_id: u64,
+        subscriber: Address,
+        auto_renew: bool,
+        coupon_code: String,
+    ) -> u64 {
+        subscriber.require_auth();
+        match sub_subscribe(&env, plan_id, subscriber, auto_renew, Some(coupon_code)) {
             Ok(id) => id,
             Err(e) => panic!("subscribe error: {}", e as u32),
         }
     }
 
+    /// Approve the contract to pull renewals from the subscriber's tokens
+    ///
+    /// Call after subscribing so keepers can bill due renewals unattended.
+    ///
+    /// # Arguments
+    /// * `subscription_id` - ID of the subscription
+    /// * `caller` - Subscriber granting the allowance
+    /// * `amount` - Total amount renewals may pull (0 revokes)
+    /// * `expiration_ledger` - Ledger sequence at which the allowance lapses
+    ///
+    /// # Returns
+    /// true if successful
+    pub fn approve_subscription_billing(
+        env: Env,
+        subscription_id: u64,
+        caller: Address,
+        amount: i128,
+        expiration_ledger: u32,
+    ) -> bool {
+        caller.require_auth();
+        match sub_approve_billing(&env, subscription_id, caller, amount, expiration_ledger) {
+            Ok(result) => result,
+            Err(e) => panic!("approve_billing error: {}", e as u32),
+        }
+    }
+
+    /// Set the free trial length for a subscription plan
+    ///
+    /// # Arguments
+    /// * `plan_id` - ID of the plan
+    /// * `trial_seconds` - Trial length in seconds (0 disables the trial)
+    /// * `caller` - Plan creator
+    ///
+    /// # Returns
+    /// true if successful
+    pub fn set_plan_trial(env: Env, plan_id: u64, trial_seconds: u64, caller: Address) -> bool {
+        caller.require_auth();
+        match sub_set_plan_trial(&env, plan_id, trial_seconds, caller) {
+            Ok(result) => result,
+            Err(e) => panic!("set_plan_trial error: {}", e as u32),
+        }
+    }
+
+    /// Create a discount code for a subscription plan
+    ///
+    /// # Arguments
+    /// * `plan_id` - ID of the plan the coupon applies to
+    /// * `code` - Code subscribers redeem at subscribe time
+    /// * `kind` - Percent (basis points) or fixed discount
+    /// * `value` - Discount value
+    /// * `cycles` - Number of billing cycles the discount lasts
+    /// * `max_uses` - Maximum number of redemptions
+    /// * `expires_at` - Timestamp after which the code can't be redeemed
+    /// * `caller` - Plan creator
+    ///
+    /// # Returns
+    /// true if successful
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_subscription_coupon(
+        env: Env,
+        plan_id: u64,
+        code: String,
+        kind: DiscountKind,
+        value: i128,
+        cycles: u32,
+        max_uses: u32,
+        expires_at: u64,
+        caller: Address,
+    ) -> bool {
+        caller.require_auth();
+        match sub_create_coupon(
+            &env, plan_id, code, kind, value, cycles, max_uses, expires_at, caller,
+        ) {
+            Ok(result) => result,
+            Err(e) => panic!("create_coupon error: {}", e as u32),
+        }
+    }
+
+    /// Get a subscription plan's coupon by code
+    pub fn get_subscription_coupon(env: Env, plan_id: u64, code: String) -> Option<Coupon> {
+        subscription::storage::get_coupon(&env, plan_id, &code)
+    }
+
+    /// Set the guild role a subscription plan grants its subscribers
+    ///
+    /// # Arguments
+    /// * `plan_id` - ID of the plan
+    /// * `role` - `Member` or `Contributor`, or None to stop granting a role
+    /// * `caller` - Guild admin
+    ///
+    /// # Returns
+    /// true if successful
+    pub fn set_plan_role(env: Env, plan_id: u64, role: Option<Role>, caller: Address) -> bool {
+        caller.require_auth();
+        match sub_set_plan_role(&env, plan_id, role, caller) {
+            Ok(result) => result,
+            Err(e) => panic!("set_plan_role error: {}", e as u32),
+        }
+    }
+
+    /// Get the guild role a subscription plan grants its subscribers
+    pub fn get_plan_role(env: Env, plan_id: u64) -> Option<Role> {
+        subscription::storage::get_plan_role(&env, plan_id)
+    }
+
+    /// Set how a plan's revenue is split on each successful payment
+    ///
+    /// # Arguments
+    /// * `plan_id` - ID of the plan
+    /// * `treasury_id` - Treasury of the plan's guild (0 for none)
+    /// * `treasury_bps` - Treasury share in basis points
+    /// * `pool_id` - Pending contributor payment pool (0 for none)
+    /// * `pool_bps` - Pool share in basis points
+    /// * `platform_bps` - Platform fee in basis points
+    /// * `caller` - Plan creator
+    ///
+    /// # Returns
+    /// true if successful
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_subscription_revenue_split(
+        env: Env,
+        plan_id: u64,
+        treasury_id: u64,
+        treasury_bps: u32,
+        pool_id: u64,
+        pool_bps: u32,
+        platform_bps: u32,
+        caller: Address,
+    ) -> bool {
+        caller.require_auth();
+        match sub_set_revenue_split(
+            &env,
+            plan_id,
+            treasury_id,
+            treasury_bps,
+            pool_id,
+            pool_bps,
+            platform_bps,
+            caller,
+        ) {
+            Ok(result) => result,
+            Err(e) => panic!("set_revenue_split error: {}", e as u32),
+        }
+    }
+
+    /// Get a subscription plan's revenue split
+    pub fn get_subscription_revenue_split(env: Env, plan_id: u64) -> Option<RevenueSplit> {
+        subscription::storage::get_revenue_split(&env, plan_id)
+    }
+
+    /// Get a guild's subscription revenue report
+    ///
+    /// # Arguments
+    /// * `guild_id` - The ID of the guild
+    /// * `timestamp` - Any time within the 30-day reporting period
+    /// * `token` - Token the revenue was collected in (None for native XLM)
+    ///
+    /// # Returns
+    /// Collected revenue and how it was routed during the period
+    pub fn get_subscription_revenue_report(
+        env: Env,
+        guild_id: u64,
+        timestamp: u64,
+        token: Option<Address>,
+    ) -> RevenueReport {
+        sub_get_revenue_report(&env, guild_id, timestamp, token)
+    }
+
     /// Process a subscription payment
     ///
     /// # Arguments
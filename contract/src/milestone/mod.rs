@@ -13,8 +13,10 @@ pub mod types;
 
 // Re-export main functions
 pub use tracker::{
-    add_milestone, approve_milestone, cancel_project, create_project, extend_milestone_deadline,
-    get_milestone_view, get_project_progress, reject_milestone, release_milestone_payment,
+    add_milestone, approve_milestone, approve_project_funding, auto_approve_milestone,
+    cancel_project, create_project, extend_milestone_deadline, get_milestone_view,
+    get_project_progress, get_project_progress_detail, reject_milestone, release_milestone_payment,
+    review_milestone, set_project_payout_conversion, set_review_policy, set_review_sla,
     start_milestone, submit_milestone,
 };
 #[allow(unused_imports)]
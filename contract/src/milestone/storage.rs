@@ -1,6 +1,7 @@
 ﻿use soroban_sdk::{contracttype, Env, Vec};
 
-use crate::milestone::types::{Milestone, Project};
+use crate::interfaces::swapper::PayoutConversion;
+use crate::milestone::types::{Milestone, MilestoneReview, Project, ProjectFunding, ReviewPolicy};
 
 #[contracttype]
 pub enum MilestoneStorageKey {
@@ -9,6 +10,11 @@ pub enum MilestoneStorageKey {
     Project(u64),
     Milestone(u64),
     ProjectMilestones(u64), // Vec<milestone_id>
+    PayoutConversion(u64),  // project_id
+    Funding(u64),           // project_id
+    ReviewPolicy(u64),      // project_id
+    Reviews(u64, u32),      // (milestone_id, version) -> Vec<MilestoneReview>
+    ReviewSla(u64),         // project_id -> seconds
 }
 
 #[allow(dead_code)]
@@ -92,3 +98,72 @@ pub fn get_project_milestone_ids(env: &Env, project_id: u64) -> Vec<u64> {
         .get(&key)
         .unwrap_or(Vec::new(env))
 }
+
+pub fn set_payout_conversion(env: &Env, project_id: u64, conversion: &Option<PayoutConversion>) {
+    let key = MilestoneStorageKey::PayoutConversion(project_id);
+    match conversion {
+        Some(c) => env.storage().persistent().set(&key, c),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+pub fn get_payout_conversion(env: &Env, project_id: u64) -> Option<PayoutConversion> {
+    env.storage()
+        .persistent()
+        .get(&MilestoneStorageKey::PayoutConversion(project_id))
+}
+
+pub fn store_funding(env: &Env, funding: &ProjectFunding) {
+    env.storage()
+        .persistent()
+        .set(&MilestoneStorageKey::Funding(funding.project_id), funding);
+}
+
+pub fn get_funding(env: &Env, project_id: u64) -> Option<ProjectFunding> {
+    env.storage()
+        .persistent()
+        .get(&MilestoneStorageKey::Funding(project_id))
+}
+
+pub fn set_review_policy(env: &Env, project_id: u64, policy: &Option<ReviewPolicy>) {
+    let key = MilestoneStorageKey::ReviewPolicy(project_id);
+    match policy {
+        Some(p) => env.storage().persistent().set(&key, p),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+pub fn get_review_policy(env: &Env, project_id: u64) -> Option<ReviewPolicy> {
+    env.storage()
+        .persistent()
+        .get(&MilestoneStorageKey::ReviewPolicy(project_id))
+}
+
+pub fn add_review(env: &Env, milestone_id: u64, version: u32, review: &MilestoneReview) {
+    let key = MilestoneStorageKey::Reviews(milestone_id, version);
+    let mut reviews = get_reviews(env, milestone_id, version);
+    reviews.push_back(review.clone());
+    env.storage().persistent().set(&key, &reviews);
+}
+
+pub fn get_reviews(env: &Env, milestone_id: u64, version: u32) -> Vec<MilestoneReview> {
+    env.storage()
+        .persistent()
+        .get(&MilestoneStorageKey::Reviews(milestone_id, version))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn set_review_sla(env: &Env, project_id: u64, sla_seconds: u64) {
+    let key = MilestoneStorageKey::ReviewSla(project_id);
+    if sla_seconds == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &sla_seconds);
+    }
+}
+
+pub fn get_review_sla(env: &Env, project_id: u64) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&MilestoneStorageKey::ReviewSla(project_id))
+}
@@ -6,12 +6,15 @@
 //! NOTE: Payment release tests are excluded as they require treasury integration.
 
 use crate::guild::types::Role;
+use crate::interfaces::mocks::{MockSwapper, MockSwapperClient};
+use crate::interfaces::swapper::{PayoutConversion, RATE_SCALE};
 use crate::milestone::types::{MilestoneInput, MilestoneStatus};
+use crate::treasury::types::BudgetMode;
 use crate::InitializerProof;
 use crate::StellarGuildsContract;
 use crate::StellarGuildsContractClient;
 use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
-use soroban_sdk::{Address, Env, String, Vec};
+use soroban_sdk::{token, Address, Env, String, Vec};
 
 // ============ Test Helpers ============
 
@@ -103,6 +106,7 @@ fn test_create_project_success() {
         description: String::from_str(&env, "Initial development"),
         payment_amount: 100_000,
         deadline: now + 86400,
+        prerequisites: Vec::new(&env),
     });
 
     milestones.push_back(MilestoneInput {
@@ -110,6 +114,7 @@ fn test_create_project_success() {
         description: String::from_str(&env, "Testing phase"),
         payment_amount: 50_000,
         deadline: now + 2 * 86400,
+        prerequisites: Vec::new(&env),
     });
 
     let project_id = client.create_project(
@@ -182,6 +187,7 @@ fn test_create_project_zero_amount_fails() {
         description: String::from_str(&env, "Work"),
         payment_amount: 1000,
         deadline: now + 86400,
+        prerequisites: Vec::new(&env),
     });
 
     client.create_project(
@@ -218,6 +224,7 @@ fn test_create_project_overallocated_fails() {
         description: String::from_str(&env, ""),
         payment_amount: 60_000,
         deadline: now + 86400,
+        prerequisites: Vec::new(&env),
     });
 
     milestones.push_back(MilestoneInput {
@@ -225,6 +232,7 @@ fn test_create_project_overallocated_fails() {
         description: String::from_str(&env, ""),
         payment_amount: 60_000,
         deadline: now + 2 * 86400,
+        prerequisites: Vec::new(&env),
     });
 
     // Total milestones = 120k, but budget is only 100k
@@ -261,6 +269,7 @@ fn test_create_project_past_deadline_fails() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: 500, // Past deadline
+        prerequisites: Vec::new(&env),
     });
 
     client.create_project(
@@ -298,6 +307,7 @@ fn test_start_milestone_success() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        prerequisites: Vec::new(&env),
     });
 
     client.create_project(
@@ -343,6 +353,7 @@ fn test_start_milestone_wrong_contributor_fails() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        prerequisites: Vec::new(&env),
     });
 
     client.create_project(
@@ -383,6 +394,7 @@ fn test_submit_milestone_success() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        prerequisites: Vec::new(&env),
     });
 
     client.create_project(
@@ -432,6 +444,7 @@ fn test_submit_milestone_before_starting_fails() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        prerequisites: Vec::new(&env),
     });
 
     client.create_project(
@@ -477,6 +490,7 @@ fn test_approve_milestone_success() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        prerequisites: Vec::new(&env),
     });
 
     client.create_project(
@@ -504,6 +518,125 @@ fn test_approve_milestone_success() {
     assert_eq!(milestone.status, MilestoneStatus::Approved);
 }
 
+#[test]
+fn test_milestone_payment_converts_from_treasury_asset() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let signer = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let contributor = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    add_admin(&client, &env, guild_id, &owner, &admin);
+
+    // The treasury only holds `held`; the project is denominated in `usdc`
+    let token_admin = Address::generate(&env);
+    let held = env
+        .register_stellar_asset_contract_v2(token_admin.clone())
+        .address();
+    let usdc = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    token::StellarAssetClient::new(&env, &held).mint(&owner, &5000);
+
+    let mut signers = Vec::new(&env);
+    signers.push_back(owner.clone());
+    signers.push_back(signer.clone());
+    let treasury_id = client.initialize_treasury(&guild_id, &signers, &1u32);
+    client.deposit_treasury(&treasury_id, &owner, &5000i128, &Some(held.clone()));
+
+    let swapper = env.register_contract(None, MockSwapper);
+    MockSwapperClient::new(&env, &swapper).init(&contract_id);
+    token::StellarAssetClient::new(&env, &usdc).mint(&swapper, &5000);
+    let tx_id = client.propose_swapper_change(
+        &treasury_id,
+        &owner,
+        &swapper,
+        &String::from_str(&env, "swapper"),
+    );
+    client.approve_transaction(&tx_id, &signer);
+    client.execute_transaction(&tx_id, &owner);
+
+    let now = env.ledger().timestamp();
+    let mut milestones: Vec<MilestoneInput> = Vec::new(&env);
+    milestones.push_back(MilestoneInput {
+        title: String::from_str(&env, "M1"),
+        description: String::from_str(&env, ""),
+        payment_amount: 1000,
+        deadline: now + 86400,
+        prerequisites: Vec::new(&env),
+    });
+    let project_id = client.create_project(
+        &guild_id,
+        &contributor,
+        &milestones,
+        &1000i128,
+        &treasury_id,
+        &Some(usdc.clone()),
+        &false,
+    );
+
+    client.set_project_payout_conversion(
+        &project_id,
+        &admin,
+        &Some(PayoutConversion {
+            source_token: held.clone(),
+            target_token: usdc.clone(),
+            treasury_id,
+            min_rate: RATE_SCALE,
+        }),
+    );
+
+    // Funding reserves the asset the treasury pays from
+    client.approve_project_funding(&project_id, &owner);
+    let funding = client.get_project_funding(&project_id).unwrap();
+    assert_eq!(funding.reserved_token, Some(held.clone()));
+    assert_eq!(funding.reserved_amount, 1000);
+    assert_eq!(
+        client
+            .get_treasury_balance_detail(&treasury_id, &Some(held.clone()))
+            .committed,
+        1000
+    );
+
+    // The reservation is in `held`, so the conversion can no longer be dropped
+    assert!(client
+        .try_set_project_payout_conversion(&project_id, &admin, &None)
+        .is_err());
+
+    let milestone_id = 1u64;
+    client.start_milestone(&milestone_id, &contributor);
+    client.submit_milestone(
+        &milestone_id,
+        &String::from_str(&env, "https://github.com/pr/1"),
+    );
+    client.approve_milestone(&milestone_id, &admin);
+
+    assert!(client.get_milestone(&milestone_id).is_payment_released);
+    assert_eq!(
+        token::TokenClient::new(&env, &usdc).balance(&contributor),
+        1000
+    );
+    assert_eq!(token::TokenClient::new(&env, &held).balance(&swapper), 1000);
+    assert_eq!(token::TokenClient::new(&env, &usdc).balance(&swapper), 4000);
+    assert_eq!(
+        client.get_treasury_balance(&treasury_id, &Some(held.clone())),
+        4000
+    );
+    assert_eq!(
+        client
+            .get_treasury_balance_detail(&treasury_id, &Some(held.clone()))
+            .committed,
+        0
+    );
+}
+
 #[test]
 #[should_panic(expected = "approver must be guild admin")]
 fn test_approve_milestone_non_admin_fails() {
@@ -528,6 +661,7 @@ fn test_approve_milestone_non_admin_fails() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        prerequisites: Vec::new(&env),
     });
 
     client.create_project(
@@ -574,6 +708,7 @@ fn test_approve_milestone_not_submitted_fails() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        prerequisites: Vec::new(&env),
     });
 
     client.create_project(
@@ -618,6 +753,7 @@ fn test_reject_milestone_success() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        prerequisites: Vec::new(&env),
     });
 
     client.create_project(
@@ -668,6 +804,7 @@ fn test_resubmit_after_rejection() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        prerequisites: Vec::new(&env),
     });
 
     client.create_project(
@@ -730,6 +867,7 @@ fn test_sequential_prevents_out_of_order_start() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        prerequisites: Vec::new(&env),
     });
 
     milestones.push_back(MilestoneInput {
@@ -737,6 +875,7 @@ fn test_sequential_prevents_out_of_order_start() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 2 * 86400,
+        prerequisites: Vec::new(&env),
     });
 
     client.create_project(
@@ -778,6 +917,7 @@ fn test_sequential_allows_second_after_first_approved() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        prerequisites: Vec::new(&env),
     });
 
     milestones.push_back(MilestoneInput {
@@ -785,6 +925,7 @@ fn test_sequential_allows_second_after_first_approved() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 2 * 86400,
+        prerequisites: Vec::new(&env),
     });
 
     let project_id = client.create_project(
@@ -843,6 +984,7 @@ fn test_parallel_allows_any_order() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        prerequisites: Vec::new(&env),
     });
 
     milestones.push_back(MilestoneInput {
@@ -850,6 +992,7 @@ fn test_parallel_allows_any_order() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 2 * 86400,
+        prerequisites: Vec::new(&env),
     });
 
     client.create_project(
@@ -894,6 +1037,7 @@ fn test_progress_calculation() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        prerequisites: Vec::new(&env),
     });
 
     milestones.push_back(MilestoneInput {
@@ -901,6 +1045,7 @@ fn test_progress_calculation() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 2 * 86400,
+        prerequisites: Vec::new(&env),
     });
 
     milestones.push_back(MilestoneInput {
@@ -908,6 +1053,7 @@ fn test_progress_calculation() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 3 * 86400,
+        prerequisites: Vec::new(&env),
     });
 
     milestones.push_back(MilestoneInput {
@@ -915,6 +1061,7 @@ fn test_progress_calculation() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 4 * 86400,
+        prerequisites: Vec::new(&env),
     });
 
     let project_id = client.create_project(
@@ -973,6 +1120,7 @@ fn test_add_milestone_to_existing_project() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        prerequisites: Vec::new(&env),
     });
 
     let project_id = client.create_project(
@@ -996,6 +1144,7 @@ fn test_add_milestone_to_existing_project() {
         &2000i128,
         &(now + 2 * 86400),
         &owner,
+        &Vec::new(&env),
     );
 
     assert_eq!(new_milestone_id, 2);
@@ -1028,6 +1177,7 @@ fn test_add_milestone_non_admin_fails() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        prerequisites: Vec::new(&env),
     });
 
     let project_id = client.create_project(
@@ -1048,6 +1198,7 @@ fn test_add_milestone_non_admin_fails() {
         &1000i128,
         &(now + 2 * 86400),
         &non_admin,
+        &Vec::new(&env),
     );
 }
 
@@ -1075,6 +1226,7 @@ fn test_extend_milestone_deadline() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        prerequisites: Vec::new(&env),
     });
 
     client.create_project(
@@ -1121,6 +1273,7 @@ fn test_cancel_project() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        prerequisites: Vec::new(&env),
     });
 
     let project_id = client.create_project(
@@ -1161,6 +1314,7 @@ fn test_cancel_project_non_admin_fails() {
         description: String::from_str(&env, ""),
         payment_amount: 1000,
         deadline: now + 86400,
+        prerequisites: Vec::new(&env),
     });
 
     let project_id = client.create_project(
@@ -1176,3 +1330,583 @@ fn test_cancel_project_non_admin_fails() {
     // Non-admin tries to cancel
     client.cancel_project(&project_id, &non_admin);
 }
+
+// ============ Project Funding Tests ============
+
+fn setup_funded_project(
+    env: &Env,
+    client: &StellarGuildsContractClient<'_>,
+    owner: &Address,
+    signer1: &Address,
+    signer2: &Address,
+    contributor: &Address,
+) -> (u64, u64) {
+    let guild_id = setup_guild(client, env, owner);
+
+    let mut signers = Vec::new(env);
+    signers.push_back(owner.clone());
+    signers.push_back(signer1.clone());
+    signers.push_back(signer2.clone());
+    let treasury_id = client.initialize_treasury(&guild_id, &signers, &2u32);
+    client.deposit_treasury(&treasury_id, owner, &5000i128, &None);
+
+    let now = env.ledger().timestamp();
+    let mut milestones: Vec<MilestoneInput> = Vec::new(env);
+    milestones.push_back(MilestoneInput {
+        title: String::from_str(env, "M1"),
+        description: String::from_str(env, ""),
+        payment_amount: 1000,
+        deadline: now + 86400,
+        prerequisites: Vec::new(env),
+    });
+    milestones.push_back(MilestoneInput {
+        title: String::from_str(env, "M2"),
+        description: String::from_str(env, ""),
+        payment_amount: 2000,
+        deadline: now + 2 * 86400,
+        prerequisites: Vec::new(env),
+    });
+
+    let project_id = client.create_project(
+        &guild_id,
+        contributor,
+        &milestones,
+        &3000i128,
+        &treasury_id,
+        &None,
+        &false,
+    );
+    (project_id, treasury_id)
+}
+
+#[test]
+fn test_project_funding_reserves_and_releases_on_cancel() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let signer1 = Address::generate(&env);
+    let signer2 = Address::generate(&env);
+    let contributor = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let (project_id, treasury_id) =
+        setup_funded_project(&env, &client, &owner, &signer1, &signer2, &contributor);
+
+    // One approval is below the threshold, nothing is committed yet
+    client.approve_project_funding(&project_id, &signer1);
+    let funding = client.get_project_funding(&project_id).unwrap();
+    assert!(!funding.funded);
+    assert_eq!(
+        client
+            .get_treasury_balance_detail(&treasury_id, &None)
+            .committed,
+        0
+    );
+
+    assert!(client
+        .try_approve_project_funding(&project_id, &signer1)
+        .is_err());
+    assert!(client
+        .try_approve_project_funding(&project_id, &contributor)
+        .is_err());
+
+    client.approve_project_funding(&project_id, &signer2);
+    let funding = client.get_project_funding(&project_id).unwrap();
+    assert!(funding.funded);
+    assert_eq!(funding.reserved_amount, 3000);
+
+    let detail = client.get_treasury_balance_detail(&treasury_id, &None);
+    assert_eq!(detail.liquid, 5000);
+    assert_eq!(detail.committed, 3000);
+
+    // Withdrawing into committed funds is refused
+    let tx_id = client.propose_withdrawal(
+        &treasury_id,
+        &signer1,
+        &Address::generate(&env),
+        &2500i128,
+        &None,
+        &String::from_str(&env, "payout"),
+    );
+    client.approve_transaction(&tx_id, &signer2);
+    assert!(client.try_execute_transaction(&tx_id, &owner).is_err());
+
+    client.cancel_project(&project_id, &owner);
+    assert_eq!(
+        client
+            .get_project_funding(&project_id)
+            .unwrap()
+            .reserved_amount,
+        0
+    );
+    assert_eq!(
+        client
+            .get_treasury_balance_detail(&treasury_id, &None)
+            .committed,
+        0
+    );
+
+    client.execute_transaction(&tx_id, &owner);
+    assert_eq!(client.get_treasury_balance(&treasury_id, &None), 2500);
+}
+
+#[test]
+fn test_milestone_payment_draws_down_reservation() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let signer1 = Address::generate(&env);
+    let signer2 = Address::generate(&env);
+    let contributor = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let (project_id, treasury_id) =
+        setup_funded_project(&env, &client, &owner, &signer1, &signer2, &contributor);
+
+    client.approve_project_funding(&project_id, &owner);
+    client.approve_project_funding(&project_id, &signer1);
+
+    client.start_milestone(&1u64, &contributor);
+    client.submit_milestone(&1u64, &String::from_str(&env, "https://proof/1"));
+    client.approve_milestone(&1u64, &owner);
+
+    let funding = client.get_project_funding(&project_id).unwrap();
+    assert_eq!(funding.reserved_amount, 2000);
+    let detail = client.get_treasury_balance_detail(&treasury_id, &None);
+    assert_eq!(detail.liquid, 4000);
+    assert_eq!(detail.committed, 2000);
+}
+
+#[test]
+fn test_milestone_overspends_soft_budget_once_all_signers_fund() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let signer1 = Address::generate(&env);
+    let signer2 = Address::generate(&env);
+    let contributor = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let (project_id, treasury_id) =
+        setup_funded_project(&env, &client, &owner, &signer1, &signer2, &contributor);
+
+    let category = String::from_str(&env, "milestone");
+    client.set_budget(&treasury_id, &category, &1500i128, &0u64, &owner);
+    client.configure_budget(
+        &treasury_id,
+        &category,
+        &0i128,
+        &None,
+        &BudgetMode::Soft,
+        &owner,
+    );
+
+    client.approve_project_funding(&project_id, &owner);
+    client.approve_project_funding(&project_id, &signer1);
+
+    client.start_milestone(&1u64, &contributor);
+    client.submit_milestone(&1u64, &String::from_str(&env, "https://proof/1"));
+    client.approve_milestone(&1u64, &owner);
+
+    // The second payment overspends the budget; two of three signers funded
+    client.start_milestone(&2u64, &contributor);
+    client.submit_milestone(&2u64, &String::from_str(&env, "https://proof/2"));
+    assert!(client.try_approve_milestone(&2u64, &owner).is_err());
+
+    client.approve_project_funding(&project_id, &signer2);
+    assert_eq!(
+        client
+            .get_project_funding(&project_id)
+            .unwrap()
+            .reserved_amount,
+        2000
+    );
+    client.approve_milestone(&2u64, &owner);
+    assert!(client.get_milestone(&2u64).is_payment_released);
+    assert_eq!(client.get_treasury_balance(&treasury_id, &None), 2000);
+}
+
+// ============ Review Quorum Tests ============
+
+fn setup_reviewed_project(
+    env: &Env,
+    client: &StellarGuildsContractClient<'_>,
+    owner: &Address,
+    contributor: &Address,
+    reviewers: &Vec<Address>,
+    required: u32,
+) -> u64 {
+    let guild_id = setup_guild(client, env, owner);
+    let treasury_id = create_treasury_with_funds(client, env, guild_id, owner, 5000i128);
+
+    let now = env.ledger().timestamp();
+    let mut milestones: Vec<MilestoneInput> = Vec::new(env);
+    milestones.push_back(MilestoneInput {
+        title: String::from_str(env, "M1"),
+        description: String::from_str(env, ""),
+        payment_amount: 1000,
+        deadline: now + 86400,
+        prerequisites: Vec::new(env),
+    });
+    let project_id = client.create_project(
+        &guild_id,
+        contributor,
+        &milestones,
+        &1000i128,
+        &treasury_id,
+        &None,
+        &false,
+    );
+    client.set_milestone_reviewers(&project_id, owner, reviewers, &required);
+    project_id
+}
+
+#[test]
+fn test_milestone_requires_reviewer_quorum() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let r1 = Address::generate(&env);
+    let r2 = Address::generate(&env);
+    let r3 = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let mut reviewers = Vec::new(&env);
+    reviewers.push_back(r1.clone());
+    reviewers.push_back(r2.clone());
+    reviewers.push_back(r3.clone());
+    let project_id = setup_reviewed_project(&env, &client, &owner, &contributor, &reviewers, 2);
+    assert_eq!(
+        client
+            .get_milestone_reviewers(&project_id)
+            .unwrap()
+            .required_approvals,
+        2
+    );
+
+    client.start_milestone(&1u64, &contributor);
+    client.submit_milestone(&1u64, &String::from_str(&env, "https://proof/1"));
+
+    // Guild admins are not reviewers once a reviewer set is named
+    assert!(client.try_approve_milestone(&1u64, &owner).is_err());
+
+    client.approve_milestone(&1u64, &r1);
+    assert_eq!(
+        client.get_milestone(&1u64).status,
+        MilestoneStatus::Submitted
+    );
+    assert!(client.try_approve_milestone(&1u64, &r1).is_err());
+
+    client.review_milestone(&1u64, &r2, &true, &String::from_str(&env, "looks good"));
+    let milestone = client.get_milestone(&1u64);
+    assert_eq!(milestone.status, MilestoneStatus::Approved);
+    assert!(milestone.is_payment_released);
+
+    let reviews = client.get_milestone_reviews(&1u64, &milestone.version);
+    assert_eq!(reviews.len(), 2);
+    assert_eq!(
+        reviews.get(1).unwrap().comment,
+        String::from_str(&env, "looks good")
+    );
+}
+
+#[test]
+fn test_milestone_rejected_when_quorum_unreachable() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let r1 = Address::generate(&env);
+    let r2 = Address::generate(&env);
+    let r3 = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let mut reviewers = Vec::new(&env);
+    reviewers.push_back(r1.clone());
+    reviewers.push_back(r2.clone());
+    reviewers.push_back(r3.clone());
+    let project_id = setup_reviewed_project(&env, &client, &owner, &contributor, &reviewers, 2);
+    assert!(client
+        .try_set_milestone_reviewers(&project_id, &owner, &reviewers, &4u32)
+        .is_err());
+
+    client.start_milestone(&1u64, &contributor);
+    client.submit_milestone(&1u64, &String::from_str(&env, "https://proof/1"));
+    let first_version = client.get_milestone(&1u64).version;
+
+    client.approve_milestone(&1u64, &r1);
+    client.reject_milestone(&1u64, &r2, &String::from_str(&env, "missing tests"));
+    assert_eq!(
+        client.get_milestone(&1u64).status,
+        MilestoneStatus::Submitted
+    );
+    client.reject_milestone(&1u64, &r3, &String::from_str(&env, "docs incomplete"));
+    assert_eq!(
+        client.get_milestone(&1u64).status,
+        MilestoneStatus::Rejected
+    );
+
+    // Resubmission starts a fresh round of reviews
+    client.submit_milestone(&1u64, &String::from_str(&env, "https://proof/2"));
+    let milestone = client.get_milestone(&1u64);
+    assert_eq!(milestone.version, first_version + 1);
+    assert_eq!(
+        client
+            .get_milestone_reviews(&1u64, &milestone.version)
+            .len(),
+        0
+    );
+
+    let old_reviews = client.get_milestone_reviews(&1u64, &first_version);
+    assert_eq!(old_reviews.len(), 3);
+    assert_eq!(
+        old_reviews.get(2).unwrap().comment,
+        String::from_str(&env, "docs incomplete")
+    );
+
+    client.approve_milestone(&1u64, &r2);
+    client.approve_milestone(&1u64, &r3);
+    assert_eq!(
+        client.get_milestone(&1u64).status,
+        MilestoneStatus::Approved
+    );
+}
+
+// ============ Review SLA Tests ============
+
+#[test]
+fn test_auto_approve_after_review_sla() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let reviewer = Address::generate(&env);
+    let keeper = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let mut reviewers = Vec::new(&env);
+    reviewers.push_back(reviewer.clone());
+    let project_id = setup_reviewed_project(&env, &client, &owner, &contributor, &reviewers, 1);
+    client.set_milestone_review_sla(&project_id, &owner, &3600u64);
+
+    client.start_milestone(&1u64, &contributor);
+    client.submit_milestone(&1u64, &String::from_str(&env, "https://proof/1"));
+
+    set_ledger_timestamp(&env, 1000 + 3599);
+    assert!(client.try_auto_approve_milestone(&1u64, &keeper).is_err());
+
+    set_ledger_timestamp(&env, 1000 + 3600);
+    client.auto_approve_milestone(&1u64, &keeper);
+
+    let milestone = client.get_milestone(&1u64);
+    assert_eq!(milestone.status, MilestoneStatus::Approved);
+    assert!(milestone.is_payment_released);
+    assert!(client.try_auto_approve_milestone(&1u64, &keeper).is_err());
+}
+
+#[test]
+#[should_panic(expected = "milestone expired")]
+fn test_auto_approve_after_deadline_fails() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let reviewer = Address::generate(&env);
+    let keeper = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let mut reviewers = Vec::new(&env);
+    reviewers.push_back(reviewer.clone());
+    let project_id = setup_reviewed_project(&env, &client, &owner, &contributor, &reviewers, 1);
+    client.set_milestone_review_sla(&project_id, &owner, &3600u64);
+
+    client.start_milestone(&1u64, &contributor);
+    client.submit_milestone(&1u64, &String::from_str(&env, "https://proof/1"));
+
+    // The SLA has lapsed, but so has the milestone's deadline
+    set_ledger_timestamp(&env, 1000 + 86400 + 1);
+    client.auto_approve_milestone(&1u64, &keeper);
+}
+
+#[test]
+fn test_auto_approve_blocked_by_dispute_or_missing_sla() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let reviewer = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let mut reviewers = Vec::new(&env);
+    reviewers.push_back(reviewer.clone());
+    let project_id = setup_reviewed_project(&env, &client, &owner, &contributor, &reviewers, 1);
+
+    client.start_milestone(&1u64, &contributor);
+    client.submit_milestone(&1u64, &String::from_str(&env, "https://proof/1"));
+
+    set_ledger_timestamp(&env, 1000 + 7200);
+    assert!(client.try_auto_approve_milestone(&1u64, &owner).is_err());
+
+    client.set_milestone_review_sla(&project_id, &owner, &3600u64);
+    client.create_dispute(
+        &1u64,
+        &owner,
+        &contributor,
+        &String::from_str(&env, "work incomplete"),
+        &String::from_str(&env, "https://evidence"),
+    );
+    assert!(client.try_auto_approve_milestone(&1u64, &owner).is_err());
+    assert_eq!(
+        client.get_milestone(&1u64).status,
+        MilestoneStatus::Submitted
+    );
+}
+
+// ============ Prerequisite Tests ============
+
+fn milestone_input(env: &Env, title: &str, day: u64, prerequisites: &[u32]) -> MilestoneInput {
+    MilestoneInput {
+        title: String::from_str(env, title),
+        description: String::from_str(env, ""),
+        payment_amount: 1000,
+        deadline: env.ledger().timestamp() + day * 86400,
+        prerequisites: Vec::from_slice(env, prerequisites),
+    }
+}
+
+#[test]
+fn test_prerequisites_gate_start_and_report_blocked() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let contributor = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+    let treasury_id = create_treasury_with_funds(&client, &env, guild_id, &owner, 5000i128);
+
+    // M3 depends on M1 and M2, which are independent of each other
+    let mut milestones: Vec<MilestoneInput> = Vec::new(&env);
+    milestones.push_back(milestone_input(&env, "M1", 1, &[]));
+    milestones.push_back(milestone_input(&env, "M2", 1, &[]));
+    milestones.push_back(milestone_input(&env, "M3", 2, &[0, 1]));
+
+    let project_id = client.create_project(
+        &guild_id,
+        &contributor,
+        &milestones,
+        &3000i128,
+        &treasury_id,
+        &None,
+        &false,
+    );
+    assert_eq!(
+        client.get_milestone(&3u64).prerequisites,
+        Vec::from_array(&env, [1u64, 2u64])
+    );
+
+    let progress = client.get_project_progress_detail(&project_id);
+    assert_eq!(progress.blocked, Vec::from_array(&env, [3u64]));
+    assert!(client.try_start_milestone(&3u64, &contributor).is_err());
+
+    for id in [1u64, 2u64] {
+        client.start_milestone(&id, &contributor);
+        client.submit_milestone(&id, &String::from_str(&env, "https://proof"));
+    }
+    client.approve_milestone(&1u64, &owner);
+    assert!(client.try_start_milestone(&3u64, &contributor).is_err());
+
+    client.approve_milestone(&2u64, &owner);
+    let progress = client.get_project_progress_detail(&project_id);
+    assert_eq!(progress.blocked.len(), 0);
+    assert_eq!(progress.completed, 2);
+
+    client.start_milestone(&3u64, &contributor);
+
+    // Added milestones may only depend on milestones of the same project
+    let other_project = client.create_project(
+        &guild_id,
+        &contributor,
+        &Vec::from_array(&env, [milestone_input(&env, "Other", 1, &[])]),
+        &2000i128,
+        &treasury_id,
+        &None,
+        &false,
+    );
+    assert!(client
+        .try_add_milestone(
+            &other_project,
+            &String::from_str(&env, "Follow-up"),
+            &String::from_str(&env, ""),
+            &500i128,
+            &(env.ledger().timestamp() + 86400),
+            &owner,
+            &Vec::from_array(&env, [1u64]),
+        )
+        .is_err());
+}
+
+#[test]
+#[should_panic(expected = "prerequisite cycle detected")]
+fn test_create_project_with_prerequisite_cycle_fails() {
+    let env = setup_env();
+    let owner = Address::generate(&env);
+    let contributor = Address::generate(&env);
+
+    set_ledger_timestamp(&env, 1000);
+    env.mock_all_auths();
+
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+    let guild_id = setup_guild(&client, &env, &owner);
+
+    let mut milestones: Vec<MilestoneInput> = Vec::new(&env);
+    milestones.push_back(milestone_input(&env, "M1", 1, &[2]));
+    milestones.push_back(milestone_input(&env, "M2", 1, &[0]));
+    milestones.push_back(milestone_input(&env, "M3", 1, &[1]));
+
+    client.create_project(
+        &guild_id,
+        &contributor,
+        &milestones,
+        &3000i128,
+        &1u64,
+        &None,
+        &false,
+    );
+}
@@ -4,21 +4,32 @@ use crate::dispute::storage as dispute_storage;
 use crate::dispute::types::DisputeReference;
 use crate::events::emit::emit_event;
 use crate::events::topics::{
-    ACT_APPROVED, ACT_CANCELLED, ACT_COMPLETED, ACT_CREATED, ACT_REJECTED, ACT_RELEASED,
-    ACT_STARTED, ACT_SUBMITTED, ACT_UPDATED, MOD_MILESTONE,
+    ACT_APPROVED, ACT_AUTO_APPROVED, ACT_CANCELLED, ACT_COMPLETED, ACT_CREATED, ACT_FUNDED,
+    ACT_POLICY_SET, ACT_REJECTED, ACT_RELEASED, ACT_STARTED, ACT_SUBMITTED, ACT_UPDATED, ACT_VOTED,
+    MOD_MILESTONE,
 };
 use crate::guild::membership::has_permission;
 use crate::guild::types::Role;
+use crate::interfaces::swapper::{quote, validate_conversion, PayoutConversion};
 use crate::milestone::storage::{
-    append_milestone_to_project, get_milestone, get_next_milestone_id, get_next_project_id,
-    get_project, get_project_milestone_ids, store_milestone, store_project,
+    add_review, append_milestone_to_project, get_funding, get_milestone, get_next_milestone_id,
+    get_next_project_id, get_payout_conversion, get_project, get_project_milestone_ids,
+    get_review_policy, get_review_sla, get_reviews, set_payout_conversion,
+    set_review_policy as set_review_policy_storage, set_review_sla as set_review_sla_storage,
+    store_funding, store_milestone, store_project,
 };
 use crate::milestone::types::{
-    Milestone, MilestoneAddedEvent, MilestoneInput, MilestonePaymentReleasedEvent,
-    MilestoneRejectedEvent, MilestoneStatus, MilestoneStatusChangedEvent, MilestoneSubmittedEvent,
-    Project, ProjectCreatedEvent, ProjectStatus, ProjectStatusChangedEvent,
+    Milestone, MilestoneAddedEvent, MilestoneAutoApprovedEvent, MilestoneInput,
+    MilestonePaymentReleasedEvent, MilestoneRejectedEvent, MilestoneReview, MilestoneReviewedEvent,
+    MilestoneStatus, MilestoneStatusChangedEvent, MilestoneSubmittedEvent,
+    PayoutConversionUpdatedEvent, Project, ProjectCreatedEvent, ProjectFunding,
+    ProjectFundingEvent, ProjectProgress, ProjectStatus, ProjectStatusChangedEvent, ReviewPolicy,
+    ReviewPolicyUpdatedEvent, ReviewSlaUpdatedEvent,
 };
-use crate::treasury::execute_milestone_payment;
+use crate::treasury::commitment::{commit_funds, release_commitment};
+use crate::treasury::multisig::assert_signer;
+use crate::treasury::storage::get_treasury;
+use crate::treasury::{execute_converted_milestone_payment, execute_milestone_payment};
 
 fn assert_project_active(project: &Project, _env: &Env) {
     if project.status != ProjectStatus::Active {
@@ -85,6 +96,8 @@ pub fn create_project(
         panic!("allocated milestone budget exceeds project total");
     }
 
+    assert_acyclic_prerequisites(&milestones);
+
     let project_id = get_next_project_id(env);
 
     let project = Project {
@@ -103,10 +116,20 @@ pub fn create_project(
 
     store_project(env, &project);
 
+    // Ids are assigned up front so prerequisites can refer to later inputs
+    let mut ids: Vec<u64> = Vec::new(env);
+    for _ in milestones.iter() {
+        ids.push_back(get_next_milestone_id(env));
+    }
+
     // Create milestones
     let mut order: u32 = 1;
     for input in milestones.iter() {
-        let milestone_id = get_next_milestone_id(env);
+        let milestone_id = ids.get(order - 1).unwrap();
+        let mut prerequisites: Vec<u64> = Vec::new(env);
+        for idx in input.prerequisites.iter() {
+            prerequisites.push_back(ids.get(idx).unwrap());
+        }
         let milestone = Milestone {
             id: milestone_id,
             project_id,
@@ -122,6 +145,7 @@ pub fn create_project(
             last_updated_at: now,
             version: 0,
             is_payment_released: false,
+            prerequisites,
         };
         store_milestone(env, &milestone);
         append_milestone_to_project(env, project_id, milestone_id);
@@ -152,6 +176,7 @@ pub fn create_project(
     project_id
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn add_milestone(
     env: &Env,
     project_id: u64,
@@ -160,6 +185,7 @@ pub fn add_milestone(
     amount: i128,
     deadline: u64,
     caller: Address,
+    prerequisites: Vec<u64>,
 ) -> u64 {
     caller.require_auth();
 
@@ -185,6 +211,14 @@ pub fn add_milestone(
         panic!("milestone description too long");
     }
 
+    // A new milestone can only depend on existing ones, so no cycle can form
+    for id in prerequisites.iter() {
+        let prerequisite = get_milestone(env, id).expect("prerequisite not found");
+        if prerequisite.project_id != project_id {
+            panic!("prerequisite not in project");
+        }
+    }
+
     let new_allocated = project
         .allocated_amount
         .checked_add(amount)
@@ -213,6 +247,7 @@ pub fn add_milestone(
         last_updated_at: now,
         version: 0,
         is_payment_released: false,
+        prerequisites,
     };
 
     store_milestone(env, &milestone);
@@ -261,6 +296,10 @@ pub fn start_milestone(env: &Env, milestone_id: u64, contributor: Address) -> bo
         }
     }
 
+    if !prerequisites_approved(env, &milestone) {
+        panic!("prerequisites not approved");
+    }
+
     let old_status = milestone.status.clone();
     milestone.status = MilestoneStatus::InProgress;
     milestone.last_updated_at = env.ledger().timestamp();
@@ -323,7 +362,29 @@ pub fn submit_milestone(env: &Env, milestone_id: u64, proof_url: String) -> bool
 }
 
 pub fn approve_milestone(env: &Env, milestone_id: u64, approver: Address) -> bool {
-    approver.require_auth();
+    review_milestone(env, milestone_id, approver, true, String::from_str(env, ""))
+}
+
+pub fn reject_milestone(env: &Env, milestone_id: u64, approver: Address, reason: String) -> bool {
+    review_milestone(env, milestone_id, approver, false, reason)
+}
+
+/// Record a reviewer's verdict on the current version of a submitted
+/// milestone.
+///
+/// Without a review policy a single guild admin decides. With one, the
+/// milestone is approved (and paid) once the policy's quorum of reviewers
+/// approve this version, and rejected as soon as the quorum can no longer
+/// be reached. Resubmission bumps `version`, so every submission is
+/// reviewed afresh.
+pub fn review_milestone(
+    env: &Env,
+    milestone_id: u64,
+    reviewer: Address,
+    approve: bool,
+    comment: String,
+) -> bool {
+    reviewer.require_auth();
 
     let mut milestone = get_milestone(env, milestone_id).expect("milestone not found");
     let mut project = get_project(env, milestone.project_id).expect("project not found");
@@ -331,73 +392,222 @@ pub fn approve_milestone(env: &Env, milestone_id: u64, approver: Address) -> boo
     assert_project_active(&project, env);
     ensure_not_expired(env, &mut milestone);
 
-    if !has_permission(env, project.guild_id, approver, Role::Admin) {
-        panic!("approver must be guild admin");
+    let policy = get_review_policy(env, project.id);
+    match policy {
+        Some(ref p) => {
+            if !p.reviewers.contains(&reviewer) {
+                panic!("approver is not a project reviewer");
+            }
+        }
+        None => {
+            if !has_permission(env, project.guild_id, reviewer.clone(), Role::Admin) {
+                panic!("approver must be guild admin");
+            }
+        }
     }
 
     if milestone.status != MilestoneStatus::Submitted {
         panic!("milestone not submitted");
     }
 
+    if (!approve && comment.len() == 0) || comment.len() > 512 {
+        panic!("invalid rejection reason");
+    }
+
+    let reviews = get_reviews(env, milestone_id, milestone.version);
+    if reviews.iter().any(|r| r.reviewer == reviewer) {
+        panic!("reviewer already reviewed this version");
+    }
+    let review = MilestoneReview {
+        reviewer: reviewer.clone(),
+        approved: approve,
+        comment: comment.clone(),
+        timestamp: env.ledger().timestamp(),
+    };
+    add_review(env, milestone_id, milestone.version, &review);
+
+    // Only votes from the current reviewer set count towards the quorum
+    let (reviewer_count, required) = match policy {
+        Some(ref p) => (p.reviewers.len(), p.required_approvals),
+        None => (1, 1),
+    };
+    let mut approvals: u32 = 0;
+    let mut rejections: u32 = 0;
+    for r in reviews.iter().chain(core::iter::once(review)) {
+        if let Some(ref p) = policy {
+            if !p.reviewers.contains(&r.reviewer) {
+                continue;
+            }
+        }
+        if r.approved {
+            approvals += 1;
+        } else {
+            rejections += 1;
+        }
+    }
+
+    let reviewed_event = MilestoneReviewedEvent {
+        project_id: project.id,
+        milestone_id,
+        version: milestone.version,
+        reviewer,
+        approved: approve,
+        approvals,
+        required_approvals: required,
+    };
+    emit_event(env, MOD_MILESTONE, ACT_VOTED, reviewed_event);
+
+    if approvals >= required {
+        approve_submitted(env, &mut project, &mut milestone);
+    } else if !approve && reviewer_count.saturating_sub(rejections) < required {
+        let old_status = milestone.status.clone();
+        milestone.status = MilestoneStatus::Rejected;
+        milestone.last_updated_at = env.ledger().timestamp();
+        store_milestone(env, &milestone);
+
+        let reject_event = MilestoneRejectedEvent {
+            project_id: project.id,
+            milestone_id,
+            reason: comment,
+        };
+        emit_event(env, MOD_MILESTONE, ACT_REJECTED, reject_event);
+
+        let status_event = MilestoneStatusChangedEvent {
+            project_id: project.id,
+            milestone_id,
+            old_status,
+            new_status: milestone.status.clone(),
+        };
+        emit_event(env, MOD_MILESTONE, ACT_UPDATED, status_event);
+    }
+
+    true
+}
+
+fn approve_submitted(env: &Env, project: &mut Project, milestone: &mut Milestone) {
     let old_status = milestone.status.clone();
     milestone.status = MilestoneStatus::Approved;
     milestone.last_updated_at = env.ledger().timestamp();
-    store_milestone(env, &milestone);
+    store_milestone(env, milestone);
 
     let status_event = MilestoneStatusChangedEvent {
         project_id: project.id,
-        milestone_id,
+        milestone_id: milestone.id,
         old_status,
         new_status: milestone.status.clone(),
     };
     emit_event(env, MOD_MILESTONE, ACT_APPROVED, status_event);
 
     // Automatic payment release (Option B via treasury)
-    let _ = release_milestone_payment_internal(env, &mut project, &mut milestone);
+    let _ = release_milestone_payment_internal(env, project, milestone);
+}
+
+/// Set how long reviewers have to decide on a submission before anyone
+/// may auto-approve it. Zero clears the SLA.
+pub fn set_review_sla(env: &Env, project_id: u64, caller: Address, sla_seconds: u64) -> bool {
+    caller.require_auth();
+
+    let project = get_project(env, project_id).expect("project not found");
+    assert_project_active(&project, env);
+
+    if !has_permission(env, project.guild_id, caller, Role::Admin) {
+        panic!("caller must be guild admin");
+    }
+
+    set_review_sla_storage(env, project_id, sla_seconds);
+
+    let event = ReviewSlaUpdatedEvent {
+        project_id,
+        sla_seconds,
+    };
+    emit_event(env, MOD_MILESTONE, ACT_POLICY_SET, event);
 
     true
 }
 
-pub fn reject_milestone(env: &Env, milestone_id: u64, approver: Address, reason: String) -> bool {
-    approver.require_auth();
+/// Approve and pay a submission whose reviewers stayed silent past the
+/// project's review SLA. Callable by anyone; disputed milestones are
+/// excluded.
+pub fn auto_approve_milestone(env: &Env, milestone_id: u64, caller: Address) -> bool {
+    caller.require_auth();
 
     let mut milestone = get_milestone(env, milestone_id).expect("milestone not found");
-    let project = get_project(env, milestone.project_id).expect("project not found");
+    let mut project = get_project(env, milestone.project_id).expect("project not found");
 
     assert_project_active(&project, env);
     ensure_not_expired(env, &mut milestone);
 
-    if !has_permission(env, project.guild_id, approver, Role::Admin) {
-        panic!("approver must be guild admin");
-    }
-
     if milestone.status != MilestoneStatus::Submitted {
         panic!("milestone not submitted");
     }
-
-    if reason.len() == 0 || reason.len() > 512 {
-        panic!("invalid rejection reason");
+    if dispute_storage::is_reference_locked(env, &DisputeReference::Milestone, milestone_id) {
+        panic!("milestone is in active dispute");
     }
 
-    let old_status = milestone.status.clone();
-    milestone.status = MilestoneStatus::Rejected;
-    milestone.last_updated_at = env.ledger().timestamp();
-    store_milestone(env, &milestone);
+    let sla_seconds = get_review_sla(env, project.id).expect("no review SLA set");
+    let submitted_at = milestone.submitted_at.expect("milestone not submitted");
+    if env.ledger().timestamp() < submitted_at.saturating_add(sla_seconds) {
+        panic!("review window still open");
+    }
 
-    let reject_event = MilestoneRejectedEvent {
+    let event = MilestoneAutoApprovedEvent {
         project_id: project.id,
         milestone_id,
-        reason,
+        version: milestone.version,
+        submitted_at,
+        sla_seconds,
+        triggered_by: caller,
     };
-    emit_event(env, MOD_MILESTONE, ACT_REJECTED, reject_event);
+    emit_event(env, MOD_MILESTONE, ACT_AUTO_APPROVED, event);
 
-    let status_event = MilestoneStatusChangedEvent {
-        project_id: project.id,
-        milestone_id,
-        old_status,
-        new_status: milestone.status.clone(),
+    approve_submitted(env, &mut project, &mut milestone);
+
+    true
+}
+
+/// Name the reviewers whose quorum approves this project's milestones.
+/// An empty reviewer set clears the policy so guild admins approve alone.
+pub fn set_review_policy(
+    env: &Env,
+    project_id: u64,
+    caller: Address,
+    reviewers: Vec<Address>,
+    required_approvals: u32,
+) -> bool {
+    caller.require_auth();
+
+    let project = get_project(env, project_id).expect("project not found");
+    assert_project_active(&project, env);
+
+    if !has_permission(env, project.guild_id, caller, Role::Admin) {
+        panic!("caller must be guild admin");
+    }
+
+    let policy = if reviewers.is_empty() {
+        None
+    } else {
+        if required_approvals == 0 || required_approvals > reviewers.len() {
+            panic!("invalid required approvals");
+        }
+        for (i, reviewer) in reviewers.iter().enumerate() {
+            if reviewers.iter().skip(i + 1).any(|other| other == reviewer) {
+                panic!("duplicate reviewer");
+            }
+        }
+        Some(ReviewPolicy {
+            project_id,
+            reviewers: reviewers.clone(),
+            required_approvals,
+        })
     };
-    emit_event(env, MOD_MILESTONE, ACT_UPDATED, status_event);
+    set_review_policy_storage(env, project_id, &policy);
+
+    let event = ReviewPolicyUpdatedEvent {
+        project_id,
+        reviewer_count: reviewers.len(),
+        required_approvals: policy.map(|p| p.required_approvals).unwrap_or(0),
+    };
+    emit_event(env, MOD_MILESTONE, ACT_POLICY_SET, event);
 
     true
 }
@@ -422,6 +632,79 @@ pub fn get_project_progress(env: &Env, project_id: u64) -> (u32, u32, u32) {
     (completed, total, percentage)
 }
 
+/// Progress counts plus the pending milestones still waiting on
+/// prerequisites.
+pub fn get_project_progress_detail(env: &Env, project_id: u64) -> ProjectProgress {
+    let (completed, total, percentage) = get_project_progress(env, project_id);
+
+    let mut blocked: Vec<u64> = Vec::new(env);
+    for id in get_project_milestone_ids(env, project_id).iter() {
+        if let Some(m) = get_milestone(env, id) {
+            if m.status == MilestoneStatus::Pending && !prerequisites_approved(env, &m) {
+                blocked.push_back(id);
+            }
+        }
+    }
+
+    ProjectProgress {
+        completed,
+        total,
+        percentage,
+        blocked,
+    }
+}
+
+fn prerequisites_approved(env: &Env, milestone: &Milestone) -> bool {
+    milestone.prerequisites.iter().all(|id| {
+        get_milestone(env, id)
+            .map(|m| m.status == MilestoneStatus::Approved)
+            .unwrap_or(false)
+    })
+}
+
+/// Reject out-of-range prerequisite positions and dependency cycles among
+/// the inputs of a new project.
+fn assert_acyclic_prerequisites(milestones: &Vec<MilestoneInput>) {
+    let count = milestones.len();
+    for input in milestones.iter() {
+        for idx in input.prerequisites.iter() {
+            if idx >= count {
+                panic!("invalid prerequisite index");
+            }
+        }
+    }
+
+    // Repeatedly resolve inputs whose prerequisites are all resolved; if a
+    // pass makes no progress, the remaining inputs form a cycle.
+    let env = milestones.env();
+    let mut resolved: Vec<bool> = Vec::new(env);
+    for _ in 0..count {
+        resolved.push_back(false);
+    }
+    let mut remaining = count;
+    while remaining > 0 {
+        let mut progressed = false;
+        for (i, input) in milestones.iter().enumerate() {
+            let i = i as u32;
+            if resolved.get(i).unwrap() {
+                continue;
+            }
+            if input
+                .prerequisites
+                .iter()
+                .all(|idx| resolved.get(idx).unwrap())
+            {
+                resolved.set(i, true);
+                remaining -= 1;
+                progressed = true;
+            }
+        }
+        if !progressed {
+            panic!("prerequisite cycle detected");
+        }
+    }
+}
+
 pub fn get_milestone_view(env: &Env, milestone_id: u64) -> Milestone {
     get_milestone(env, milestone_id).expect("milestone not found")
 }
@@ -463,13 +746,7 @@ fn release_milestone_payment_internal(
     let token = project.token.clone();
     let amount = milestone.payment_amount;
 
-    execute_milestone_payment(
-        env,
-        project.treasury_id,
-        token.clone(),
-        project.contributor.clone(),
-        amount,
-    );
+    pay_milestone(env, project, project.contributor.clone(), amount);
 
     project.released_amount = new_released;
     milestone.is_payment_released = true;
@@ -501,18 +778,230 @@ fn release_milestone_payment_internal(
     }
 
     if all_done && project.status != ProjectStatus::Completed {
-        let old_status = project.status.clone();
-        project.status = ProjectStatus::Completed;
-        store_project(env, project);
+        complete_project(env, project);
+    }
 
-        let pe = ProjectStatusChangedEvent {
-            project_id: project.id,
-            old_status,
-            new_status: ProjectStatus::Completed,
+    true
+}
+
+/// Pay `amount` (valued in the project token) of a project's milestone
+/// budget to `recipient`, drawing it from the project's reservation first.
+pub(crate) fn pay_milestone(env: &Env, project: &Project, recipient: Address, amount: i128) {
+    let conversion = get_payout_conversion(env, project.id);
+    let (source, source_amount) = payout_source(env, project, &conversion, amount);
+    let overspend_approved = funding_approved_by_all_signers(env, project);
+
+    // Funds reserved for the project are released just before they are paid
+    if let Some(mut funding) = get_funding(env, project.id) {
+        if funding.reserved_amount > 0 && funding.reserved_token == source {
+            let drawn = source_amount.min(funding.reserved_amount);
+            release_commitment(env, project.treasury_id, &source, drawn);
+            funding.reserved_amount -= drawn;
+            store_funding(env, &funding);
+        }
+    }
+
+    match conversion {
+        Some(ref c) => {
+            execute_converted_milestone_payment(
+                env,
+                project.treasury_id,
+                c,
+                recipient,
+                source_amount,
+                overspend_approved,
+            );
+        }
+        None => {
+            execute_milestone_payment(
+                env,
+                project.treasury_id,
+                source,
+                recipient,
+                amount,
+                overspend_approved,
+            );
+        }
+    }
+}
+
+/// Whether every signer of the project's treasury approved its funding, which
+/// lets milestone payments overspend a soft budget.
+fn funding_approved_by_all_signers(env: &Env, project: &Project) -> bool {
+    let treasury = get_treasury(env, project.treasury_id).expect("treasury not found");
+    match get_funding(env, project.id) {
+        Some(funding) => treasury
+            .signers
+            .iter()
+            .all(|s| funding.approvals.contains(&s)),
+        None => false,
+    }
+}
+
+/// Value `amount` of the project token in the asset the treasury pays from.
+fn payout_source(
+    env: &Env,
+    project: &Project,
+    conversion: &Option<PayoutConversion>,
+    amount: i128,
+) -> (Option<Address>, i128) {
+    match conversion {
+        Some(c) => match project.token {
+            Some(ref t) if *t == c.source_token => (project.token.clone(), amount),
+            Some(ref t) => (
+                Some(c.source_token.clone()),
+                quote(env, c, t, &c.source_token, amount),
+            ),
+            None => panic!("conversion requires a project token"),
+        },
+        None => (project.token.clone(), amount),
+    }
+}
+
+/// Mark a project completed and return whatever is still reserved for it.
+pub(crate) fn complete_project(env: &Env, project: &mut Project) {
+    let old_status = project.status.clone();
+    project.status = ProjectStatus::Completed;
+    store_project(env, project);
+    release_reservation(env, project);
+
+    let event = ProjectStatusChangedEvent {
+        project_id: project.id,
+        old_status,
+        new_status: ProjectStatus::Completed,
+    };
+    emit_event(env, MOD_MILESTONE, ACT_COMPLETED, event);
+}
+
+/// Approve reserving treasury funds for a project. Only signers of the
+/// project's treasury may approve; once the treasury's approval threshold is
+/// met, the project's unreleased total is committed from the treasury's
+/// available balance. A funded project still takes approvals: once every
+/// signer has approved, its milestone payments may overspend a soft budget.
+pub fn approve_project_funding(env: &Env, project_id: u64, signer: Address) -> bool {
+    let project = get_project(env, project_id).expect("project not found");
+    assert_project_active(&project, env);
+
+    let treasury = get_treasury(env, project.treasury_id).expect("treasury not found");
+    assert_signer(env, &treasury, &signer);
+
+    let mut funding = get_funding(env, project_id).unwrap_or(ProjectFunding {
+        project_id,
+        approvals: Vec::new(env),
+        funded: false,
+        reserved_amount: 0,
+        reserved_token: None,
+    });
+    if funding.approvals.contains(&signer) {
+        panic!("duplicate approval");
+    }
+    funding.approvals.push_back(signer);
+
+    let newly_funded = !funding.funded && funding.approvals.len() >= treasury.approval_threshold;
+    if newly_funded {
+        // Reserve in the asset milestone payments are actually drawn from
+        let conversion = get_payout_conversion(env, project_id);
+        let unreleased = project.total_amount - project.released_amount;
+        let (token, amount) = payout_source(env, &project, &conversion, unreleased);
+        commit_funds(env, &treasury, &token, amount);
+        funding.funded = true;
+        funding.reserved_amount = amount;
+        funding.reserved_token = token;
+    }
+    store_funding(env, &funding);
+
+    let event = ProjectFundingEvent {
+        project_id,
+        treasury_id: project.treasury_id,
+        funded: funding.funded,
+        reserved_amount: funding.reserved_amount,
+    };
+    let action = if newly_funded {
+        ACT_FUNDED
+    } else {
+        ACT_APPROVED
+    };
+    emit_event(env, MOD_MILESTONE, action, event);
+
+    true
+}
+
+/// Return whatever is still reserved for a project to its treasury.
+fn release_reservation(env: &Env, project: &Project) {
+    let mut funding = match get_funding(env, project.id) {
+        Some(f) if f.reserved_amount > 0 => f,
+        _ => return,
+    };
+    release_commitment(
+        env,
+        project.treasury_id,
+        &funding.reserved_token,
+        funding.reserved_amount,
+    );
+    funding.reserved_amount = 0;
+    store_funding(env, &funding);
+
+    let event = ProjectFundingEvent {
+        project_id: project.id,
+        treasury_id: project.treasury_id,
+        funded: funding.funded,
+        reserved_amount: 0,
+    };
+    emit_event(env, MOD_MILESTONE, ACT_UPDATED, event);
+}
+
+/// Pay this project's milestones in another asset than the treasury holds.
+///
+/// Each payment is valued in the project token, funded from the conversion's
+/// source asset and swapped into its target asset for the contributor. Once
+/// the project is funded the source asset is fixed. Only a guild admin may set
+/// or clear (`None`) the conversion.
+pub fn set_project_payout_conversion(
+    env: &Env,
+    project_id: u64,
+    caller: Address,
+    conversion: Option<PayoutConversion>,
+) -> bool {
+    caller.require_auth();
+
+    let project = get_project(env, project_id).expect("project not found");
+    assert_project_active(&project, env);
+
+    if !has_permission(env, project.guild_id, caller, Role::Admin) {
+        panic!("caller must be guild admin");
+    }
+
+    if let Some(ref c) = conversion {
+        validate_conversion(c);
+        if project.token.is_none() {
+            panic!("conversion requires a project token");
+        }
+        if c.treasury_id != project.treasury_id {
+            panic!("conversion must use the project treasury");
+        }
+    }
+
+    // A funded project's reservation is held in the asset it pays from
+    if let Some(funding) = get_funding(env, project_id) {
+        let source = match conversion {
+            Some(ref c) => Some(c.source_token.clone()),
+            None => project.token.clone(),
         };
-        emit_event(env, MOD_MILESTONE, ACT_COMPLETED, pe);
+        if funding.funded && funding.reserved_token != source {
+            panic!("payout source cannot change once funded");
+        }
     }
 
+    set_payout_conversion(env, project_id, &conversion);
+
+    let event = PayoutConversionUpdatedEvent {
+        project_id,
+        source_token: conversion.as_ref().map(|c| c.source_token.clone()),
+        target_token: conversion.as_ref().map(|c| c.target_token.clone()),
+        min_rate: conversion.map(|c| c.min_rate).unwrap_or(0),
+    };
+    emit_event(env, MOD_MILESTONE, ACT_UPDATED, event);
+
     true
 }
 
@@ -565,6 +1054,7 @@ pub fn cancel_project(env: &Env, project_id: u64, caller: Address) -> bool {
     let old_status = project.status.clone();
     project.status = ProjectStatus::Cancelled;
     store_project(env, &project);
+    release_reservation(env, &project);
 
     let event = ProjectStatusChangedEvent {
         project_id: project.id,
@@ -1,4 +1,4 @@
-﻿use soroban_sdk::{contracttype, Address, String};
+﻿use soroban_sdk::{contracttype, Address, String, Vec};
 
 /// Overall status of a project
 #[contracttype]
@@ -56,6 +56,8 @@ pub struct Milestone {
     pub last_updated_at: u64,
     pub version: u32,
     pub is_payment_released: bool,
+    /// Milestones in the same project that must be approved before this one starts
+    pub prerequisites: Vec<u64>,
 }
 
 /// Input used when creating a project with multiple milestones
@@ -66,6 +68,20 @@ pub struct MilestoneInput {
     pub description: String,
     pub payment_amount: i128,
     pub deadline: u64,
+    /// Positions (0-based) of other inputs in the same list that must be
+    /// approved first
+    pub prerequisites: Vec<u32>,
+}
+
+/// Project progress including milestones waiting on prerequisites
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProjectProgress {
+    pub completed: u32,
+    pub total: u32,
+    pub percentage: u32,
+    /// Pending milestones with at least one unapproved prerequisite
+    pub blocked: Vec<u64>,
 }
 
 // Events
@@ -136,3 +152,106 @@ pub struct MilestonePaymentReleasedEvent {
     pub token: Option<Address>,
     pub recipient: Address,
 }
+
+/// Treasury funds reserved for a project.
+///
+/// Signers of the project's treasury approve the reservation; once the
+/// treasury's approval threshold is met, the project's unreleased total is
+/// committed. `reserved_amount` shrinks as milestones are paid and is
+/// released back to the treasury when the project completes or is cancelled.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProjectFunding {
+    pub project_id: u64,
+    pub approvals: Vec<Address>,
+    pub funded: bool,
+    pub reserved_amount: i128,
+    /// Token the treasury pays from: the conversion's source token if the
+    /// project had a payout conversion when funded, the project token otherwise
+    pub reserved_token: Option<Address>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProjectFundingEvent {
+    pub project_id: u64,
+    pub treasury_id: u64,
+    pub funded: bool,
+    pub reserved_amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayoutConversionUpdatedEvent {
+    pub project_id: u64,
+    /// Both tokens are `None` when the conversion was cleared
+    pub source_token: Option<Address>,
+    pub target_token: Option<Address>,
+    pub min_rate: i128,
+}
+
+/// Reviewer set for a project's milestones.
+///
+/// When present, a submitted milestone is approved once
+/// `required_approvals` of `reviewers` approve its current `version`.
+/// Without a policy any guild admin may approve alone.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReviewPolicy {
+    pub project_id: u64,
+    pub reviewers: Vec<Address>,
+    pub required_approvals: u32,
+}
+
+/// A reviewer's verdict on one submitted version of a milestone
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MilestoneReview {
+    pub reviewer: Address,
+    pub approved: bool,
+    /// Rejection reason or optional approval comment
+    pub comment: String,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReviewPolicyUpdatedEvent {
+    pub project_id: u64,
+    /// Zero when the policy was cleared
+    pub reviewer_count: u32,
+    pub required_approvals: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MilestoneReviewedEvent {
+    pub project_id: u64,
+    pub milestone_id: u64,
+    pub version: u32,
+    pub reviewer: Address,
+    pub approved: bool,
+    pub approvals: u32,
+    pub required_approvals: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReviewSlaUpdatedEvent {
+    pub project_id: u64,
+    /// Zero when the SLA was cleared
+    pub sla_seconds: u64,
+}
+
+/// Emitted when a submission is approved because reviewers stayed silent
+/// past the project's review SLA
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MilestoneAutoApprovedEvent {
+    pub project_id: u64,
+    pub milestone_id: u64,
+    pub version: u32,
+    pub submitted_at: u64,
+    pub sla_seconds: u64,
+    pub triggered_by: Address,
+}
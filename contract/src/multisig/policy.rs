@@ -1,4 +1,5 @@
-﻿use crate::multisig::storage::{get_account, get_policy, store_policy};
+﻿use crate::multisig::registrar::is_safe_threshold;
+use crate::multisig::storage::{get_account, get_policy, store_policy};
 use crate::multisig::types::{
     OperationPolicy, OperationType, DEFAULT_TIMEOUT, TIMEOUT_24H, TIMEOUT_48H,
 };
@@ -21,9 +22,7 @@ pub fn ms_set_operation_policy(
         return Err(2u32);
     }
 
-    let min_safe = (account.signers.len() / 2) + 1;
-    if !require_all_signers && (min_signatures < min_safe || min_signatures > account.signers.len())
-    {
+    if !require_all_signers && !is_safe_threshold(env, &account, min_signatures) {
         return Err(1u32);
     }
     let timeout = if timeout_seconds == 0 {
@@ -1,23 +1,52 @@
-﻿use crate::multisig::storage::{get_account, next_account_id, store_account};
+﻿use crate::guild::membership::has_permission;
+use crate::guild::types::Role;
+use crate::multisig::storage::{
+    get_account, get_signer_weight, next_account_id, remove_signer_key, store_account,
+    store_account_guild, store_signer_key, store_signer_weight,
+};
 use crate::multisig::types::{AccountStatus, MultiSigAccount};
-use soroban_sdk::{Address, Env, Vec};
+use soroban_sdk::{Address, BytesN, Env, Vec};
+
+/// Combined signing weight of an account's current signers.
+pub fn total_weight(env: &Env, account: &MultiSigAccount) -> u32 {
+    account.signers.iter().fold(0u32, |acc, s| {
+        acc.saturating_add(get_signer_weight(env, account.id, &s))
+    })
+}
+
+/// Combined weight of `signatures` from an account's current signers.
+pub fn signed_weight(env: &Env, account: &MultiSigAccount, signatures: &Vec<Address>) -> u32 {
+    signatures
+        .iter()
+        .filter(|s| account.signers.contains(s))
+        .fold(0u32, |acc, s| {
+            acc.saturating_add(get_signer_weight(env, account.id, &s))
+        })
+}
+
+/// A threshold is safe when it needs a weighted majority and is reachable.
+pub fn is_safe_threshold(env: &Env, account: &MultiSigAccount, threshold: u32) -> bool {
+    let total = total_weight(env, account);
+    threshold > total / 2 && threshold <= total
+}
 
 pub fn ms_register_account(
     env: &Env,
     owner: Address,
     mut signers: Vec<Address>,
     threshold: u32,
-    _guild_id: Option<u64>,
+    guild_id: Option<u64>,
     _timeout_seconds: u64,
 ) -> Result<u64, u32> {
     owner.require_auth();
+    if let Some(guild_id) = guild_id {
+        if !has_permission(env, guild_id, owner.clone(), Role::Admin) {
+            return Err(3u32);
+        }
+    }
     if !signers.contains(&owner) {
         signers.push_back(owner.clone());
     }
-    let min_safe_threshold = (signers.len() / 2) + 1;
-    if threshold < min_safe_threshold || threshold > signers.len() {
-        return Err(1u32);
-    }
     let account_id = next_account_id(env);
     let account = MultiSigAccount {
         id: account_id,
@@ -27,7 +56,13 @@ pub fn ms_register_account(
         status: AccountStatus::Active,
         nonce: 0,
     };
+    if !is_safe_threshold(env, &account, threshold) {
+        return Err(1u32);
+    }
     store_account(env, account_id, &account);
+    if let Some(guild_id) = guild_id {
+        store_account_guild(env, account_id, guild_id);
+    }
     Ok(account_id)
 }
 
@@ -53,21 +88,34 @@ pub fn ms_unfreeze_account(env: &Env, account_id: u64, caller: Address) -> Resul
     Ok(())
 }
 
+/// Add a signer with `weight`, or change the weight of an existing signer,
+/// and set the threshold that must remain a reachable weighted majority.
 pub fn ms_add_signer(
     env: &Env,
     account_id: u64,
     new_signer: Address,
+    weight: u32,
     caller: Address,
+    new_threshold: u32,
 ) -> Result<(), u32> {
     caller.require_auth();
     let mut account = get_account(env, account_id).ok_or(2u32)?;
     if account.owner != caller {
         return Err(3u32);
     }
+    if weight == 0 {
+        return Err(1u32);
+    }
     if !account.signers.contains(&new_signer) {
-        account.signers.push_back(new_signer);
-        store_account(env, account_id, &account);
+        account.signers.push_back(new_signer.clone());
     }
+    store_signer_weight(env, account_id, &new_signer, weight);
+    if !is_safe_threshold(env, &account, new_threshold) {
+        return Err(1u32);
+    }
+    account.threshold = new_threshold;
+    account.nonce += 1;
+    store_account(env, account_id, &account);
     Ok(())
 }
 
@@ -85,11 +133,12 @@ pub fn ms_remove_signer(
     }
     if let Some(idx) = account.signers.first_index_of(&signer) {
         account.signers.remove(idx);
+        store_signer_weight(env, account_id, &signer, 1);
+        remove_signer_key(env, account_id, &signer);
         if account.signers.is_empty() {
             return Err(1u32);
         }
-        let min_safe = (account.signers.len() / 2) + 1;
-        if new_threshold < min_safe || new_threshold > account.signers.len() {
+        if !is_safe_threshold(env, &account, new_threshold) {
             return Err(1u32);
         }
         account.threshold = new_threshold;
@@ -99,23 +148,34 @@ pub fn ms_remove_signer(
     Ok(())
 }
 
+/// Replace `old_signer` with `new_signer`, who signs with `weight`, and set
+/// the threshold that must remain a reachable weighted majority.
 pub fn ms_rotate_signer(
     env: &Env,
     account_id: u64,
     old_signer: Address,
     new_signer: Address,
+    weight: u32,
     caller: Address,
+    new_threshold: u32,
 ) -> Result<(), u32> {
     caller.require_auth();
     let mut account = get_account(env, account_id).ok_or(2u32)?;
     if account.owner != caller {
         return Err(3u32);
     }
-    if account.signers.contains(&new_signer) {
+    if account.signers.contains(&new_signer) || weight == 0 {
         return Err(1u32);
     }
     if let Some(idx) = account.signers.first_index_of(&old_signer) {
-        account.signers.set(idx, new_signer);
+        account.signers.set(idx, new_signer.clone());
+        store_signer_weight(env, account_id, &old_signer, 1);
+        store_signer_weight(env, account_id, &new_signer, weight);
+        remove_signer_key(env, account_id, &old_signer);
+        if !is_safe_threshold(env, &account, new_threshold) {
+            return Err(1u32);
+        }
+        account.threshold = new_threshold;
         if account.owner == old_signer {
             account.owner = account.signers.get(idx).unwrap();
         }
@@ -126,6 +186,22 @@ pub fn ms_rotate_signer(
     Err(4u32)
 }
 
+/// Register the ed25519 public key a signer uses for off-chain signatures.
+pub fn ms_set_signer_key(
+    env: &Env,
+    account_id: u64,
+    signer: Address,
+    public_key: BytesN<32>,
+) -> Result<(), u32> {
+    signer.require_auth();
+    let account = get_account(env, account_id).ok_or(2u32)?;
+    if !account.signers.contains(&signer) {
+        return Err(4u32);
+    }
+    store_signer_key(env, account_id, &signer, &public_key);
+    Ok(())
+}
+
 pub fn ms_update_threshold(
     env: &Env,
     account_id: u64,
@@ -137,8 +213,7 @@ pub fn ms_update_threshold(
     if account.owner != caller {
         return Err(3u32);
     }
-    let min_safe = (account.signers.len() / 2) + 1;
-    if new_threshold < min_safe || new_threshold > account.signers.len() {
+    if !is_safe_threshold(env, &account, new_threshold) {
         return Err(1u32);
     }
     account.threshold = new_threshold;
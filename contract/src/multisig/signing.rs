@@ -1,17 +1,118 @@
-﻿use crate::multisig::policy::ms_get_operation_policy;
+﻿use crate::integration::auth::require_admin;
+use crate::multisig::policy::ms_get_operation_policy;
+use crate::multisig::registrar::{signed_weight, total_weight};
 use crate::multisig::storage::{
-    get_account, get_operation, next_operation_id, store_account, store_operation, DataKey,
+    get_account, get_account_guild, get_operation, get_operation_call, get_operation_result,
+    get_signer_key, is_allowed_call, next_operation_id, set_allowed_call, store_account,
+    store_operation, store_operation_call, store_operation_result, DataKey,
 };
 use crate::multisig::types::{
-    AccountStatus, MultiSigOperation, OperationStatus, OperationType, TIMEOUT_24H, TIMEOUT_48H,
+    AccountStatus, MultiSigOperation, OperationPayload, OperationStatus, OperationType,
+    SignerSignature, SigningPayload, TIMEOUT_24H, TIMEOUT_48H,
 };
-use soroban_sdk::{Address, Env, String, Vec};
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{Address, BytesN, Env, String, Symbol, Val, Vec};
+
+/// Hash of a payload as committed to by `ms_propose_operation`.
+pub fn ms_payload_hash(env: &Env, payload: &OperationPayload) -> BytesN<32> {
+    env.crypto().sha256(&payload.clone().to_xdr(env)).to_bytes()
+}
 
 pub fn ms_propose_operation(
     env: &Env,
     account_id: u64,
     op_type: OperationType,
     description: String,
+    payload_hash: BytesN<32>,
+    proposer: Address,
+) -> Result<u64, u32> {
+    if op_type == OperationType::ContractInvocation {
+        return Err(11u32);
+    }
+    propose(
+        env,
+        account_id,
+        op_type,
+        description,
+        payload_hash,
+        proposer,
+    )
+}
+
+/// Token functions that could move funds this contract holds. They are never
+/// invocable, even if an admin allows the target.
+const FUND_MOVING_FUNCTIONS: [&str; 5] =
+    ["transfer", "transfer_from", "approve", "burn", "burn_from"];
+
+/// Allow or disallow multisig accounts to invoke `function` on `target`
+/// (contract admin only). Nothing is invocable until allowed.
+pub fn ms_set_allowed_call(
+    env: &Env,
+    target: Address,
+    function: Symbol,
+    allowed: bool,
+    caller: Address,
+) -> Result<(), u32> {
+    require_admin(env, &caller);
+    if allowed && !is_invocable(env, &target, &function) {
+        return Err(15u32);
+    }
+    set_allowed_call(env, &target, &function, allowed);
+    Ok(())
+}
+
+fn is_invocable(env: &Env, target: &Address, function: &Symbol) -> bool {
+    // Soroban does not allow a contract to re-enter itself.
+    *target != env.current_contract_address()
+        && !FUND_MOVING_FUNCTIONS
+            .iter()
+            .any(|name| *function == Symbol::new(env, name))
+}
+
+/// Propose a call to `function` on `target` with `args`, performed by this
+/// contract once the operation is executed.
+///
+/// Only accounts bound to a guild or owned by the contract admin may propose
+/// calls, and only to `(target, function)` pairs the admin has allowed.
+pub fn ms_propose_contract_call(
+    env: &Env,
+    account_id: u64,
+    description: String,
+    target: Address,
+    function: Symbol,
+    args: Vec<Val>,
+    proposer: Address,
+) -> Result<u64, u32> {
+    if target == env.current_contract_address() {
+        return Err(12u32);
+    }
+    let account = get_account(env, account_id).ok_or(1u32)?;
+    let admin: Option<Address> = env.storage().instance().get(&crate::DataKey::Admin);
+    if get_account_guild(env, account_id).is_none() && admin != Some(account.owner) {
+        return Err(14u32);
+    }
+    if !is_invocable(env, &target, &function) || !is_allowed_call(env, &target, &function) {
+        return Err(15u32);
+    }
+    let call = OperationPayload::ContractCall(target, function, args);
+    let op_id = propose(
+        env,
+        account_id,
+        OperationType::ContractInvocation,
+        description,
+        ms_payload_hash(env, &call),
+        proposer,
+    )?;
+    store_operation_call(env, op_id, &call);
+    Ok(op_id)
+}
+
+fn propose(
+    env: &Env,
+    account_id: u64,
+    op_type: OperationType,
+    description: String,
+    payload_hash: BytesN<32>,
     proposer: Address,
 ) -> Result<u64, u32> {
     proposer.require_auth();
@@ -33,6 +134,7 @@ pub fn ms_propose_operation(
         account_id,
         op_type,
         description,
+        payload_hash,
         proposer,
         signatures,
         nonce,
@@ -67,6 +169,62 @@ pub fn ms_sign_operation(env: &Env, op_id: u64, signer: Address) -> Result<u32,
 
 pub fn ms_execute_operation(env: &Env, op_id: u64, executor: Address) -> Result<(), u32> {
     executor.require_auth();
+    execute_pending(env, op_id)
+}
+
+/// Digest a signer signs off-chain with their ed25519 key to approve `op_id`.
+pub fn ms_signing_digest(env: &Env, op_id: u64) -> Result<BytesN<32>, u32> {
+    let op = get_operation(env, op_id).ok_or(3u32)?;
+    let payload = SigningPayload {
+        contract: env.current_contract_address(),
+        account_id: op.account_id,
+        operation_id: op.id,
+        nonce: op.nonce,
+        payload_hash: op.payload_hash,
+    };
+    Ok(env.crypto().sha256(&payload.to_xdr(env)).to_bytes())
+}
+
+/// Add off-chain signatures to an operation and execute it in one call.
+///
+/// Each signature must verify against the signer's registered key over
+/// `ms_signing_digest`; signers that already signed are skipped.
+pub fn ms_execute_with_signatures(
+    env: &Env,
+    op_id: u64,
+    signatures: Vec<SignerSignature>,
+    submitter: Address,
+) -> Result<(), u32> {
+    submitter.require_auth();
+    let mut operation = get_operation(env, op_id).ok_or(3u32)?;
+    let account = get_account(env, operation.account_id).ok_or(1u32)?;
+    if operation.status != OperationStatus::Pending {
+        return Err(4u32);
+    }
+    if env.ledger().timestamp() > operation.expires_at {
+        operation.status = OperationStatus::Expired;
+        store_operation(env, op_id, &operation);
+        return Err(5u32);
+    }
+
+    let digest = ms_signing_digest(env, op_id)?;
+    let message = digest.into();
+    for sig in signatures.iter() {
+        if !account.signers.contains(&sig.signer) {
+            return Err(6u32);
+        }
+        let key = get_signer_key(env, account.id, &sig.signer).ok_or(13u32)?;
+        env.crypto().ed25519_verify(&key, &message, &sig.signature);
+        if !operation.signatures.contains(&sig.signer) {
+            operation.signatures.push_back(sig.signer);
+        }
+    }
+    store_operation(env, op_id, &operation);
+
+    execute_pending(env, op_id)
+}
+
+fn execute_pending(env: &Env, op_id: u64) -> Result<(), u32> {
     let mut operation = get_operation(env, op_id).ok_or(3u32)?;
     let account = get_account(env, operation.account_id).ok_or(1u32)?;
     if operation.status != OperationStatus::Pending {
@@ -78,24 +236,53 @@ pub fn ms_execute_operation(env: &Env, op_id: u64, executor: Address) -> Result<
         return Err(5u32);
     }
     let policy = ms_get_operation_policy(env, account.id, operation.op_type.clone());
-    let required_sigs = if policy.require_all_signers {
-        account.signers.len()
+    // Thresholds are sums of signer weights.
+    let required_weight = if policy.require_all_signers {
+        total_weight(env, &account)
     } else if policy.min_signatures > 0 {
         policy.min_signatures
     } else {
         account.threshold
     };
-    if operation.signatures.len() < required_sigs {
+    if signed_weight(env, &account, &operation.signatures) < required_weight {
         return Err(7u32);
     }
     if policy.require_owner_signature && !operation.signatures.contains(&account.owner) {
         return Err(8u32);
     }
+
+    let call = if operation.op_type == OperationType::ContractInvocation {
+        match get_operation_call(env, op_id) {
+            Some(OperationPayload::ContractCall(target, function, args)) => {
+                // The admin may have withdrawn the permission since proposal.
+                if !is_invocable(env, &target, &function)
+                    || !is_allowed_call(env, &target, &function)
+                {
+                    return Err(15u32);
+                }
+                Some((target, function, args))
+            }
+            _ => None,
+        }
+    } else {
+        None
+    };
+
     operation.status = OperationStatus::Executed;
     store_operation(env, op_id, &operation);
+
+    if let Some((target, function, args)) = call {
+        let result: Val = env.invoke_contract(&target, &function, args);
+        store_operation_result(env, op_id, &result);
+    }
     Ok(())
 }
 
+/// Return value recorded for an executed contract invocation.
+pub fn ms_get_operation_result(env: &Env, op_id: u64) -> Option<Val> {
+    get_operation_result(env, op_id)
+}
+
 pub fn ms_cancel_operation(env: &Env, op_id: u64, caller: Address) -> Result<(), u32> {
     caller.require_auth();
     let mut op = get_operation(env, op_id).ok_or(3u32)?;
@@ -155,18 +342,26 @@ pub fn ms_get_operation_status(env: &Env, op_id: u64) -> Result<MultiSigOperatio
     get_operation(env, op_id).ok_or(3u32)
 }
 
-pub fn ms_require_executed_operation(
+/// Check that an executed operation approved exactly `payload`, then mark it
+/// consumed so it cannot gate another call.
+pub fn ms_consume_executed_operation(
     env: &Env,
     op_id: u64,
     expected_type: OperationType,
+    payload: &OperationPayload,
 ) -> Result<(), u32> {
-    let op = get_operation(env, op_id).ok_or(3u32)?;
+    let mut op = get_operation(env, op_id).ok_or(3u32)?;
     if op.status != OperationStatus::Executed {
         return Err(4u32);
     }
     if op.op_type != expected_type {
         return Err(9u32);
     }
+    if op.payload_hash != ms_payload_hash(env, payload) {
+        return Err(10u32);
+    }
+    op.status = OperationStatus::Consumed;
+    store_operation(env, op_id, &op);
     Ok(())
 }
 
@@ -1,11 +1,19 @@
-﻿use crate::multisig::types::{MultiSigAccount, MultiSigOperation, OperationPolicy, OperationType};
-use soroban_sdk::{contracttype, Env};
+﻿use crate::multisig::types::{
+    MultiSigAccount, MultiSigOperation, OperationPayload, OperationPolicy, OperationType,
+};
+use soroban_sdk::{contracttype, Address, BytesN, Env, Symbol, Val};
 
 #[contracttype]
 pub enum DataKey {
     MultiSigAccount(u64),
     MultiSigOperation(u64),
     OperationPolicy(u64, OperationType),
+    OperationCall(u64),
+    OperationResult(u64),
+    SignerKey(u64, Address),
+    SignerWeight(u64, Address),
+    AccountGuild(u64),
+    AllowedCall(Address, Symbol),
     AccountCounter,
     OperationCounter,
 }
@@ -71,3 +79,91 @@ pub fn get_policy(env: &Env, account_id: u64, op_type: OperationType) -> Option<
         .persistent()
         .get(&DataKey::OperationPolicy(account_id, op_type))
 }
+
+pub fn store_operation_call(env: &Env, op_id: u64, call: &OperationPayload) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::OperationCall(op_id), call);
+}
+
+pub fn get_operation_call(env: &Env, op_id: u64) -> Option<OperationPayload> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::OperationCall(op_id))
+}
+
+pub fn store_operation_result(env: &Env, op_id: u64, result: &Val) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::OperationResult(op_id), result);
+}
+
+pub fn get_operation_result(env: &Env, op_id: u64) -> Option<Val> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::OperationResult(op_id))
+}
+
+pub fn store_signer_key(env: &Env, account_id: u64, signer: &Address, key: &BytesN<32>) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::SignerKey(account_id, signer.clone()), key);
+}
+
+pub fn get_signer_key(env: &Env, account_id: u64, signer: &Address) -> Option<BytesN<32>> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SignerKey(account_id, signer.clone()))
+}
+
+pub fn remove_signer_key(env: &Env, account_id: u64, signer: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::SignerKey(account_id, signer.clone()));
+}
+
+/// Guild an account was registered for; only guild-bound accounts (or the
+/// contract admin's) may propose contract invocations.
+pub fn store_account_guild(env: &Env, account_id: u64, guild_id: u64) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::AccountGuild(account_id), &guild_id);
+}
+
+pub fn get_account_guild(env: &Env, account_id: u64) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AccountGuild(account_id))
+}
+
+pub fn set_allowed_call(env: &Env, target: &Address, function: &Symbol, allowed: bool) {
+    let key = DataKey::AllowedCall(target.clone(), function.clone());
+    if allowed {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+pub fn is_allowed_call(env: &Env, target: &Address, function: &Symbol) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::AllowedCall(target.clone(), function.clone()))
+}
+
+/// Signing weight of `signer` on an account; signers without one weigh 1.
+pub fn get_signer_weight(env: &Env, account_id: u64, signer: &Address) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SignerWeight(account_id, signer.clone()))
+        .unwrap_or(1)
+}
+
+pub fn store_signer_weight(env: &Env, account_id: u64, signer: &Address, weight: u32) {
+    let key = DataKey::SignerWeight(account_id, signer.clone());
+    if weight == 1 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &weight);
+    }
+}
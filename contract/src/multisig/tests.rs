@@ -1,11 +1,29 @@
 #[cfg(test)]
 mod tests {
     use crate::governance::{ProposalType, VoteDecision};
-    use crate::multisig::types::{OperationStatus, OperationType, TIMEOUT_24H, TIMEOUT_48H};
+    use crate::multisig::types::{
+        OperationPayload, OperationStatus, OperationType, SignerSignature, TIMEOUT_24H, TIMEOUT_48H,
+    };
     use crate::InitializerProof;
     use crate::{StellarGuildsContract, StellarGuildsContractClient};
+    use ed25519_dalek::{Signer, SigningKey};
     use soroban_sdk::testutils::{Address as _, Ledger as _, LedgerInfo};
-    use soroban_sdk::{Address, Env, String, Vec};
+    use soroban_sdk::{
+        contract, contractimpl, symbol_short, Address, BytesN, Env, IntoVal, String, TryFromVal,
+        Val, Vec,
+    };
+
+    #[contract]
+    struct CounterContract;
+
+    #[contractimpl]
+    impl CounterContract {
+        pub fn increment(env: Env, by: u32) -> u32 {
+            let count: u32 = env.storage().instance().get(&0u32).unwrap_or(0) + by;
+            env.storage().instance().set(&0u32, &count);
+            count
+        }
+    }
 
     fn setup_env() -> (Env, Address, Address, Address) {
         let env = Env::default();
@@ -36,6 +54,10 @@ mod tests {
         });
     }
 
+    fn no_payload(env: &Env) -> BytesN<32> {
+        BytesN::from_array(env, &[0u8; 32])
+    }
+
     fn register_ms_account(
         env: &Env,
         client: &StellarGuildsContractClient<'_>,
@@ -62,6 +84,7 @@ mod tests {
             &account_id,
             &OperationType::TreasuryWithdrawal,
             &desc,
+            &no_payload(&env),
             &owner,
         );
 
@@ -83,6 +106,7 @@ mod tests {
             &account_id,
             &OperationType::TreasuryWithdrawal,
             &desc,
+            &no_payload(&env),
             &owner,
         );
 
@@ -114,6 +138,7 @@ mod tests {
             &account_id,
             &OperationType::EmergencyAction,
             &desc,
+            &no_payload(&env),
             &owner,
         );
 
@@ -151,6 +176,7 @@ mod tests {
             &account_id,
             &OperationType::GovernanceUpdate,
             &desc,
+            &no_payload(&env),
             &signer1,
         );
         client.ms_sign_operation(&op_id, &signer2);
@@ -178,7 +204,7 @@ mod tests {
         let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
         let replacement = Address::generate(&env);
 
-        assert!(client.ms_rotate_signer(&account_id, &signer1, &replacement, &owner));
+        assert!(client.ms_rotate_signer(&account_id, &signer1, &replacement, &1, &owner, &2));
         let account = client.ms_get_account(&account_id);
         assert!(account.signers.contains(&replacement));
         assert!(!account.signers.contains(&signer1));
@@ -206,10 +232,12 @@ mod tests {
         // Multisig gate setup
         let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
         let op_desc = String::from_str(&env, "Approve treasury withdrawal");
+        let payload = OperationPayload::TreasuryWithdrawal(treasury_id, signer1.clone(), 100, None);
         let op_id = client.ms_propose_operation(
             &account_id,
             &OperationType::TreasuryWithdrawal,
             &op_desc,
+            &client.ms_hash_payload(&payload),
             &owner,
         );
         client.ms_sign_operation(&op_id, &signer1);
@@ -227,6 +255,96 @@ mod tests {
         );
         // Tx id 1 is the deposit; withdrawal proposal is the next tx.
         assert_eq!(tx_id, 2);
+        assert_eq!(
+            client.ms_get_operation(&op_id).status,
+            OperationStatus::Consumed
+        );
+    }
+
+    fn executed_withdrawal_op(
+        env: &Env,
+        client: &StellarGuildsContractClient<'_>,
+        owner: &Address,
+        signer1: &Address,
+        signer2: &Address,
+        payload: &OperationPayload,
+    ) -> (u64, u64) {
+        let guild_id = client.create_guild(
+            &String::from_str(env, "Treasury Guild"),
+            &String::from_str(env, "Guild for treasury integration"),
+            owner,
+            &None::<InitializerProof>,
+        );
+        let mut treasury_signers = Vec::new(env);
+        treasury_signers.push_back(owner.clone());
+        let treasury_id = client.initialize_treasury(&guild_id, &treasury_signers, &1u32);
+        client.deposit_treasury(&treasury_id, owner, &1_000i128, &None);
+
+        let account_id = register_ms_account(env, client, owner, signer1, signer2);
+        let op_id = client.ms_propose_operation(
+            &account_id,
+            &OperationType::TreasuryWithdrawal,
+            &String::from_str(env, "Approve treasury withdrawal"),
+            &client.ms_hash_payload(payload),
+            owner,
+        );
+        client.ms_sign_operation(&op_id, signer1);
+        assert!(client.ms_execute_operation(&op_id, signer2));
+        (op_id, treasury_id)
+    }
+
+    #[test]
+    #[should_panic(expected = "ms_propose_treasury_withdrawal gate error: 10")]
+    fn test_treasury_gate_rejects_different_amount() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+
+        let payload = OperationPayload::TreasuryWithdrawal(1, signer1.clone(), 100, None);
+        let (op_id, treasury_id) =
+            executed_withdrawal_op(&env, &client, &owner, &signer1, &signer2, &payload);
+
+        client.ms_propose_treasury_withdrawal(
+            &op_id,
+            &treasury_id,
+            &owner,
+            &signer1,
+            &900i128,
+            &None,
+            &String::from_str(&env, "more than approved"),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ms_propose_treasury_withdrawal gate error: 4")]
+    fn test_treasury_gate_operation_cannot_be_replayed() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+
+        let payload = OperationPayload::TreasuryWithdrawal(1, signer1.clone(), 100, None);
+        let (op_id, treasury_id) =
+            executed_withdrawal_op(&env, &client, &owner, &signer1, &signer2, &payload);
+
+        let reason = String::from_str(&env, "approved once");
+        client.ms_propose_treasury_withdrawal(
+            &op_id,
+            &treasury_id,
+            &owner,
+            &signer1,
+            &100i128,
+            &None,
+            &reason,
+        );
+        client.ms_propose_treasury_withdrawal(
+            &op_id,
+            &treasury_id,
+            &owner,
+            &signer1,
+            &100i128,
+            &None,
+            &reason,
+        );
     }
 
     #[test]
@@ -255,10 +373,12 @@ mod tests {
 
         let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
         let op_desc = String::from_str(&env, "Approve governance execution");
+        let payload = OperationPayload::GovernanceExecution(proposal_id);
         let op_id = client.ms_propose_operation(
             &account_id,
             &OperationType::GovernanceUpdate,
             &op_desc,
+            &client.ms_hash_payload(&payload),
             &owner,
         );
         client.ms_sign_operation(&op_id, &signer1);
@@ -275,7 +395,7 @@ mod tests {
         let signer3 = Address::generate(&env);
         let replacement = Address::generate(&env);
 
-        assert!(client.ms_add_signer(&account_id, &signer3, &owner));
+        assert!(client.ms_add_signer(&account_id, &signer3, &1, &owner, &3));
         let account = client.ms_get_account(&account_id);
         assert!(account.signers.contains(&signer3));
 
@@ -293,7 +413,7 @@ mod tests {
             crate::multisig::types::AccountStatus::Active
         );
 
-        assert!(client.ms_rotate_signer(&account_id, &signer3, &replacement, &owner));
+        assert!(client.ms_rotate_signer(&account_id, &signer3, &replacement, &1, &owner, &3));
         let account = client.ms_get_account(&account_id);
         assert!(account.signers.contains(&replacement));
         assert!(!account.signers.contains(&signer3));
@@ -315,6 +435,7 @@ mod tests {
             &account_id,
             &OperationType::EmergencyAction,
             &String::from_str(&env, "cancel me"),
+            &no_payload(&env),
             &owner,
         );
         assert_eq!(client.ms_get_pending_ops(&account_id).len(), 1);
@@ -329,6 +450,7 @@ mod tests {
             &account_id,
             &OperationType::GovernanceUpdate,
             &String::from_str(&env, "expire me"),
+            &no_payload(&env),
             &owner,
         );
         set_timestamp(&env, env.ledger().timestamp() + TIMEOUT_48H + 5);
@@ -342,12 +464,14 @@ mod tests {
             &account_id,
             &OperationType::TreasuryWithdrawal,
             &String::from_str(&env, "sweep me"),
+            &no_payload(&env),
             &owner,
         );
         let op_d = client.ms_propose_operation(
             &account_id,
             &OperationType::TreasuryWithdrawal,
             &String::from_str(&env, "expire now"),
+            &no_payload(&env),
             &owner,
         );
         assert!(client.ms_emergency_extend_timeout(&op_c, &TIMEOUT_24H, &owner));
@@ -419,7 +543,369 @@ mod tests {
             &account_id,
             &OperationType::EmergencyAction,
             &String::from_str(&env, "blocked"),
+            &no_payload(&env),
+            &owner,
+        );
+    }
+
+    /// Contract with a known admin, and a 2-of-3 account bound to `owner`'s guild.
+    fn invocation_setup(
+        env: &Env,
+        owner: &Address,
+        signer1: &Address,
+        signer2: &Address,
+    ) -> (StellarGuildsContractClient<'static>, Address, u64) {
+        let contract_id = env.register_contract(None, StellarGuildsContract);
+        let client = StellarGuildsContractClient::new(env, &contract_id);
+        let admin = Address::generate(env);
+        client.initialize(&admin);
+        let guild_id = client.create_guild(
+            &String::from_str(env, "Guild"),
+            &String::from_str(env, "Desc"),
+            owner,
+            &None::<InitializerProof>,
+        );
+        let mut signers = Vec::new(env);
+        signers.push_back(signer1.clone());
+        signers.push_back(signer2.clone());
+        let account_id =
+            client.ms_register_account(owner, &signers, &2u32, &Some(guild_id), &TIMEOUT_24H);
+        (client, admin, account_id)
+    }
+
+    #[test]
+    fn test_contract_invocation_runs_after_threshold() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let (client, admin, account_id) = invocation_setup(&env, &owner, &signer1, &signer2);
+        let counter_id = env.register_contract(None, CounterContract);
+        client.ms_set_allowed_call(&counter_id, &symbol_short!("increment"), &true, &admin);
+
+        let mut args: Vec<Val> = Vec::new(&env);
+        args.push_back(5u32.into_val(&env));
+        let op_id = client.ms_propose_contract_call(
+            &account_id,
+            &String::from_str(&env, "bump counter"),
+            &counter_id,
+            &symbol_short!("increment"),
+            &args,
             &owner,
         );
+        assert_eq!(
+            client.ms_get_operation(&op_id).op_type,
+            OperationType::ContractInvocation
+        );
+        assert!(client.ms_get_operation_result(&op_id).is_none());
+
+        client.ms_sign_operation(&op_id, &signer1);
+        assert!(client.ms_execute_operation(&op_id, &signer2));
+
+        let result = client.ms_get_operation_result(&op_id).unwrap();
+        assert_eq!(u32::try_from_val(&env, &result).unwrap(), 5);
+        assert_eq!(
+            client.ms_get_operation(&op_id).status,
+            OperationStatus::Executed
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ms_propose_operation error: 11")]
+    fn test_contract_invocation_requires_call_data() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+
+        client.ms_propose_operation(
+            &account_id,
+            &OperationType::ContractInvocation,
+            &String::from_str(&env, "no call"),
+            &no_payload(&env),
+            &owner,
+        );
+    }
+
+    fn weighted_account(
+        env: &Env,
+        client: &StellarGuildsContractClient<'_>,
+        owner: &Address,
+        signer1: &Address,
+        signer2: &Address,
+        founder: &Address,
+    ) -> u64 {
+        let account_id = register_ms_account(env, client, owner, signer1, signer2);
+        assert!(client.ms_add_signer(&account_id, founder, &3, owner, &4));
+        assert_eq!(client.ms_get_account(&account_id).threshold, 4);
+        assert!(client.ms_set_policy(
+            &account_id,
+            &OperationType::GuildConfigChange,
+            &4,
+            &false,
+            &TIMEOUT_24H,
+            &false,
+            owner,
+        ));
+        account_id
+    }
+
+    #[test]
+    fn test_weighted_signers_meet_policy_by_weight() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let founder = Address::generate(&env);
+        let account_id = weighted_account(&env, &client, &owner, &signer1, &signer2, &founder);
+
+        // One rotating member (1) plus the founder (3) reach the weight of 4.
+        let op_id = client.ms_propose_operation(
+            &account_id,
+            &OperationType::GuildConfigChange,
+            &String::from_str(&env, "change config"),
+            &no_payload(&env),
+            &signer1,
+        );
+        client.ms_sign_operation(&op_id, &founder);
+        assert!(client.ms_execute_operation(&op_id, &signer1));
+    }
+
+    #[test]
+    #[should_panic(expected = "ms_execute_operation error: 7")]
+    fn test_weighted_signers_below_policy_weight_fail() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let founder = Address::generate(&env);
+        let account_id = weighted_account(&env, &client, &owner, &signer1, &signer2, &founder);
+
+        // Three equal-weight signers only add up to 3.
+        let op_id = client.ms_propose_operation(
+            &account_id,
+            &OperationType::GuildConfigChange,
+            &String::from_str(&env, "change config"),
+            &no_payload(&env),
+            &signer1,
+        );
+        client.ms_sign_operation(&op_id, &signer2);
+        client.ms_sign_operation(&op_id, &owner);
+        client.ms_execute_operation(&op_id, &signer1);
+    }
+
+    #[test]
+    #[should_panic(expected = "ms_rotate_signer error: 1")]
+    fn test_rotation_cannot_leave_threshold_unreachable() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let founder = Address::generate(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+        client.ms_add_signer(&account_id, &founder, &3, &owner, &4);
+        client.ms_update_threshold(&account_id, &5, &owner);
+
+        // Total weight would drop from 6 to 4, below the threshold of 5.
+        client.ms_rotate_signer(
+            &account_id,
+            &founder,
+            &Address::generate(&env),
+            &1,
+            &owner,
+            &5,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ms_add_signer error: 1")]
+    fn test_reweighting_cannot_leave_threshold_below_majority() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let founder = Address::generate(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+        assert_eq!(client.ms_get_account(&account_id).threshold, 2);
+
+        // Total weight would grow to 6, so a threshold of 2 is no longer a majority.
+        client.ms_add_signer(&account_id, &founder, &3, &owner, &2);
+    }
+
+    fn register_key(
+        env: &Env,
+        client: &StellarGuildsContractClient<'_>,
+        account_id: u64,
+        signer: &Address,
+        seed: u8,
+    ) -> SigningKey {
+        let key = SigningKey::from_bytes(&[seed; 32]);
+        let public_key = BytesN::from_array(env, &key.verifying_key().to_bytes());
+        assert!(client.ms_set_signer_key(&account_id, signer, &public_key));
+        key
+    }
+
+    fn sign_offchain(
+        env: &Env,
+        client: &StellarGuildsContractClient<'_>,
+        op_id: u64,
+        signer: &Address,
+        key: &SigningKey,
+    ) -> SignerSignature {
+        let digest = client.ms_signing_digest(&op_id);
+        let signature = key.sign(&digest.to_array()).to_bytes();
+        SignerSignature {
+            signer: signer.clone(),
+            signature: BytesN::from_array(env, &signature),
+        }
+    }
+
+    fn propose_config_change(
+        env: &Env,
+        client: &StellarGuildsContractClient<'_>,
+        account_id: u64,
+        owner: &Address,
+    ) -> u64 {
+        client.ms_propose_operation(
+            &account_id,
+            &OperationType::GuildConfigChange,
+            &String::from_str(env, "change config"),
+            &no_payload(env),
+            owner,
+        )
+    }
+
+    #[test]
+    fn test_execute_with_aggregated_offchain_signatures() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+        assert!(client.ms_set_policy(
+            &account_id,
+            &OperationType::GuildConfigChange,
+            &3,
+            &false,
+            &TIMEOUT_24H,
+            &false,
+            &owner,
+        ));
+        let key1 = register_key(&env, &client, account_id, &signer1, 1);
+        let key2 = register_key(&env, &client, account_id, &signer2, 2);
+
+        let op_id = propose_config_change(&env, &client, account_id, &owner);
+        let mut signatures = Vec::new(&env);
+        signatures.push_back(sign_offchain(&env, &client, op_id, &signer1, &key1));
+        signatures.push_back(sign_offchain(&env, &client, op_id, &signer2, &key2));
+
+        let submitter = Address::generate(&env);
+        assert!(client.ms_execute_with_signatures(&op_id, &signatures, &submitter));
+
+        let op = client.ms_get_operation(&op_id);
+        assert_eq!(op.status, OperationStatus::Executed);
+        assert_eq!(op.signatures.len(), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_offchain_signature_cannot_be_replayed_on_another_operation() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+        let key1 = register_key(&env, &client, account_id, &signer1, 1);
+
+        let first = propose_config_change(&env, &client, account_id, &owner);
+        let second = propose_config_change(&env, &client, account_id, &owner);
+
+        let mut signatures = Vec::new(&env);
+        signatures.push_back(sign_offchain(&env, &client, first, &signer1, &key1));
+        client.ms_execute_with_signatures(&second, &signatures, &owner);
+    }
+
+    #[test]
+    #[should_panic(expected = "ms_execute_with_signatures error: 13")]
+    fn test_offchain_signature_requires_registered_key() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let client = init_client(&env);
+        let account_id = register_ms_account(&env, &client, &owner, &signer1, &signer2);
+        let key1 = SigningKey::from_bytes(&[1; 32]);
+
+        let op_id = propose_config_change(&env, &client, account_id, &owner);
+        let mut signatures = Vec::new(&env);
+        signatures.push_back(sign_offchain(&env, &client, op_id, &signer1, &key1));
+        client.ms_execute_with_signatures(&op_id, &signatures, &owner);
+    }
+
+    #[test]
+    fn test_contract_invocation_cannot_move_contract_funds() {
+        let (env, owner, signer1, signer2) = setup_env();
+        env.mock_all_auths();
+        let (client, admin, account_id) = invocation_setup(&env, &owner, &signer1, &signer2);
+        let counter_id = env.register_contract(None, CounterContract);
+        let token = env
+            .register_stellar_asset_contract_v2(admin.clone())
+            .address();
+        let attacker = Address::generate(&env);
+        let transfer = symbol_short!("transfer");
+        let mut args: Vec<Val> = Vec::new(&env);
+        args.push_back(client.address.into_val(&env));
+        args.push_back(attacker.into_val(&env));
+        args.push_back(1_000i128.into_val(&env));
+
+        // Token movements can't be allowed, let alone proposed
+        assert!(client
+            .try_ms_set_allowed_call(&token, &transfer, &true, &admin)
+            .is_err());
+        assert!(client
+            .try_ms_propose_contract_call(
+                &account_id,
+                &String::from_str(&env, "drain"),
+                &token,
+                &transfer,
+                &args,
+                &owner,
+            )
+            .is_err());
+
+        // Only the admin manages the allowlist
+        assert!(client
+            .try_ms_set_allowed_call(&counter_id, &symbol_short!("increment"), &true, &attacker)
+            .is_err());
+        client.ms_set_allowed_call(&counter_id, &symbol_short!("increment"), &true, &admin);
+
+        // A self-registered account with no guild can't propose invocations
+        let mut solo = Vec::new(&env);
+        solo.push_back(attacker.clone());
+        let attacker_account =
+            client.ms_register_account(&attacker, &solo, &1u32, &None, &TIMEOUT_24H);
+        let mut bump: Vec<Val> = Vec::new(&env);
+        bump.push_back(1u32.into_val(&env));
+        assert!(client
+            .try_ms_propose_contract_call(
+                &attacker_account,
+                &String::from_str(&env, "bump"),
+                &counter_id,
+                &symbol_short!("increment"),
+                &bump,
+                &attacker,
+            )
+            .is_err());
+        // ... and can't bind itself to a guild it doesn't administer
+        assert!(client
+            .try_ms_register_account(&attacker, &solo, &1u32, &Some(1u64), &TIMEOUT_24H)
+            .is_err());
+
+        // Withdrawing the permission blocks calls already proposed
+        let op_id = client.ms_propose_contract_call(
+            &account_id,
+            &String::from_str(&env, "bump"),
+            &counter_id,
+            &symbol_short!("increment"),
+            &bump,
+            &owner,
+        );
+        client.ms_sign_operation(&op_id, &signer1);
+        client.ms_set_allowed_call(&counter_id, &symbol_short!("increment"), &false, &admin);
+        assert!(client.try_ms_execute_operation(&op_id, &signer2).is_err());
+        assert_eq!(
+            client.ms_get_operation(&op_id).status,
+            OperationStatus::Pending
+        );
     }
 }
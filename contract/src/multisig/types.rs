@@ -1,4 +1,4 @@
-﻿use soroban_sdk::{contracttype, Address, String, Vec};
+﻿use soroban_sdk::{contracttype, Address, BytesN, String, Symbol, Val, Vec};
 
 pub const TIMEOUT_24H: u64 = 86_400;
 pub const TIMEOUT_48H: u64 = 172_800;
@@ -18,6 +18,8 @@ pub enum OperationStatus {
     Executed,
     Expired,
     Cancelled,
+    /// Executed and already used by a gated entrypoint.
+    Consumed,
 }
 
 #[contracttype]
@@ -27,6 +29,8 @@ pub enum OperationType {
     GovernanceUpdate,
     GuildConfigChange,
     EmergencyAction,
+    /// Arbitrary contract call performed by `ms_execute_operation`.
+    ContractInvocation,
 }
 
 #[contracttype]
@@ -35,6 +39,8 @@ pub struct MultiSigAccount {
     pub id: u64,
     pub owner: Address,
     pub signers: Vec<Address>,
+    /// Sum of signer weights required to execute.
+    /// Weights are stored separately; signers without one weigh 1.
     pub threshold: u32,
     pub status: AccountStatus,
     pub nonce: u64, // Replay protection
@@ -47,6 +53,8 @@ pub struct MultiSigOperation {
     pub account_id: u64,
     pub op_type: OperationType,
     pub description: String,
+    /// SHA-256 of the XDR-encoded `OperationPayload` the signers approve.
+    pub payload_hash: BytesN<32>,
     pub proposer: Address,
     pub signatures: Vec<Address>,
     pub nonce: u64,
@@ -55,6 +63,39 @@ pub struct MultiSigOperation {
     pub status: OperationStatus,
 }
 
+/// Exact call arguments a gated operation commits to.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OperationPayload {
+    /// Treasury id, recipient, amount and token of a withdrawal.
+    TreasuryWithdrawal(u64, Address, i128, Option<Address>),
+    /// Id of the governance proposal to execute.
+    GovernanceExecution(u64),
+    /// Target contract, function and arguments of a contract invocation.
+    ContractCall(Address, Symbol, Vec<Val>),
+}
+
+/// Message digested and signed off-chain to approve an operation. The
+/// contract, account, operation and nonce stop a signature being replayed
+/// anywhere else.
+#[contracttype]
+#[derive(Clone)]
+pub struct SigningPayload {
+    pub contract: Address,
+    pub account_id: u64,
+    pub operation_id: u64,
+    pub nonce: u64,
+    pub payload_hash: BytesN<32>,
+}
+
+/// An off-chain ed25519 signature over an operation's signing digest.
+#[contracttype]
+#[derive(Clone)]
+pub struct SignerSignature {
+    pub signer: Address,
+    pub signature: BytesN<64>,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct OperationPolicy {
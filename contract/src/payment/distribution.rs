@@ -1,6 +1,9 @@
+use crate::dispute::storage as dispute_storage;
+use crate::dispute::types::DisputeReference;
 use crate::events::emit::emit_event;
 use crate::events::topics::{
-    ACT_CANCELLED, ACT_CREATED, ACT_DISTRIBUTED, ACT_FAILED, ACT_RECIPIENT_ADDED, MOD_PAYMENT,
+    ACT_CANCELLED, ACT_CREATED, ACT_DISTRIBUTED, ACT_FAILED, ACT_FUNDED, ACT_RECIPIENT_ADDED,
+    MOD_PAYMENT,
 };
 use crate::payment::storage::{
     add_recipient_to_pool, clear_pool_recipients, get_next_pool_id, get_payment_pool,
@@ -8,7 +11,8 @@ use crate::payment::storage::{
 };
 use crate::payment::types::{
     DistributionExecutedEvent, DistributionFailedEvent, DistributionRule, DistributionStatus,
-    PaymentPool, PaymentPoolCreatedEvent, PoolCancelledEvent, Recipient, RecipientAddedEvent,
+    PaymentPool, PaymentPoolCreatedEvent, PoolCancelledEvent, PoolFundedEvent, Recipient,
+    RecipientAddedEvent,
 };
 use soroban_sdk::{contracterror, Address, Env, String, Vec};
 
@@ -28,6 +32,7 @@ pub enum PaymentError {
     TransferFailed = 9,
     ArithmeticOverflow = 10,
     InvalidAmount = 11,
+    DisputeActive = 12,
 }
 
 /// Minimum share amount to avoid dust issues
@@ -117,6 +122,9 @@ pub fn add_recipient(
     if pool.status != DistributionStatus::Pending {
         return Err(PaymentError::PoolNotPending);
     }
+    if dispute_storage::is_reference_locked(env, &DisputeReference::PaymentPool, pool_id) {
+        return Err(PaymentError::DisputeActive);
+    }
     if recipient_exists_in_pool(env, pool_id, &address) {
         return Err(PaymentError::DuplicateRecipient);
     }
@@ -247,6 +255,9 @@ pub fn execute_distribution(
     if pool.status != DistributionStatus::Pending {
         return Err(PaymentError::PoolNotPending);
     }
+    if dispute_storage::is_reference_locked(env, &DisputeReference::PaymentPool, pool_id) {
+        return Err(PaymentError::DisputeActive);
+    }
 
     validate_distribution(env, pool_id)?;
 
@@ -355,6 +366,9 @@ pub fn cancel_distribution(env: &Env, pool_id: u64, caller: Address) -> Result<b
     if pool.status != DistributionStatus::Pending {
         return Err(PaymentError::PoolNotPending);
     }
+    if dispute_storage::is_reference_locked(env, &DisputeReference::PaymentPool, pool_id) {
+        return Err(PaymentError::DisputeActive);
+    }
 
     update_pool_status(env, pool_id, DistributionStatus::Cancelled);
     clear_pool_recipients(env, pool_id);
@@ -372,6 +386,85 @@ pub fn cancel_distribution(env: &Env, pool_id: u64, caller: Address) -> Result<b
     Ok(true)
 }
 
+/// Block a disputed pool after a ruling against its shares. The pool is
+/// cancelled so the creator has to set up a corrected one.
+///
+/// # Events emitted
+/// - `(payment, cancelled)` â†’ `PoolCancelledEvent`
+pub fn block_disputed_pool(env: &Env, pool_id: u64) {
+    update_pool_status(env, pool_id, DistributionStatus::Cancelled);
+    clear_pool_recipients(env, pool_id);
+
+    emit_event(
+        env,
+        MOD_PAYMENT,
+        ACT_CANCELLED,
+        PoolCancelledEvent {
+            pool_id,
+            cancelled_by: env.current_contract_address(),
+        },
+    );
+}
+
+/// Re-open a disputed pool after a ruling upholding its shares, so a
+/// distribution that failed may be run again.
+pub fn reopen_disputed_pool(env: &Env, pool_id: u64) {
+    let pool = get_payment_pool(env, pool_id).expect("payment pool not found");
+    if pool.status == DistributionStatus::Failed {
+        update_pool_status(env, pool_id, DistributionStatus::Pending);
+    }
+}
+
+/// Top up a pending pool with funds the contract already holds, e.g.
+/// subscription revenue routed to contributors.
+///
+/// # Events emitted
+/// - `(payment, funded)` → `PoolFundedEvent`
+///
+/// # Errors
+/// `InvalidAmount`, `PoolNotFound`, `PoolNotPending`, `DisputeActive`,
+/// `TransferFailed` (token differs from the pool's), `ArithmeticOverflow`
+pub fn fund_pool(
+    env: &Env,
+    pool_id: u64,
+    amount: i128,
+    token: &Option<Address>,
+) -> Result<i128, PaymentError> {
+    if amount <= 0 {
+        return Err(PaymentError::InvalidAmount);
+    }
+
+    let mut pool = get_payment_pool(env, pool_id).ok_or(PaymentError::PoolNotFound)?;
+    if pool.status != DistributionStatus::Pending {
+        return Err(PaymentError::PoolNotPending);
+    }
+    if dispute_storage::is_reference_locked(env, &DisputeReference::PaymentPool, pool_id) {
+        return Err(PaymentError::DisputeActive);
+    }
+    if pool.token != *token {
+        return Err(PaymentError::TransferFailed);
+    }
+
+    pool.total_amount = pool
+        .total_amount
+        .checked_add(amount)
+        .ok_or(PaymentError::ArithmeticOverflow)?;
+    store_payment_pool(env, &pool);
+
+    emit_event(
+        env,
+        MOD_PAYMENT,
+        ACT_FUNDED,
+        PoolFundedEvent {
+            pool_id,
+            amount,
+            total_amount: pool.total_amount,
+        },
+    );
+
+    Ok(pool.total_amount)
+}
+
 /// Get the current status of a payment pool.
 pub fn get_pool_status(env: &Env, pool_id: u64) -> Result<DistributionStatus, PaymentError> {
     let pool = get_payment_pool(env, pool_id).ok_or(PaymentError::PoolNotFound)?;
@@ -95,3 +95,11 @@ pub struct PoolCancelledEvent {
     pub pool_id: u64,
     pub cancelled_by: Address,
 }
+
+/// Event emitted when a pending pool is topped up
+#[contracttype]
+pub struct PoolFundedEvent {
+    pub pool_id: u64,
+    pub amount: i128,
+    pub total_amount: i128,
+}
@@ -1,20 +1,26 @@
+use crate::dispute::storage as dispute_storage;
+use crate::dispute::types::DisputeReference;
 use crate::events::emit::emit_event;
 use crate::events::topics::{
     ACT_CANCELLED, ACT_CREATED, ACT_FAILED, ACT_GRACE_STARTED, ACT_PAUSED, ACT_PAYMENT_FAILED,
-    ACT_PAYMENT_PROCESSED, ACT_PAYMENT_RETRIED, ACT_PLAN_CREATED, ACT_RECORDED, ACT_RESUMED,
-    ACT_TIER_CHANGED, MOD_SUBSCRIPTION,
+    ACT_PAYMENT_PROCESSED, ACT_PAYMENT_RETRIED, ACT_PLAN_CREATED, ACT_RECORDED, ACT_REDEEMED,
+    ACT_REFUNDED, ACT_RESUMED, ACT_TIER_CHANGED, ACT_UPDATED, MOD_SUBSCRIPTION,
 };
+use crate::subscription::membership::{grant_plan_membership, revoke_plan_membership};
+use crate::subscription::revenue::{last_charge_routed, report_revenue, route_revenue};
 use crate::subscription::storage::{
-    add_active_subscription, add_guild_revenue, add_plan_to_guild, get_next_plan_id,
+    add_active_subscription, add_guild_revenue, add_plan_to_guild, get_coupon, get_next_plan_id,
     get_next_revenue_record_id, get_next_subscription_id, get_plan, get_retry_config,
-    get_subscription, get_user_subscription, remove_active_subscription, store_plan,
+    get_subscription, get_user_subscription, has_used_trial, mark_trial_used,
+    remove_active_subscription, set_last_revenue_record, store_coupon, store_plan,
     store_revenue_record, store_subscription, store_user_subscription,
 };
 use crate::subscription::types::{
-    GracePeriodStartedEvent, MembershipTier, PaymentProcessedEvent, PlanCreatedEvent,
-    ProrationResult, RetryConfig, RevenueRecord, RevenueRecordedEvent, Subscription,
-    SubscriptionCancelledEvent, SubscriptionChange, SubscriptionCreatedEvent, SubscriptionError,
-    SubscriptionPlan, SubscriptionStatus, TierChangedEvent,
+    BillingApprovedEvent, ChargeRefundedEvent, Coupon, CouponCreatedEvent, CouponRedeemedEvent,
+    DiscountKind, GracePeriodStartedEvent, MembershipTier, PaymentProcessedEvent, PlanCreatedEvent,
+    PlanTrialUpdatedEvent, ProrationResult, RetryConfig, RevenueRecord, RevenueRecordedEvent,
+    Subscription, SubscriptionCancelledEvent, SubscriptionChange, SubscriptionCreatedEvent,
+    SubscriptionError, SubscriptionPlan, SubscriptionStatus, TierChangedEvent,
 };
 use soroban_sdk::{token, Address, Env, String, Vec};
 
@@ -66,6 +72,7 @@ pub fn create_plan(
         benefits,
         created_by: created_by.clone(),
         created_at: env.ledger().timestamp(),
+        trial_seconds: 0,
     };
 
     store_plan(env, &plan);
@@ -89,13 +96,127 @@ pub fn create_plan(
     Ok(plan_id)
 }
 
+/// Set the free trial length for a plan
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `plan_id` - ID of the plan
+/// * `trial_seconds` - Trial length in seconds (0 disables the trial)
+/// * `caller` - Address making the request (must be the plan creator)
+///
+/// # Returns
+/// true if successful
+pub fn set_plan_trial(
+    env: &Env,
+    plan_id: u64,
+    trial_seconds: u64,
+    caller: Address,
+) -> Result<bool, SubscriptionError> {
+    let mut plan = get_plan(env, plan_id).ok_or(SubscriptionError::PlanNotFound)?;
+
+    if plan.created_by != caller {
+        return Err(SubscriptionError::Unauthorized);
+    }
+
+    plan.trial_seconds = trial_seconds;
+    store_plan(env, &plan);
+
+    let event = PlanTrialUpdatedEvent {
+        plan_id,
+        trial_seconds,
+    };
+    emit_event(env, MOD_SUBSCRIPTION, ACT_UPDATED, event);
+
+    Ok(true)
+}
+
+/// Create a discount code for a plan
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `plan_id` - ID of the plan the coupon applies to
+/// * `code` - Code subscribers redeem at subscribe time
+/// * `kind` - Percent (basis points) or fixed discount
+/// * `value` - Discount value
+/// * `cycles` - Number of billing cycles the discount lasts
+/// * `max_uses` - Maximum number of redemptions
+/// * `expires_at` - Timestamp after which the code can't be redeemed
+/// * `caller` - Address making the request (must be the plan creator)
+///
+/// # Returns
+/// true if successful
+#[allow(clippy::too_many_arguments)]
+pub fn create_coupon(
+    env: &Env,
+    plan_id: u64,
+    code: String,
+    kind: DiscountKind,
+    value: i128,
+    cycles: u32,
+    max_uses: u32,
+    expires_at: u64,
+    caller: Address,
+) -> Result<bool, SubscriptionError> {
+    let plan = get_plan(env, plan_id).ok_or(SubscriptionError::PlanNotFound)?;
+
+    if plan.created_by != caller {
+        return Err(SubscriptionError::Unauthorized);
+    }
+
+    let value_valid = match kind {
+        DiscountKind::Percent => value > 0 && value <= 10_000,
+        DiscountKind::Fixed => value > 0,
+    };
+    if code.is_empty()
+        || code.len() > 32
+        || !value_valid
+        || cycles == 0
+        || max_uses == 0
+        || expires_at <= env.ledger().timestamp()
+        || get_coupon(env, plan_id, &code).is_some()
+    {
+        return Err(SubscriptionError::InvalidCoupon);
+    }
+
+    let coupon = Coupon {
+        code: code.clone(),
+        plan_id,
+        kind: kind.clone(),
+        value,
+        cycles,
+        max_uses,
+        uses: 0,
+        expires_at,
+        created_by: caller,
+    };
+    store_coupon(env, &coupon);
+
+    let event = CouponCreatedEvent {
+        plan_id,
+        code,
+        kind,
+        value,
+        cycles,
+        max_uses,
+        expires_at,
+    };
+    emit_event(env, MOD_SUBSCRIPTION, ACT_CREATED, event);
+
+    Ok(true)
+}
+
 /// Subscribe to a plan
 ///
+/// A plan's free trial is granted once per subscriber and pushes the
+/// first charge back by the trial length. A coupon, if given, must belong
+/// to the plan and still have uses left before its expiry.
+///
 /// # Arguments
 /// * `env` - The contract environment
 /// * `plan_id` - ID of the plan to subscribe to
 /// * `subscriber` - Address subscribing
 /// * `auto_renew` - Whether to auto-renew
+/// * `coupon_code` - Optional discount code to redeem
 ///
 /// # Returns
 /// The ID of the newly created subscription
@@ -104,6 +225,7 @@ pub fn subscribe(
     plan_id: u64,
     subscriber: Address,
     auto_renew: bool,
+    coupon_code: Option<String>,
 ) -> Result<u64, SubscriptionError> {
     let plan = get_plan(env, plan_id).ok_or(SubscriptionError::PlanNotFound)?;
 
@@ -120,8 +242,30 @@ pub fn subscribe(
         }
     }
 
-    let subscription_id = get_next_subscription_id(env);
     let now = env.ledger().timestamp();
+
+    let mut coupon = match coupon_code {
+        Some(ref code) => {
+            let coupon = get_coupon(env, plan_id, code).ok_or(SubscriptionError::InvalidCoupon)?;
+            if now > coupon.expires_at {
+                return Err(SubscriptionError::CouponExpired);
+            }
+            if coupon.uses >= coupon.max_uses {
+                return Err(SubscriptionError::CouponExhausted);
+            }
+            Some(coupon)
+        }
+        None => None,
+    };
+
+    let trial_ends_at = if plan.trial_seconds > 0 && !has_used_trial(env, &subscriber, plan_id) {
+        mark_trial_used(env, &subscriber, plan_id);
+        Some(now + plan.trial_seconds)
+    } else {
+        None
+    };
+
+    let subscription_id = get_next_subscription_id(env);
     let cycle_duration = plan.billing_cycle.duration_seconds();
 
     let subscription = Subscription {
@@ -132,7 +276,7 @@ pub fn subscribe(
         current_tier: plan.tier.clone(),
         started_at: now,
         ends_at: None,
-        next_billing_at: now + cycle_duration,
+        next_billing_at: trial_ends_at.unwrap_or(now) + cycle_duration,
         last_payment_at: None,
         last_payment_amount: None,
         failed_payment_count: 0,
@@ -140,11 +284,28 @@ pub fn subscribe(
         auto_renew,
         cancelled_at: None,
         cancellation_reason: None,
+        trial_ends_at,
+        coupon_code: coupon_code.clone(),
+        discount_cycles_remaining: coupon.as_ref().map(|c| c.cycles).unwrap_or(0),
     };
 
     store_subscription(env, &subscription);
     store_user_subscription(env, &subscriber, plan.guild_id, subscription_id);
     add_active_subscription(env, subscription_id);
+    grant_plan_membership(env, &subscription, &plan);
+
+    if let Some(ref mut c) = coupon {
+        c.uses += 1;
+        store_coupon(env, c);
+
+        let event = CouponRedeemedEvent {
+            plan_id,
+            code: c.code.clone(),
+            subscription_id,
+            uses: c.uses,
+        };
+        emit_event(env, MOD_SUBSCRIPTION, ACT_REDEEMED, event);
+    }
 
     // Emit event
     let event = SubscriptionCreatedEvent {
@@ -185,16 +346,31 @@ pub fn process_payment(
         return Err(SubscriptionError::InvalidState);
     }
 
-    let payment_result = execute_payment(env, &subscription.subscriber, plan.price, &plan.token);
+    if dispute_storage::is_reference_locked(env, &DisputeReference::Subscription, subscription_id) {
+        return Err(SubscriptionError::DisputeActive);
+    }
+
+    let discount = current_discount(env, &subscription, &plan);
+    let amount = plan.price - discount;
 
     let now = env.ledger().timestamp();
+    let is_due = now >= subscription.next_billing_at;
+
+    let payment_result = if amount > 0 {
+        execute_payment(env, &subscription.subscriber, amount, &plan.token, is_due)
+    } else {
+        Ok(())
+    };
 
     match payment_result {
         Ok(()) => {
             // Payment successful
             subscription.last_payment_at = Some(now);
-            subscription.last_payment_amount = Some(plan.price);
+            subscription.last_payment_amount = Some(amount);
             subscription.failed_payment_count = 0;
+            if discount > 0 {
+                subscription.discount_cycles_remaining -= 1;
+            }
             subscription.grace_period_ends_at = None;
             subscription.status = SubscriptionStatus::Active;
 
@@ -207,12 +383,11 @@ pub fn process_payment(
             // Record revenue
             record_revenue(
                 env,
-                plan.guild_id,
+                &plan,
                 subscription_id,
                 subscription.subscriber.clone(),
-                plan.price,
-                plan.token.clone(),
-                plan.billing_cycle.clone(),
+                amount,
+                discount,
                 retry_attempt > 0,
                 retry_attempt,
             );
@@ -220,7 +395,7 @@ pub fn process_payment(
             // Emit success event
             let event = PaymentProcessedEvent {
                 subscription_id,
-                amount: plan.price,
+                amount,
                 success: true,
                 retry_attempt,
             };
@@ -247,6 +422,7 @@ pub fn process_payment(
                 subscription.cancellation_reason =
                     Some(String::from_str(env, "Max payment retries exceeded"));
                 remove_active_subscription(env, subscription_id);
+                revoke_plan_membership(env, &subscription.subscriber, plan.guild_id);
             } else {
                 // Enter or continue grace period
                 subscription.status = SubscriptionStatus::GracePeriod;
@@ -267,7 +443,7 @@ pub fn process_payment(
             // Emit failure event
             let event = PaymentProcessedEvent {
                 subscription_id,
-                amount: plan.price,
+                amount,
                 success: false,
                 retry_attempt,
             };
@@ -278,19 +454,112 @@ pub fn process_payment(
     }
 }
 
+/// Approve this contract to pull renewals from the subscriber's tokens
+///
+/// Sets the token allowance used by unattended renewals. Passing 0 revokes
+/// it, after which due renewals fail into the grace period.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `subscription_id` - ID of the subscription
+/// * `caller` - Address making the request (must be the subscriber)
+/// * `amount` - Total amount renewals may pull
+/// * `expiration_ledger` - Ledger sequence at which the allowance lapses
+///
+/// # Returns
+/// true if successful
+pub fn approve_billing(
+    env: &Env,
+    subscription_id: u64,
+    caller: Address,
+    amount: i128,
+    expiration_ledger: u32,
+) -> Result<bool, SubscriptionError> {
+    let subscription =
+        get_subscription(env, subscription_id).ok_or(SubscriptionError::SubscriptionNotFound)?;
+
+    if subscription.subscriber != caller {
+        return Err(SubscriptionError::Unauthorized);
+    }
+    if amount < 0 {
+        return Err(SubscriptionError::InvalidPrice);
+    }
+
+    let plan = get_plan(env, subscription.plan_id).ok_or(SubscriptionError::PlanNotFound)?;
+    let token_addr = plan.token.ok_or(SubscriptionError::InvalidState)?;
+
+    let token_client = token::Client::new(env, &token_addr);
+    token_client.approve(
+        &caller,
+        &env.current_contract_address(),
+        &amount,
+        &expiration_ledger,
+    );
+
+    let event = BillingApprovedEvent {
+        subscription_id,
+        subscriber: caller,
+        amount,
+        expiration_ledger,
+    };
+    emit_event(env, MOD_SUBSCRIPTION, ACT_UPDATED, event);
+
+    Ok(true)
+}
+
+/// Coupon discount applicable to the subscription's next charge.
+///
+/// The coupon is looked up against the subscription's current plan, so a
+/// tier change to another plan ends the discount.
+fn current_discount(env: &Env, subscription: &Subscription, plan: &SubscriptionPlan) -> i128 {
+    if subscription.discount_cycles_remaining == 0 {
+        return 0;
+    }
+    let coupon = match subscription
+        .coupon_code
+        .as_ref()
+        .and_then(|code| get_coupon(env, plan.id, code))
+    {
+        Some(c) => c,
+        None => return 0,
+    };
+    match coupon.kind {
+        DiscountKind::Percent => plan.price * coupon.value / 10_000,
+        DiscountKind::Fixed => coupon.value.min(plan.price),
+    }
+}
+
 /// Execute the actual token transfer for payment
+///
+/// Due renewals are pulled with `transfer_from` against the allowance the
+/// subscriber granted this contract, so keepers can bill without the
+/// subscriber present; a short allowance or balance fails the payment.
+/// Charges taken before the due date need the subscriber's authorization.
 fn execute_payment(
     env: &Env,
     from: &Address,
     amount: i128,
     token: &Option<Address>,
+    pull: bool,
 ) -> Result<(), ()> {
-    from.require_auth();
+    if !pull {
+        from.require_auth();
+    }
 
     if let Some(token_addr) = token {
         // Transfer custom token
         let token_client = token::Client::new(env, token_addr);
-        token_client.transfer(from, &env.current_contract_address(), &amount);
+        let contract = env.current_contract_address();
+        if pull {
+            if token_client.allowance(from, &contract) < amount
+                || token_client.balance(from) < amount
+            {
+                return Err(());
+            }
+            token_client.transfer_from(&contract, from, &contract, &amount);
+        } else {
+            token_client.transfer(from, &contract, &amount);
+        }
     } else {
         // For native XLM, we would need additional handling
         // In a real implementation, this would check and transfer XLM
@@ -300,41 +569,89 @@ fn execute_payment(
     Ok(())
 }
 
-/// Record revenue from a successful payment
+/// Refund part of a subscription's last charge after a dispute ruling.
+///
+/// Called by dispute resolution; the refund is paid from the contract to the
+/// subscriber and deducted from `last_payment_amount`. Charges whose revenue
+/// was routed to a treasury, pool or the platform are never refunded.
+pub fn refund_disputed_charge(env: &Env, subscription_id: u64, amount: i128) {
+    let mut subscription = get_subscription(env, subscription_id).expect("subscription not found");
+    let plan = get_plan(env, subscription.plan_id).expect("plan not found");
+
+    let charged = subscription.last_payment_amount.unwrap_or(0);
+    if amount <= 0 || amount > charged {
+        panic!("invalid refund amount");
+    }
+    if last_charge_routed(env, subscription_id) {
+        panic!("routed charges cannot be refunded");
+    }
+
+    if let Some(token_addr) = &plan.token {
+        let token_client = token::Client::new(env, token_addr);
+        token_client.transfer(
+            &env.current_contract_address(),
+            &subscription.subscriber,
+            &amount,
+        );
+    }
+
+    subscription.last_payment_amount = Some(charged - amount);
+    store_subscription(env, &subscription);
+
+    let event = ChargeRefundedEvent {
+        subscription_id,
+        subscriber: subscription.subscriber,
+        amount,
+    };
+    emit_event(env, MOD_SUBSCRIPTION, ACT_REFUNDED, event);
+}
+
+/// Record revenue from a successful payment and route it per the plan's split
+#[allow(clippy::too_many_arguments)]
 fn record_revenue(
     env: &Env,
-    guild_id: u64,
+    plan: &SubscriptionPlan,
     subscription_id: u64,
     subscriber: Address,
     amount: i128,
-    token: Option<Address>,
-    billing_cycle: crate::subscription::types::BillingCycle,
+    discount_amount: i128,
     is_retry: bool,
     retry_attempt: u32,
 ) -> u64 {
     let record_id = get_next_revenue_record_id(env);
     let now = env.ledger().timestamp();
+    let guild_id = plan.guild_id;
 
     // Calculate period start (beginning of the cycle this payment covers)
-    let cycle_duration = billing_cycle.duration_seconds();
+    let cycle_duration = plan.billing_cycle.duration_seconds();
     let period_start = now - cycle_duration;
 
+    let (treasury_amount, pool_amount, platform_fee) =
+        route_revenue(env, plan, &subscriber, amount);
+
     let record = RevenueRecord {
         id: record_id,
         guild_id,
-        plan_id: 0, // Will be filled from subscription
+        plan_id: plan.id,
         subscription_id,
         subscriber,
         amount,
-        token,
+        gross_amount: amount + discount_amount,
+        discount_amount,
+        treasury_amount,
+        pool_amount,
+        platform_fee,
+        token: plan.token.clone(),
         paid_at: now,
-        billing_cycle,
+        billing_cycle: plan.billing_cycle.clone(),
         is_retry,
         retry_attempt,
     };
 
     store_revenue_record(env, &record);
+    set_last_revenue_record(env, subscription_id, record_id);
     add_guild_revenue(env, guild_id, period_start, record_id);
+    report_revenue(env, &record);
 
     // Emit revenue event
     let event = RevenueRecordedEvent {
@@ -463,6 +780,9 @@ pub fn cancel_subscription(
 
     store_subscription(env, &subscription);
     remove_active_subscription(env, subscription_id);
+    if let Some(plan) = get_plan(env, subscription.plan_id) {
+        revoke_plan_membership(env, &subscription.subscriber, plan.guild_id);
+    }
 
     // Emit cancellation event
     let event = SubscriptionCancelledEvent {
@@ -562,6 +882,8 @@ pub fn change_tier(
     };
 
     store_subscription(env, &subscription);
+    revoke_plan_membership(env, &subscription.subscriber, current_plan.guild_id);
+    grant_plan_membership(env, &subscription, &new_plan);
 
     // Emit tier change event
     let event = TierChangedEvent {
@@ -637,6 +959,9 @@ pub fn process_due_subscriptions(env: &Env, limit: u32) -> u32 {
                             Some(String::from_str(env, "Grace period expired"));
                         store_subscription(env, &sub);
                         remove_active_subscription(env, sub_id);
+                        if let Some(plan) = get_plan(env, sub.plan_id) {
+                            revoke_plan_membership(env, &sub.subscriber, plan.guild_id);
+                        }
 
                         // Emit cancellation event
                         let event = SubscriptionCancelledEvent {
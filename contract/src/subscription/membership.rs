@@ -0,0 +1,169 @@
+use crate::events::emit::emit_event;
+use crate::events::topics::{
+    ACT_MEMBER_ADDED, ACT_MEMBER_REMOVED, ACT_ROLE_UPDATED, ACT_UPDATED, MOD_GUILD,
+    MOD_SUBSCRIPTION,
+};
+use crate::guild::membership::has_permission;
+use crate::guild::storage as guild_storage;
+use crate::guild::types::{Member, MemberAddedEvent, MemberRemovedEvent, Role, RoleUpdatedEvent};
+use crate::subscription::storage::{
+    get_membership_grant, get_plan, get_plan_role, remove_membership_grant,
+    set_plan_role as store_plan_role, store_membership_grant,
+};
+use crate::subscription::types::{
+    MembershipGrant, PlanRoleUpdatedEvent, Subscription, SubscriptionError, SubscriptionPlan,
+};
+use soroban_sdk::{Address, Env};
+
+/// Map a plan to the guild role its subscribers receive
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `plan_id` - ID of the plan
+/// * `role` - `Member` or `Contributor`, or None to stop granting a role
+/// * `caller` - Address making the request (must be a guild admin)
+///
+/// # Returns
+/// true if successful
+pub fn set_plan_role(
+    env: &Env,
+    plan_id: u64,
+    role: Option<Role>,
+    caller: Address,
+) -> Result<bool, SubscriptionError> {
+    let plan = get_plan(env, plan_id).ok_or(SubscriptionError::PlanNotFound)?;
+
+    if plan.guild_id == 0 {
+        return Err(SubscriptionError::InvalidPlanRole);
+    }
+    if !has_permission(env, plan.guild_id, caller, Role::Admin) {
+        return Err(SubscriptionError::Unauthorized);
+    }
+    if matches!(role, Some(Role::Owner) | Some(Role::Admin)) {
+        return Err(SubscriptionError::InvalidPlanRole);
+    }
+
+    let previous = get_plan_role(env, plan_id);
+    store_plan_role(env, plan_id, &role);
+
+    let enabled = role.is_some();
+    let event = PlanRoleUpdatedEvent {
+        plan_id,
+        guild_id: plan.guild_id,
+        role: role.or(previous).unwrap_or(Role::Member),
+        enabled,
+    };
+    emit_event(env, MOD_SUBSCRIPTION, ACT_UPDATED, event);
+
+    Ok(true)
+}
+
+/// Grant the plan's guild role to a subscriber.
+///
+/// Non-members are added with the role; members holding a lower role are
+/// raised to it. Members already at or above the role are left untouched
+/// and nothing is recorded, so revoking never demotes them.
+pub fn grant_plan_membership(env: &Env, subscription: &Subscription, plan: &SubscriptionPlan) {
+    let role = match get_plan_role(env, plan.id) {
+        Some(r) => r,
+        None => return,
+    };
+    let mut guild = match guild_storage::get_guild(env, plan.guild_id) {
+        Some(g) => g,
+        None => return,
+    };
+    let subscriber = subscription.subscriber.clone();
+
+    let grant = match guild_storage::get_member(env, plan.guild_id, &subscriber) {
+        None => {
+            let now = env.ledger().timestamp();
+            let member = Member {
+                address: subscriber.clone(),
+                role,
+                joined_at: now,
+            };
+            guild_storage::store_member(env, plan.guild_id, &member);
+            guild.member_count += 1;
+            guild_storage::update_guild(env, &guild);
+
+            let event = MemberAddedEvent {
+                guild_id: plan.guild_id,
+                address: subscriber.clone(),
+                role,
+                joined_at: now,
+            };
+            emit_event(env, MOD_GUILD, ACT_MEMBER_ADDED, event);
+
+            MembershipGrant {
+                subscription_id: subscription.id,
+                role,
+                added: true,
+                previous_role: role,
+            }
+        }
+        Some(member) => {
+            if member.role.has_permission(&role) {
+                return;
+            }
+            let previous_role = member.role;
+            set_member_role(env, plan.guild_id, member, role);
+
+            MembershipGrant {
+                subscription_id: subscription.id,
+                role,
+                added: false,
+                previous_role,
+            }
+        }
+    };
+
+    store_membership_grant(env, &subscriber, plan.guild_id, &grant);
+}
+
+/// Undo the membership a subscription granted in a guild, if any.
+pub fn revoke_plan_membership(env: &Env, subscriber: &Address, guild_id: u64) {
+    let grant = match get_membership_grant(env, subscriber, guild_id) {
+        Some(g) => g,
+        None => return,
+    };
+    remove_membership_grant(env, subscriber, guild_id);
+
+    let member = match guild_storage::get_member(env, guild_id, subscriber) {
+        Some(m) if m.role == grant.role => m,
+        _ => return,
+    };
+
+    if grant.added {
+        guild_storage::remove_member(env, guild_id, subscriber);
+        if let Some(mut guild) = guild_storage::get_guild(env, guild_id) {
+            guild.member_count = guild.member_count.saturating_sub(1);
+            guild_storage::update_guild(env, &guild);
+        }
+
+        let event = MemberRemovedEvent {
+            guild_id,
+            address: subscriber.clone(),
+        };
+        emit_event(env, MOD_GUILD, ACT_MEMBER_REMOVED, event);
+    } else {
+        set_member_role(env, guild_id, member, grant.previous_role);
+    }
+}
+
+fn set_member_role(env: &Env, guild_id: u64, member: Member, new_role: Role) {
+    let old_role = member.role;
+    let updated = Member {
+        address: member.address.clone(),
+        role: new_role,
+        joined_at: member.joined_at,
+    };
+    guild_storage::store_member(env, guild_id, &updated);
+
+    let event = RoleUpdatedEvent {
+        guild_id,
+        address: member.address,
+        old_role,
+        new_role,
+    };
+    emit_event(env, MOD_GUILD, ACT_ROLE_UPDATED, event);
+}
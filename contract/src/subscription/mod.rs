@@ -1,4 +1,6 @@
 ﻿pub mod lifecycle;
+pub mod membership;
+pub mod revenue;
 pub mod storage;
 pub mod types;
 
@@ -11,6 +13,8 @@ pub mod types;
 /// - `types`: Defines all core data structures, events, and error types
 /// - `storage`: Manages persistent storage of plans, subscriptions, and revenue records
 /// - `lifecycle`: Core functions for subscription lifecycle management
+/// - `membership`: Guild roles granted to subscribers of a plan
+/// - `revenue`: Revenue split routing and per-guild revenue reports
 ///
 /// # Key Features
 /// - **Tiered Membership**: Basic, Standard, Premium, and Enterprise tiers with different benefits
@@ -19,7 +23,13 @@ pub mod types;
 /// - **Grace Periods**: Automatic handling of failed payments with grace periods
 /// - **Proration Support**: Automatic calculations for mid-cycle tier changes
 /// - **Revenue Tracking**: Comprehensive revenue recording and reporting
+/// - **Revenue Sharing**: Plans split each payment between the guild treasury, a contributor
+///   payment pool and the platform fee, with a per-guild report for every 30-day period
 /// - **Pause/Resume**: Subscribers can pause and resume subscriptions
+/// - **Trials and Coupons**: One free trial per subscriber per plan, and discount codes limited
+///   by uses and expiry that reduce the price for a number of cycles
+/// - **Guild Roles**: Plans can grant a guild role that follows the subscription through tier
+///   changes and is revoked on cancellation or grace period expiry
 ///
 /// # Subscription Lifecycle
 /// 1. **Plan Creation**: Guilds or platform create subscription plans with pricing and benefits
@@ -37,10 +47,11 @@ pub mod types;
 /// - Auto-renewal management
 // Re-export main types
 pub use types::{
-    BillingCycle, GracePeriodStartedEvent, MembershipTier, PaymentProcessedEvent, PlanCreatedEvent,
-    ProrationResult, RetryConfig, RevenueRecord, RevenueRecordedEvent, Subscription,
-    SubscriptionCancelledEvent, SubscriptionChange, SubscriptionCreatedEvent, SubscriptionError,
-    SubscriptionPlan, SubscriptionStatus, TierChangedEvent,
+    BillingCycle, Coupon, DiscountKind, GracePeriodStartedEvent, MembershipTier,
+    PaymentProcessedEvent, PlanCreatedEvent, ProrationResult, RetryConfig, RevenueRecord,
+    RevenueRecordedEvent, RevenueReport, RevenueSplit, Subscription, SubscriptionCancelledEvent,
+    SubscriptionChange, SubscriptionCreatedEvent, SubscriptionError, SubscriptionPlan,
+    SubscriptionStatus, TierChangedEvent,
 };
 
 // Re-export storage functions
@@ -51,11 +62,18 @@ pub use storage::{
     store_subscription,
 };
 
+// Re-export membership functions
+pub use membership::set_plan_role;
+
+// Re-export revenue functions
+pub use revenue::{get_revenue_report, set_revenue_split};
+
 // Re-export lifecycle functions
 pub use lifecycle::{
-    cancel_subscription, change_tier, create_plan, days_until_billing, get_subscription_status,
-    is_subscription_active, pause_subscription, process_due_subscriptions, process_payment,
-    resume_subscription, retry_payment, subscribe,
+    approve_billing, cancel_subscription, change_tier, create_coupon, create_plan,
+    days_until_billing, get_subscription_status, is_subscription_active, pause_subscription,
+    process_due_subscriptions, process_payment, resume_subscription, retry_payment, set_plan_trial,
+    subscribe,
 };
 
 #[cfg(test)]
@@ -0,0 +1,258 @@
+use crate::events::emit::emit_event;
+use crate::events::topics::{ACT_DISTRIBUTED, ACT_UPDATED, MOD_SUBSCRIPTION};
+use crate::payment::distribution::fund_pool;
+use crate::payment::storage::get_payment_pool;
+use crate::payment::types::DistributionStatus;
+use crate::subscription::storage::{
+    get_last_revenue_record, get_plan, get_revenue_report as load_revenue_report,
+    get_revenue_split, set_revenue_split as store_revenue_split, store_revenue_report,
+};
+use crate::subscription::types::{
+    RevenueRecord, RevenueReport, RevenueRoutedEvent, RevenueSplit, RevenueSplitUpdatedEvent,
+    SubscriptionError, SubscriptionPlan,
+};
+use crate::treasury::management::credit_deposit;
+use crate::treasury::storage::get_treasury;
+use crate::DataKey;
+use soroban_sdk::{token, Address, Env};
+
+/// Basis points making up a whole payment
+pub const SPLIT_TOTAL_BPS: u32 = 10_000;
+
+/// Length of a revenue reporting period (30 days)
+pub const REPORT_PERIOD_SECONDS: u64 = 30 * 24 * 60 * 60;
+
+/// Set how a plan's revenue is split between the guild treasury, a
+/// contributor payment pool and the platform fee
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `plan_id` - ID of the plan
+/// * `treasury_id` - Treasury of the plan's guild (0 for none)
+/// * `treasury_bps` - Treasury share in basis points
+/// * `pool_id` - Pending payment pool in the plan's token (0 for none)
+/// * `pool_bps` - Pool share in basis points
+/// * `platform_bps` - Platform fee in basis points
+/// * `caller` - Plan creator
+///
+/// Shares must add up to 10_000; passing all shares as 0 clears the split.
+///
+/// # Returns
+/// true if successful
+#[allow(clippy::too_many_arguments)]
+pub fn set_revenue_split(
+    env: &Env,
+    plan_id: u64,
+    treasury_id: u64,
+    treasury_bps: u32,
+    pool_id: u64,
+    pool_bps: u32,
+    platform_bps: u32,
+    caller: Address,
+) -> Result<bool, SubscriptionError> {
+    let plan = get_plan(env, plan_id).ok_or(SubscriptionError::PlanNotFound)?;
+
+    if plan.created_by != caller {
+        return Err(SubscriptionError::Unauthorized);
+    }
+
+    let total = treasury_bps as u64 + pool_bps as u64 + platform_bps as u64;
+    let split = if total == 0 {
+        None
+    } else {
+        if total != SPLIT_TOTAL_BPS as u64 {
+            return Err(SubscriptionError::InvalidRevenueSplit);
+        }
+        validate_targets(env, &plan, treasury_id, treasury_bps, pool_id, pool_bps)?;
+        Some(RevenueSplit {
+            plan_id,
+            treasury_id,
+            treasury_bps,
+            pool_id,
+            pool_bps,
+            platform_bps,
+        })
+    };
+
+    store_revenue_split(env, plan_id, &split);
+
+    let event = RevenueSplitUpdatedEvent {
+        plan_id,
+        treasury_id,
+        treasury_bps,
+        pool_id,
+        pool_bps,
+        platform_bps,
+    };
+    emit_event(env, MOD_SUBSCRIPTION, ACT_UPDATED, event);
+
+    Ok(true)
+}
+
+fn validate_targets(
+    env: &Env,
+    plan: &SubscriptionPlan,
+    treasury_id: u64,
+    treasury_bps: u32,
+    pool_id: u64,
+    pool_bps: u32,
+) -> Result<(), SubscriptionError> {
+    if treasury_id == 0 {
+        if treasury_bps > 0 {
+            return Err(SubscriptionError::InvalidRevenueSplit);
+        }
+    } else {
+        let treasury =
+            get_treasury(env, treasury_id).ok_or(SubscriptionError::InvalidRevenueSplit)?;
+        if treasury.guild_id != plan.guild_id {
+            return Err(SubscriptionError::InvalidRevenueSplit);
+        }
+    }
+
+    if pool_id == 0 {
+        if pool_bps > 0 {
+            return Err(SubscriptionError::InvalidRevenueSplit);
+        }
+    } else {
+        let pool = get_payment_pool(env, pool_id).ok_or(SubscriptionError::InvalidRevenueSplit)?;
+        if pool.status != DistributionStatus::Pending || pool.token != plan.token {
+            return Err(SubscriptionError::InvalidRevenueSplit);
+        }
+    }
+
+    Ok(())
+}
+
+/// Route a collected payment according to the plan's split.
+///
+/// Returns the `(treasury, pool, platform)` amounts actually handed over.
+/// A share whose target can't take it right now (treasury paused or gone,
+/// pool no longer pending) stays in the contract and is reported as unrouted
+/// rather than failing the subscriber's payment.
+pub fn route_revenue(
+    env: &Env,
+    plan: &SubscriptionPlan,
+    subscriber: &Address,
+    amount: i128,
+) -> (i128, i128, i128) {
+    let split = match get_revenue_split(env, plan.id) {
+        Some(s) if amount > 0 => s,
+        _ => return (0, 0, 0),
+    };
+
+    let share = |bps: u32| amount * bps as i128 / SPLIT_TOTAL_BPS as i128;
+    let mut treasury_amount = share(split.treasury_bps);
+    let mut pool_amount = share(split.pool_bps);
+    let mut platform_fee = share(split.platform_bps);
+
+    // Rounding dust goes to the guild first, the platform last
+    let dust = amount - treasury_amount - pool_amount - platform_fee;
+    if split.treasury_bps > 0 {
+        treasury_amount += dust;
+    } else if split.pool_bps > 0 {
+        pool_amount += dust;
+    } else {
+        platform_fee += dust;
+    }
+
+    if treasury_amount > 0 {
+        let available = get_treasury(env, split.treasury_id)
+            .map(|t| !t.paused)
+            .unwrap_or(false);
+        if available {
+            credit_deposit(
+                env,
+                split.treasury_id,
+                subscriber.clone(),
+                treasury_amount,
+                plan.token.clone(),
+            );
+        } else {
+            treasury_amount = 0;
+        }
+    }
+
+    if pool_amount > 0 && fund_pool(env, split.pool_id, pool_amount, &plan.token).is_err() {
+        pool_amount = 0;
+    }
+
+    if platform_fee > 0 {
+        let admin: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+        match admin {
+            Some(admin) => {
+                if let Some(ref token_addr) = plan.token {
+                    let client = token::Client::new(env, token_addr);
+                    client.transfer(&env.current_contract_address(), &admin, &platform_fee);
+                }
+            }
+            None => platform_fee = 0,
+        }
+    }
+
+    (treasury_amount, pool_amount, platform_fee)
+}
+
+/// Whether any share of a subscription's latest charge left the contract's
+/// unrouted balance. Routed shares can't be pulled back, so such a charge
+/// can't be refunded.
+pub fn last_charge_routed(env: &Env, subscription_id: u64) -> bool {
+    get_last_revenue_record(env, subscription_id)
+        .map(|r| r.treasury_amount + r.pool_amount + r.platform_fee > 0)
+        .unwrap_or(false)
+}
+
+/// Add a routed payment to its guild's report and emit the routing event
+pub fn report_revenue(env: &Env, record: &RevenueRecord) {
+    let period_start = record.paid_at - record.paid_at % REPORT_PERIOD_SECONDS;
+    let mut report = get_revenue_report(env, record.guild_id, period_start, record.token.clone());
+
+    let routed = record.treasury_amount + record.pool_amount + record.platform_fee;
+    let unrouted = record.amount - routed;
+
+    report.payments += 1;
+    report.gross_amount += record.gross_amount;
+    report.discount_amount += record.discount_amount;
+    report.collected += record.amount;
+    report.treasury_amount += record.treasury_amount;
+    report.pool_amount += record.pool_amount;
+    report.platform_fee += record.platform_fee;
+    report.unrouted += unrouted;
+    store_revenue_report(env, &report);
+
+    if routed > 0 {
+        let event = RevenueRoutedEvent {
+            record_id: record.id,
+            guild_id: record.guild_id,
+            treasury_amount: record.treasury_amount,
+            pool_amount: record.pool_amount,
+            platform_fee: record.platform_fee,
+            unrouted,
+        };
+        emit_event(env, MOD_SUBSCRIPTION, ACT_DISTRIBUTED, event);
+    }
+}
+
+/// Get a guild's subscription revenue report for the period containing
+/// `timestamp`, in one token
+pub fn get_revenue_report(
+    env: &Env,
+    guild_id: u64,
+    timestamp: u64,
+    token: Option<Address>,
+) -> RevenueReport {
+    let period_start = timestamp - timestamp % REPORT_PERIOD_SECONDS;
+    load_revenue_report(env, guild_id, period_start, &token).unwrap_or(RevenueReport {
+        guild_id,
+        token,
+        period_start,
+        period_end: period_start + REPORT_PERIOD_SECONDS,
+        payments: 0,
+        gross_amount: 0,
+        discount_amount: 0,
+        collected: 0,
+        treasury_amount: 0,
+        pool_amount: 0,
+        platform_fee: 0,
+        unrouted: 0,
+    })
+}
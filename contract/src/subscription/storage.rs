@@ -1,7 +1,9 @@
-﻿use crate::subscription::types::{
-    BillingCycle, MembershipTier, RetryConfig, RevenueRecord, Subscription, SubscriptionPlan,
+﻿use crate::guild::types::Role;
+use crate::subscription::types::{
+    BillingCycle, Coupon, MembershipGrant, MembershipTier, RetryConfig, RevenueRecord,
+    RevenueReport, RevenueSplit, Subscription, SubscriptionPlan,
 };
-use soroban_sdk::{contracttype, Address, Env, Map, Vec};
+use soroban_sdk::{contracttype, Address, Env, Map, String, Vec};
 
 /// Storage keys for subscription data
 #[contracttype]
@@ -28,6 +30,20 @@ pub enum SubscriptionStorageKey {
     GuildRevenue(u64, u64),
     /// Retry configuration
     RetryConfig,
+    /// Coupon storage: (plan_id, code) -> Coupon
+    Coupon(u64, String),
+    /// Subscribers who already used a plan's trial: (subscriber, plan_id) -> bool
+    TrialUsed(Address, u64),
+    /// Guild role granted to a plan's subscribers: plan_id -> Role
+    PlanRole(u64),
+    /// Membership granted by a subscription: (subscriber, guild_id) -> MembershipGrant
+    MembershipGrant(Address, u64),
+    /// Revenue split rules: plan_id -> RevenueSplit
+    RevenueSplit(u64),
+    /// Revenue report: (guild_id, period_start, token) -> RevenueReport
+    RevenueReport(u64, u64, Option<Address>),
+    /// Revenue record of a subscription's latest charge: subscription_id -> record_id
+    LastRevenueRecord(u64),
 }
 
 /// Initialize subscription storage
@@ -230,6 +246,23 @@ pub fn get_revenue_record(env: &Env, record_id: u64) -> Option<RevenueRecord> {
         .get(&SubscriptionStorageKey::RevenueRecord(record_id))
 }
 
+/// Remember the revenue record of a subscription's latest charge
+pub fn set_last_revenue_record(env: &Env, subscription_id: u64, record_id: u64) {
+    env.storage().persistent().set(
+        &SubscriptionStorageKey::LastRevenueRecord(subscription_id),
+        &record_id,
+    );
+}
+
+/// Get the revenue record of a subscription's latest charge
+pub fn get_last_revenue_record(env: &Env, subscription_id: u64) -> Option<RevenueRecord> {
+    let record_id: u64 = env
+        .storage()
+        .persistent()
+        .get(&SubscriptionStorageKey::LastRevenueRecord(subscription_id))?;
+    get_revenue_record(env, record_id)
+}
+
 /// Add revenue record to guild's revenue index
 pub fn add_guild_revenue(env: &Env, guild_id: u64, period_start: u64, record_id: u64) {
     let key = SubscriptionStorageKey::GuildRevenue(guild_id, period_start);
@@ -329,3 +362,133 @@ pub fn get_subscriptions_by_plan(env: &Env, plan_id: u64, limit: u32) -> Vec<Sub
 
     subscriptions
 }
+
+/// Store a coupon
+pub fn store_coupon(env: &Env, coupon: &Coupon) {
+    env.storage().persistent().set(
+        &SubscriptionStorageKey::Coupon(coupon.plan_id, coupon.code.clone()),
+        coupon,
+    );
+}
+
+/// Get a coupon by plan and code
+pub fn get_coupon(env: &Env, plan_id: u64, code: &String) -> Option<Coupon> {
+    env.storage()
+        .persistent()
+        .get(&SubscriptionStorageKey::Coupon(plan_id, code.clone()))
+}
+
+/// Record that a subscriber has used a plan's free trial
+pub fn mark_trial_used(env: &Env, subscriber: &Address, plan_id: u64) {
+    env.storage().persistent().set(
+        &SubscriptionStorageKey::TrialUsed(subscriber.clone(), plan_id),
+        &true,
+    );
+}
+
+/// Whether a subscriber has already used a plan's free trial
+pub fn has_used_trial(env: &Env, subscriber: &Address, plan_id: u64) -> bool {
+    env.storage()
+        .persistent()
+        .has(&SubscriptionStorageKey::TrialUsed(
+            subscriber.clone(),
+            plan_id,
+        ))
+}
+
+/// Set or clear the guild role a plan grants
+pub fn set_plan_role(env: &Env, plan_id: u64, role: &Option<Role>) {
+    let key = SubscriptionStorageKey::PlanRole(plan_id);
+    match role {
+        Some(r) => env.storage().persistent().set(&key, r),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// Get the guild role a plan grants
+pub fn get_plan_role(env: &Env, plan_id: u64) -> Option<Role> {
+    env.storage()
+        .persistent()
+        .get(&SubscriptionStorageKey::PlanRole(plan_id))
+}
+
+/// Store the membership a subscription granted in a guild
+pub fn store_membership_grant(
+    env: &Env,
+    subscriber: &Address,
+    guild_id: u64,
+    grant: &MembershipGrant,
+) {
+    env.storage().persistent().set(
+        &SubscriptionStorageKey::MembershipGrant(subscriber.clone(), guild_id),
+        grant,
+    );
+}
+
+/// Get the membership a subscription granted in a guild
+pub fn get_membership_grant(
+    env: &Env,
+    subscriber: &Address,
+    guild_id: u64,
+) -> Option<MembershipGrant> {
+    env.storage()
+        .persistent()
+        .get(&SubscriptionStorageKey::MembershipGrant(
+            subscriber.clone(),
+            guild_id,
+        ))
+}
+
+/// Remove a subscription's membership grant
+pub fn remove_membership_grant(env: &Env, subscriber: &Address, guild_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&SubscriptionStorageKey::MembershipGrant(
+            subscriber.clone(),
+            guild_id,
+        ));
+}
+
+/// Set or clear a plan's revenue split
+pub fn set_revenue_split(env: &Env, plan_id: u64, split: &Option<RevenueSplit>) {
+    let key = SubscriptionStorageKey::RevenueSplit(plan_id);
+    match split {
+        Some(s) => env.storage().persistent().set(&key, s),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// Get a plan's revenue split
+pub fn get_revenue_split(env: &Env, plan_id: u64) -> Option<RevenueSplit> {
+    env.storage()
+        .persistent()
+        .get(&SubscriptionStorageKey::RevenueSplit(plan_id))
+}
+
+/// Store a guild's revenue report for a period
+pub fn store_revenue_report(env: &Env, report: &RevenueReport) {
+    env.storage().persistent().set(
+        &SubscriptionStorageKey::RevenueReport(
+            report.guild_id,
+            report.period_start,
+            report.token.clone(),
+        ),
+        report,
+    );
+}
+
+/// Get a guild's revenue report for a period
+pub fn get_revenue_report(
+    env: &Env,
+    guild_id: u64,
+    period_start: u64,
+    token: &Option<Address>,
+) -> Option<RevenueReport> {
+    env.storage()
+        .persistent()
+        .get(&SubscriptionStorageKey::RevenueReport(
+            guild_id,
+            period_start,
+            token.clone(),
+        ))
+}
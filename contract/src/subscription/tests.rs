@@ -1,11 +1,13 @@
+use crate::guild::types::Role;
+use crate::payment::types::DistributionRule;
 use crate::subscription::storage;
 use crate::subscription::types::{
-    BillingCycle, MembershipTier, RetryConfig, RevenueRecord, Subscription, SubscriptionPlan,
-    SubscriptionStatus,
+    BillingCycle, DiscountKind, MembershipTier, RetryConfig, RevenueRecord, Subscription,
+    SubscriptionPlan, SubscriptionStatus,
 };
-use crate::{StellarGuildsContract, StellarGuildsContractClient};
+use crate::{InitializerProof, StellarGuildsContract, StellarGuildsContractClient};
 use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
-use soroban_sdk::{Address, Env, String, Vec};
+use soroban_sdk::{token, Address, Env, String, Vec};
 
 fn setup_env() -> Env {
     let env = Env::default();
@@ -673,6 +675,7 @@ fn test_subscription_storage_indexes_and_revenue_queries() {
             benefits: benefits.clone(),
             created_by: creator.clone(),
             created_at: 1,
+            trial_seconds: 0,
         };
         let plan_2 = SubscriptionPlan {
             id: plan_id_2,
@@ -687,6 +690,7 @@ fn test_subscription_storage_indexes_and_revenue_queries() {
             benefits,
             created_by: creator.clone(),
             created_at: 2,
+            trial_seconds: 0,
         };
 
         storage::store_plan(&env, &plan_1);
@@ -715,6 +719,9 @@ fn test_subscription_storage_indexes_and_revenue_queries() {
             auto_renew: true,
             cancelled_at: None,
             cancellation_reason: empty_reason.clone(),
+            trial_ends_at: None,
+            coupon_code: None,
+            discount_cycles_remaining: 0,
         };
         storage::store_subscription(&env, &subscription);
         storage::store_user_subscription(&env, &subscriber, 77, subscription_id);
@@ -740,6 +747,11 @@ fn test_subscription_storage_indexes_and_revenue_queries() {
             subscription_id,
             subscriber: subscriber.clone(),
             amount: 100,
+            gross_amount: 100,
+            discount_amount: 0,
+            treasury_amount: 0,
+            pool_amount: 0,
+            platform_fee: 0,
             token: None,
             paid_at: 1000,
             billing_cycle: BillingCycle::Monthly,
@@ -829,3 +841,571 @@ fn test_payment_processing_and_grace_period_cleanup() {
     );
     assert!(!client.is_subscription_active(&subscription_id));
 }
+
+#[test]
+fn test_trial_delays_first_charge_once_per_subscriber() {
+    let env = setup_env();
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let creator = Address::generate(&env);
+    let subscriber = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_ledger_timestamp(&env, 1_000);
+
+    let plan_id = create_test_plan(
+        &env,
+        &client,
+        &creator,
+        5,
+        MembershipTier::Standard,
+        1000,
+        BillingCycle::Monthly,
+    );
+    let trial = 14 * 24 * 60 * 60;
+    assert!(client
+        .try_set_plan_trial(&plan_id, &trial, &subscriber)
+        .is_err());
+    client.set_plan_trial(&plan_id, &trial, &creator);
+
+    let cycle = BillingCycle::Monthly.duration_seconds();
+    let subscription_id = client.subscribe(&plan_id, &subscriber, &true);
+    let subscription = client.get_subscription(&subscription_id);
+    assert_eq!(subscription.trial_ends_at, Some(1_000 + trial));
+    assert_eq!(subscription.next_billing_at, 1_000 + trial + cycle);
+
+    // Nothing is due at the point a trial-less subscription would be billed
+    set_ledger_timestamp(&env, 1_000 + cycle);
+    assert_eq!(client.process_due_subscriptions(&10), 0);
+
+    set_ledger_timestamp(&env, 1_000 + trial + cycle);
+    assert_eq!(client.process_due_subscriptions(&10), 1);
+    assert_eq!(
+        client
+            .get_subscription(&subscription_id)
+            .last_payment_amount,
+        Some(1000)
+    );
+
+    // Re-subscribing after cancelling does not grant a second trial
+    client.cancel_subscription(&subscription_id, &subscriber, &None);
+    let now = 1_000 + trial + cycle;
+    let second = client.subscribe(&plan_id, &subscriber, &true);
+    let resubscribed = client.get_subscription(&second);
+    assert_eq!(resubscribed.trial_ends_at, None);
+    assert_eq!(resubscribed.next_billing_at, now + cycle);
+}
+
+#[test]
+fn test_coupon_discount_applies_for_limited_cycles() {
+    let env = setup_env();
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let creator = Address::generate(&env);
+    let subscriber = Address::generate(&env);
+    let latecomer = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_ledger_timestamp(&env, 1_000);
+
+    let plan_id = create_test_plan(
+        &env,
+        &client,
+        &creator,
+        9,
+        MembershipTier::Premium,
+        1000,
+        BillingCycle::Monthly,
+    );
+    let code = String::from_str(&env, "LAUNCH50");
+    client.create_subscription_coupon(
+        &plan_id,
+        &code,
+        &DiscountKind::Percent,
+        &5_000i128,
+        &2u32,
+        &1u32,
+        &50_000u64,
+        &creator,
+    );
+    assert!(client
+        .try_create_subscription_coupon(
+            &plan_id,
+            &String::from_str(&env, "TOOBIG"),
+            &DiscountKind::Percent,
+            &10_001i128,
+            &1u32,
+            &1u32,
+            &50_000u64,
+            &creator,
+        )
+        .is_err());
+
+    let subscription_id = client.subscribe_with_coupon(&plan_id, &subscriber, &true, &code);
+    assert_eq!(
+        client
+            .get_subscription_coupon(&plan_id, &code)
+            .unwrap()
+            .uses,
+        1
+    );
+    assert!(client
+        .try_subscribe_with_coupon(&plan_id, &latecomer, &true, &code)
+        .is_err());
+
+    set_ledger_timestamp(&env, 1_000 + BillingCycle::Monthly.duration_seconds());
+    let mut charged = Vec::new(&env);
+    for _ in 0..3 {
+        client.process_subscription_payment(&subscription_id);
+        charged.push_back(
+            client
+                .get_subscription(&subscription_id)
+                .last_payment_amount
+                .unwrap(),
+        );
+    }
+    assert_eq!(charged, Vec::from_array(&env, [500i128, 500, 1000]));
+
+    env.as_contract(&contract_id, || {
+        let first = storage::get_revenue_record(&env, 1).unwrap();
+        assert_eq!(first.amount, 500);
+        assert_eq!(first.gross_amount, 1000);
+        assert_eq!(first.discount_amount, 500);
+        let third = storage::get_revenue_record(&env, 3).unwrap();
+        assert_eq!(third.discount_amount, 0);
+        assert_eq!(third.gross_amount, 1000);
+    });
+
+    // Expired codes can't be redeemed
+    let late_code = String::from_str(&env, "SPRING");
+    client.create_subscription_coupon(
+        &plan_id,
+        &late_code,
+        &DiscountKind::Fixed,
+        &100i128,
+        &1u32,
+        &10u32,
+        &(env.ledger().timestamp() + 1_000),
+        &creator,
+    );
+    set_ledger_timestamp(&env, env.ledger().timestamp() + 1_001);
+    assert!(client
+        .try_subscribe_with_coupon(&plan_id, &latecomer, &true, &late_code)
+        .is_err());
+}
+
+fn create_token_plan(
+    env: &Env,
+    client: &StellarGuildsContractClient,
+    creator: &Address,
+    token: &Address,
+    price: i128,
+) -> u64 {
+    client.create_subscription_plan(
+        &3,
+        &String::from_str(env, "Token Plan"),
+        &String::from_str(env, "billed in tokens"),
+        &MembershipTier::Standard,
+        &price,
+        &Some(token.clone()),
+        &BillingCycle::Monthly,
+        &Vec::new(env),
+        creator,
+    )
+}
+
+#[test]
+fn test_keeper_pulls_renewal_from_allowance() {
+    let env = setup_env();
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let creator = Address::generate(&env);
+    let subscriber = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_ledger_timestamp(&env, 1_000);
+
+    let token = env
+        .register_stellar_asset_contract_v2(creator.clone())
+        .address();
+    token::StellarAssetClient::new(&env, &token).mint(&subscriber, &1_000);
+
+    let plan_id = create_token_plan(&env, &client, &creator, &token, 300);
+    let subscription_id = client.subscribe(&plan_id, &subscriber, &true);
+    client.approve_subscription_billing(&subscription_id, &subscriber, &600, &10_000);
+
+    // No authorizations are available to the keeper run
+    env.set_auths(&[]);
+    let cycle = BillingCycle::Monthly.duration_seconds();
+    set_ledger_timestamp(&env, 1_000 + cycle);
+    assert_eq!(client.process_due_subscriptions(&10), 1);
+
+    let token_client = token::TokenClient::new(&env, &token);
+    assert_eq!(token_client.balance(&subscriber), 700);
+    assert_eq!(token_client.balance(&contract_id), 300);
+    assert_eq!(token_client.allowance(&subscriber, &contract_id), 300);
+    assert_eq!(
+        client.get_subscription(&subscription_id).status,
+        SubscriptionStatus::Active
+    );
+}
+
+#[test]
+fn test_short_allowance_enters_grace_period() {
+    let env = setup_env();
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let creator = Address::generate(&env);
+    let subscriber = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_ledger_timestamp(&env, 1_000);
+
+    let token = env
+        .register_stellar_asset_contract_v2(creator.clone())
+        .address();
+    token::StellarAssetClient::new(&env, &token).mint(&subscriber, &1_000);
+
+    let plan_id = create_token_plan(&env, &client, &creator, &token, 300);
+    let subscription_id = client.subscribe(&plan_id, &subscriber, &true);
+    client.approve_subscription_billing(&subscription_id, &subscriber, &100, &10_000);
+
+    let cycle = BillingCycle::Monthly.duration_seconds();
+    set_ledger_timestamp(&env, 1_000 + cycle);
+    assert_eq!(client.process_due_subscriptions(&10), 1);
+
+    let subscription = client.get_subscription(&subscription_id);
+    assert_eq!(subscription.status, SubscriptionStatus::GracePeriod);
+    assert_eq!(subscription.failed_payment_count, 1);
+    assert_eq!(
+        token::TokenClient::new(&env, &token).balance(&subscriber),
+        1_000
+    );
+
+    // Topping up the allowance lets the retry go through
+    client.approve_subscription_billing(&subscription_id, &subscriber, &300, &10_000);
+    assert!(client.retry_subscription_payment(&subscription_id));
+    assert_eq!(
+        client.get_subscription(&subscription_id).status,
+        SubscriptionStatus::Active
+    );
+}
+
+fn setup_guild(env: &Env, client: &StellarGuildsContractClient, owner: &Address) -> u64 {
+    let name = String::from_str(env, "Subscriber Guild");
+    let description = String::from_str(env, "Guild with paid membership");
+    client.create_guild(&name, &description, owner, &None::<InitializerProof>)
+}
+
+#[test]
+fn test_plan_role_follows_subscription_lifecycle() {
+    let env = setup_env();
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let subscriber = Address::generate(&env);
+    let outsider = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    let guild_id = setup_guild(&env, &client, &owner);
+    let basic = create_test_plan(
+        &env,
+        &client,
+        &owner,
+        guild_id,
+        MembershipTier::Basic,
+        500,
+        BillingCycle::Monthly,
+    );
+    let premium = create_test_plan(
+        &env,
+        &client,
+        &owner,
+        guild_id,
+        MembershipTier::Premium,
+        2000,
+        BillingCycle::Monthly,
+    );
+
+    // Only guild admins can map roles, and never to admin-level roles
+    assert!(client
+        .try_set_plan_role(&basic, &Some(Role::Contributor), &outsider)
+        .is_err());
+    assert!(client
+        .try_set_plan_role(&basic, &Some(Role::Admin), &owner)
+        .is_err());
+
+    client.set_plan_role(&basic, &Some(Role::Contributor), &owner);
+    client.set_plan_role(&premium, &Some(Role::Member), &owner);
+    assert_eq!(client.get_plan_role(&premium), Some(Role::Member));
+
+    let subscription_id = client.subscribe(&basic, &subscriber, &true);
+    assert_eq!(
+        client.get_member(&guild_id, &subscriber).role,
+        Role::Contributor
+    );
+    assert_eq!(client.get_all_members(&guild_id).len(), 2);
+    assert!(client.has_active_guild_subscription(&guild_id, &subscriber));
+
+    client.change_subscription_tier(&subscription_id, &premium, &true, &subscriber);
+    assert_eq!(client.get_member(&guild_id, &subscriber).role, Role::Member);
+    assert_eq!(client.get_all_members(&guild_id).len(), 2);
+
+    client.cancel_subscription(&subscription_id, &subscriber, &None);
+    assert!(!client.is_member(&guild_id, &subscriber));
+    assert_eq!(client.get_all_members(&guild_id).len(), 1);
+    assert!(!client.has_active_guild_subscription(&guild_id, &subscriber));
+}
+
+#[test]
+fn test_grace_expiry_restores_previous_guild_role() {
+    let env = setup_env();
+    let contract_id = register_and_init_contract(&env);
+    let client = StellarGuildsContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let admin = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_ledger_timestamp(&env, 1_000);
+
+    let guild_id = setup_guild(&env, &client, &owner);
+    client.add_member(&guild_id, &contributor, &Role::Contributor, &owner);
+    client.add_member(&guild_id, &admin, &Role::Admin, &owner);
+
+    let plan_id = create_test_plan(
+        &env,
+        &client,
+        &owner,
+        guild_id,
+        MembershipTier::Standard,
+        1000,
+        BillingCycle::Monthly,
+    );
+    client.set_plan_role(&plan_id, &Some(Role::Member), &owner);
+
+    let contributor_sub = client.subscribe(&plan_id, &contributor, &true);
+    let admin_sub = client.subscribe(&plan_id, &admin, &true);
+    assert_eq!(
+        client.get_member(&guild_id, &contributor).role,
+        Role::Member
+    );
+    // Higher roles are never lowered by a plan
+    assert_eq!(client.get_member(&guild_id, &admin).role, Role::Admin);
+
+    // Grace period in progress: the subscription still counts as live
+    env.as_contract(&contract_id, || {
+        for id in [contributor_sub, admin_sub] {
+            let mut grace = storage::get_subscription(&env, id).unwrap();
+            grace.status = SubscriptionStatus::GracePeriod;
+            grace.grace_period_ends_at = Some(2_500);
+            grace.failed_payment_count = 1;
+            storage::store_subscription(&env, &grace);
+        }
+    });
+    assert!(client.has_active_guild_subscription(&guild_id, &contributor));
+
+    set_ledger_timestamp(&env, 3_000);
+    assert_eq!(client.process_due_subscriptions(&10), 2);
+
+    assert_eq!(
+        client.get_member(&guild_id, &contributor).role,
+        Role::Contributor
+    );
+    assert_eq!(client.get_member(&guild_id, &admin).role, Role::Admin);
+    assert!(!client.has_active_guild_subscription(&guild_id, &contributor));
+}
+
+struct RevenueFixture {
+    client_id: Address,
+    admin: Address,
+    creator: Address,
+    subscriber: Address,
+    token: Address,
+    treasury_id: u64,
+    pool_id: u64,
+}
+
+fn setup_revenue_split(env: &Env) -> RevenueFixture {
+    let client_id = env.register_contract(None, StellarGuildsContract);
+    let client = StellarGuildsContractClient::new(env, &client_id);
+    let admin = Address::generate(env);
+    let creator = Address::generate(env);
+    let subscriber = Address::generate(env);
+    client.initialize(&admin);
+
+    env.mock_all_auths();
+    set_ledger_timestamp(env, 1_000);
+
+    let token = env
+        .register_stellar_asset_contract_v2(creator.clone())
+        .address();
+    token::StellarAssetClient::new(env, &token).mint(&subscriber, &5_000);
+
+    let mut signers = Vec::new(env);
+    signers.push_back(creator.clone());
+    let treasury_id = client.initialize_treasury(&3, &signers, &1);
+    let pool_id = client.create_payment_pool(
+        &100,
+        &Some(token.clone()),
+        &DistributionRule::EqualSplit,
+        &creator,
+    );
+
+    RevenueFixture {
+        client_id,
+        admin,
+        creator,
+        subscriber,
+        token,
+        treasury_id,
+        pool_id,
+    }
+}
+
+#[test]
+fn test_revenue_split_routes_payment_and_reports_period() {
+    let env = setup_env();
+    let f = setup_revenue_split(&env);
+    let client = StellarGuildsContractClient::new(&env, &f.client_id);
+
+    let plan_id = create_token_plan(&env, &client, &f.creator, &f.token, 1_000);
+    client.set_subscription_revenue_split(
+        &plan_id,
+        &f.treasury_id,
+        &8_000,
+        &f.pool_id,
+        &1_500,
+        &500,
+        &f.creator,
+    );
+    let subscription_id = client.subscribe(&plan_id, &f.subscriber, &true);
+    client.approve_subscription_billing(&subscription_id, &f.subscriber, &5_000, &10_000);
+
+    let cycle = BillingCycle::Monthly.duration_seconds();
+    set_ledger_timestamp(&env, 1_000 + cycle);
+    assert!(client.process_subscription_payment(&subscription_id));
+
+    let token_client = token::TokenClient::new(&env, &f.token);
+    assert_eq!(token_client.balance(&f.admin), 50);
+    assert_eq!(token_client.balance(&f.client_id), 950);
+    assert_eq!(
+        client.get_treasury_balance(&f.treasury_id, &Some(f.token.clone())),
+        800
+    );
+    env.as_contract(&f.client_id, || {
+        let pool = crate::payment::storage::get_payment_pool(&env, f.pool_id).unwrap();
+        assert_eq!(pool.total_amount, 250);
+
+        let record = storage::get_revenue_record(&env, 1).unwrap();
+        assert_eq!(record.plan_id, plan_id);
+        assert_eq!(record.treasury_amount, 800);
+        assert_eq!(record.pool_amount, 150);
+        assert_eq!(record.platform_fee, 50);
+    });
+
+    let report =
+        client.get_subscription_revenue_report(&3, &(1_000 + cycle), &Some(f.token.clone()));
+    assert_eq!(report.payments, 1);
+    assert_eq!(report.collected, 1_000);
+    assert_eq!(report.treasury_amount, 800);
+    assert_eq!(report.pool_amount, 150);
+    assert_eq!(report.platform_fee, 50);
+    assert_eq!(report.unrouted, 0);
+    assert!(report.period_start <= 1_000 + cycle && 1_000 + cycle < report.period_end);
+
+    // Nothing was collected in the following period
+    let next =
+        client.get_subscription_revenue_report(&3, &report.period_end, &Some(f.token.clone()));
+    assert_eq!(next.payments, 0);
+    assert_eq!(next.collected, 0);
+}
+
+#[test]
+fn test_revenue_split_validation_and_unrouted_shares() {
+    let env = setup_env();
+    let f = setup_revenue_split(&env);
+    let client = StellarGuildsContractClient::new(&env, &f.client_id);
+    let outsider = Address::generate(&env);
+
+    let plan_id = create_token_plan(&env, &client, &f.creator, &f.token, 333);
+    let mut other_signers = Vec::new(&env);
+    other_signers.push_back(f.creator.clone());
+    let other_treasury = client.initialize_treasury(&9, &other_signers, &1);
+
+    // Shares must cover the whole payment
+    assert!(client
+        .try_set_subscription_revenue_split(
+            &plan_id,
+            &f.treasury_id,
+            &8_000,
+            &f.pool_id,
+            &1_000,
+            &500,
+            &f.creator
+        )
+        .is_err());
+    // Treasury must belong to the plan's guild
+    assert!(client
+        .try_set_subscription_revenue_split(
+            &plan_id,
+            &other_treasury,
+            &8_000,
+            &f.pool_id,
+            &1_500,
+            &500,
+            &f.creator
+        )
+        .is_err());
+    // Only the plan creator configures the split
+    assert!(client
+        .try_set_subscription_revenue_split(
+            &plan_id,
+            &f.treasury_id,
+            &8_000,
+            &f.pool_id,
+            &1_500,
+            &500,
+            &outsider
+        )
+        .is_err());
+
+    client.set_subscription_revenue_split(
+        &plan_id,
+        &f.treasury_id,
+        &8_000,
+        &f.pool_id,
+        &1_500,
+        &500,
+        &f.creator,
+    );
+    let split = client.get_subscription_revenue_split(&plan_id).unwrap();
+    assert_eq!(split.treasury_bps, 8_000);
+
+    let subscription_id = client.subscribe(&plan_id, &f.subscriber, &true);
+    client.approve_subscription_billing(&subscription_id, &f.subscriber, &5_000, &10_000);
+    client.emergency_pause(&f.treasury_id, &f.creator, &true);
+
+    let cycle = BillingCycle::Monthly.duration_seconds();
+    set_ledger_timestamp(&env, 1_000 + cycle);
+    assert!(client.process_subscription_payment(&subscription_id));
+
+    // 333 splits into 266 + 49 + 16 with 2 of rounding dust kept by the guild;
+    // the paused treasury's share stays in the contract as unrouted
+    let report =
+        client.get_subscription_revenue_report(&3, &(1_000 + cycle), &Some(f.token.clone()));
+    assert_eq!(report.collected, 333);
+    assert_eq!(report.treasury_amount, 0);
+    assert_eq!(report.pool_amount, 49);
+    assert_eq!(report.platform_fee, 16);
+    assert_eq!(report.unrouted, 268);
+    assert_eq!(
+        client.get_treasury_balance(&f.treasury_id, &Some(f.token.clone())),
+        0
+    );
+
+    // Clearing the split leaves later payments unrouted
+    client.set_subscription_revenue_split(&plan_id, &0, &0, &0, &0, &0, &f.creator);
+    assert_eq!(client.get_subscription_revenue_split(&plan_id), None);
+}
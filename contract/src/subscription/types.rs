@@ -1,4 +1,5 @@
-﻿use soroban_sdk::{contracterror, contracttype, Address, String, Vec};
+﻿use crate::guild::types::Role;
+use soroban_sdk::{contracterror, contracttype, Address, String, Vec};
 
 /// Billing cycle options for subscriptions
 #[contracttype]
@@ -84,6 +85,60 @@ pub struct SubscriptionPlan {
     pub created_by: Address,
     /// Creation timestamp
     pub created_at: u64,
+    /// Free trial length in seconds before the first paid cycle (0 for none)
+    pub trial_seconds: u64,
+}
+
+/// How a coupon reduces the plan price
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DiscountKind {
+    /// `value` is in basis points of the price (10_000 = 100%)
+    Percent,
+    /// `value` is a flat amount off the price, capped at the price
+    Fixed,
+}
+
+/// A discount code redeemable when subscribing to a plan
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Coupon {
+    /// Code entered by the subscriber
+    pub code: String,
+    /// Plan the coupon applies to
+    pub plan_id: u64,
+    /// Percent or fixed discount
+    pub kind: DiscountKind,
+    /// Discount value, interpreted according to `kind`
+    pub value: i128,
+    /// Number of billing cycles the discount applies to
+    pub cycles: u32,
+    /// Maximum number of redemptions
+    pub max_uses: u32,
+    /// Redemptions so far
+    pub uses: u32,
+    /// Timestamp after which the coupon can no longer be redeemed
+    pub expires_at: u64,
+    /// Creator of the coupon
+    pub created_by: Address,
+}
+
+/// Guild membership a subscriber received through their plan's role
+///
+/// Revoking the grant removes a member the subscription added, or restores
+/// the role held before the grant. If an admin has changed the member's role
+/// since, the member is left as they are.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MembershipGrant {
+    /// Subscription that holds the grant
+    pub subscription_id: u64,
+    /// Role granted by the plan
+    pub role: Role,
+    /// Whether the subscription added the member to the guild
+    pub added: bool,
+    /// Role held before the grant (equal to `role` when `added`)
+    pub previous_role: Role,
 }
 
 /// A user subscription instance
@@ -120,6 +175,12 @@ pub struct Subscription {
     pub cancelled_at: Option<u64>,
     /// Cancellation reason
     pub cancellation_reason: Option<String>,
+    /// End of the free trial, if the subscription started with one
+    pub trial_ends_at: Option<u64>,
+    /// Coupon redeemed at subscribe time
+    pub coupon_code: Option<String>,
+    /// Billing cycles the coupon discount still applies to
+    pub discount_cycles_remaining: u32,
 }
 
 /// Payment retry configuration
@@ -161,8 +222,18 @@ pub struct RevenueRecord {
     pub subscription_id: u64,
     /// Subscriber address
     pub subscriber: Address,
-    /// Payment amount
+    /// Payment amount actually charged (gross minus discount)
     pub amount: i128,
+    /// Plan price before any discount
+    pub gross_amount: i128,
+    /// Coupon discount applied to this payment
+    pub discount_amount: i128,
+    /// Share booked into the guild treasury
+    pub treasury_amount: i128,
+    /// Share added to the contributor payment pool
+    pub pool_amount: i128,
+    /// Share paid to the platform as a fee
+    pub platform_fee: i128,
     /// Token address (None for native XLM)
     pub token: Option<Address>,
     /// Payment timestamp
@@ -175,6 +246,46 @@ pub struct RevenueRecord {
     pub retry_attempt: u32,
 }
 
+/// How a plan's collected revenue is split, in basis points of each payment
+///
+/// The three shares must add up to 10_000. A zero target id means the
+/// share is unused and must be 0.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RevenueSplit {
+    /// Plan the split applies to
+    pub plan_id: u64,
+    /// Guild treasury credited with `treasury_bps` (0 for none)
+    pub treasury_id: u64,
+    pub treasury_bps: u32,
+    /// Pending contributor payment pool topped up with `pool_bps` (0 for none)
+    pub pool_id: u64,
+    pub pool_bps: u32,
+    /// Fee paid to the platform admin
+    pub platform_bps: u32,
+}
+
+/// Subscription revenue collected by a guild in one reporting period, per token
+///
+/// `unrouted` is revenue left in the contract because a plan had no split or
+/// its treasury or pool could not take the share when the payment came in.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RevenueReport {
+    pub guild_id: u64,
+    pub token: Option<Address>,
+    pub period_start: u64,
+    pub period_end: u64,
+    pub payments: u32,
+    pub gross_amount: i128,
+    pub discount_amount: i128,
+    pub collected: i128,
+    pub treasury_amount: i128,
+    pub pool_amount: i128,
+    pub platform_fee: i128,
+    pub unrouted: i128,
+}
+
 /// Proration calculation result
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -238,6 +349,18 @@ pub enum SubscriptionError {
     InvalidState = 15,
     /// Revenue record not found
     RevenueRecordNotFound = 16,
+    /// Subscription charge is under dispute
+    DisputeActive = 17,
+    /// Coupon not found or its terms are invalid
+    InvalidCoupon = 18,
+    /// Coupon has expired
+    CouponExpired = 19,
+    /// Coupon has no redemptions left
+    CouponExhausted = 20,
+    /// Plans may only grant the Member or Contributor role of their own guild
+    InvalidPlanRole = 21,
+    /// Revenue split shares or targets are invalid
+    InvalidRevenueSplit = 22,
 }
 
 /// Event emitted when a subscription plan is created
@@ -311,3 +434,88 @@ pub struct RevenueRecordedEvent {
     pub amount: i128,
     pub paid_at: u64,
 }
+
+/// Event emitted when a disputed charge is refunded
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChargeRefundedEvent {
+    pub subscription_id: u64,
+    pub subscriber: Address,
+    pub amount: i128,
+}
+
+/// Event emitted when a plan's trial length is changed
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlanTrialUpdatedEvent {
+    pub plan_id: u64,
+    pub trial_seconds: u64,
+}
+
+/// Event emitted when a coupon is created
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CouponCreatedEvent {
+    pub plan_id: u64,
+    pub code: String,
+    pub kind: DiscountKind,
+    pub value: i128,
+    pub cycles: u32,
+    pub max_uses: u32,
+    pub expires_at: u64,
+}
+
+/// Event emitted when a coupon is redeemed at subscribe time
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CouponRedeemedEvent {
+    pub plan_id: u64,
+    pub code: String,
+    pub subscription_id: u64,
+    pub uses: u32,
+}
+
+/// Event emitted when a subscriber sets the allowance renewals are pulled from
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BillingApprovedEvent {
+    pub subscription_id: u64,
+    pub subscriber: Address,
+    pub amount: i128,
+    pub expiration_ledger: u32,
+}
+
+/// Event emitted when a plan's guild role mapping changes
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlanRoleUpdatedEvent {
+    pub plan_id: u64,
+    pub guild_id: u64,
+    /// Role now granted, or the role that stopped being granted
+    pub role: Role,
+    pub enabled: bool,
+}
+
+/// Event emitted when a plan's revenue split changes
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RevenueSplitUpdatedEvent {
+    pub plan_id: u64,
+    pub treasury_id: u64,
+    pub treasury_bps: u32,
+    pub pool_id: u64,
+    pub pool_bps: u32,
+    pub platform_bps: u32,
+}
+
+/// Event emitted when a payment's revenue is routed
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RevenueRoutedEvent {
+    pub record_id: u64,
+    pub guild_id: u64,
+    pub treasury_amount: i128,
+    pub pool_amount: i128,
+    pub platform_fee: i128,
+    pub unrouted: i128,
+}
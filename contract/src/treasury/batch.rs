@@ -0,0 +1,262 @@
+use soroban_sdk::{token::Client as TokenClient, Address, Env, Map, String, Vec};
+
+use crate::events::emit::emit_event;
+use crate::events::topics::{ACT_PROPOSED, MOD_TREASURY};
+use crate::treasury::commitment::ensure_uncommitted;
+use crate::treasury::management::{enforce_allowance, enforce_budget};
+use crate::treasury::multisig::{assert_signer, has_all_signer_approvals, TX_EXPIRY_SECONDS};
+use crate::treasury::storage::{
+    get_batch, get_next_tx_id, get_treasury, store_batch, store_transaction,
+};
+use crate::treasury::types::{
+    BatchLeg, BatchWithdrawal, BatchWithdrawalProposedEvent, Transaction, TransactionStatus,
+    TransactionType, Treasury, TreasuryError, VelocityDecision,
+};
+use crate::treasury::velocity::{evaluate_outflows, record_velocity_spend};
+
+const MAX_BATCH_LEGS: u32 = 50;
+
+/// Propose paying several recipients in one transaction.
+///
+/// The batch executes atomically: every leg is paid or none is. Amounts in
+/// different tokens are never added up; the transaction records the largest
+/// per-token total, so the batch needs the high-value approval threshold if
+/// any one token's total would as a single withdrawal.
+pub fn propose_batch_withdrawal(
+    env: &Env,
+    treasury_id: u64,
+    proposer: Address,
+    legs: Vec<BatchLeg>,
+    reason: String,
+) -> u64 {
+    if legs.is_empty() {
+        panic!("batch has no legs");
+    }
+    if legs.len() > MAX_BATCH_LEGS {
+        panic!("too many legs");
+    }
+    for leg in legs.iter() {
+        if leg.amount <= 0 {
+            panic!("amount must be positive");
+        }
+    }
+    let totals = token_totals(env, &legs);
+    let (token, amount) = largest_total(&totals);
+
+    let treasury = get_treasury(env, treasury_id).expect("treasury not found");
+    if treasury.paused {
+        panic!("treasury is paused");
+    }
+    assert_signer(env, &treasury, &proposer);
+
+    let tx_id = get_next_tx_id(env);
+    let now = env.ledger().timestamp();
+    let mut approvals = Vec::new(env);
+    approvals.push_back(proposer.clone());
+
+    // Batches over a blocking velocity limit are recorded as rejected
+    let velocity_decision = evaluate_legs(env, treasury_id, &proposer, &legs);
+    let status = if velocity_decision == VelocityDecision::Blocked {
+        TransactionStatus::Rejected
+    } else {
+        TransactionStatus::Pending
+    };
+
+    let tx = Transaction {
+        id: tx_id,
+        treasury_id,
+        tx_type: TransactionType::BatchWithdrawal,
+        amount,
+        token,
+        recipient: None,
+        proposer: proposer.clone(),
+        approvals,
+        status,
+        created_at: now,
+        expires_at: now + TX_EXPIRY_SECONDS,
+        reason,
+        velocity_decision,
+    };
+    store_transaction(env, &tx);
+    store_batch(
+        env,
+        &BatchWithdrawal {
+            tx_id,
+            legs: legs.clone(),
+            leg_tx_ids: Vec::new(env),
+        },
+    );
+
+    let event = BatchWithdrawalProposedEvent {
+        treasury_id,
+        tx_id,
+        proposer,
+        leg_count: legs.len(),
+        token_totals: totals,
+    };
+    emit_event(env, MOD_TREASURY, ACT_PROPOSED, event);
+
+    tx_id
+}
+
+/// Velocity decision for a batch against outflows executed so far.
+pub fn evaluate_batch_velocity(env: &Env, tx: &Transaction) -> VelocityDecision {
+    let batch = get_batch(env, tx.id).expect("batch not found");
+    evaluate_legs(env, tx.treasury_id, &tx.proposer, &batch.legs)
+}
+
+/// Check each token's outflows against that token's limits; the strictest
+/// decision wins.
+fn evaluate_legs(
+    env: &Env,
+    treasury_id: u64,
+    proposer: &Address,
+    legs: &Vec<BatchLeg>,
+) -> VelocityDecision {
+    let mut decision = VelocityDecision::WithinLimits;
+    for (token, by_recipient) in outflows(env, legs).iter() {
+        match evaluate_outflows(env, treasury_id, proposer, &token, &by_recipient) {
+            VelocityDecision::Blocked => return VelocityDecision::Blocked,
+            VelocityDecision::Escalated => decision = VelocityDecision::Escalated,
+            VelocityDecision::WithinLimits => {}
+        }
+    }
+    decision
+}
+
+/// Pay every leg of an approved batch.
+///
+/// The `withdrawal` budget and the executor's allowance are charged each
+/// token's total as a separate withdrawal. Each leg is recorded as an
+/// executed `Withdrawal` transaction so it shows in the transaction history.
+pub fn execute_batch_withdrawal(
+    env: &Env,
+    treasury: &mut Treasury,
+    tx: &Transaction,
+    executor: &Address,
+) {
+    let mut batch = get_batch(env, tx.id).expect("batch not found");
+
+    let category = String::from_str(env, "withdrawal");
+    let all_approved = has_all_signer_approvals(treasury, tx);
+    for (token, total) in token_totals(env, &batch.legs).iter() {
+        enforce_budget(env, tx.treasury_id, &category, total, all_approved)
+            .unwrap_or_else(treasury_error);
+        ensure_uncommitted(env, treasury, &token, total);
+        let available = match token {
+            Some(ref token_addr) => treasury.token_balances.get(token_addr.clone()).unwrap_or(0),
+            None => treasury.balance_xlm,
+        };
+        if available < total {
+            panic!("insufficient treasury balance");
+        }
+        enforce_allowance(
+            env,
+            tx.treasury_id,
+            executor,
+            &token,
+            total,
+            &crate::allowance::AllowanceOperation::Withdrawal,
+        )
+        .unwrap_or_else(treasury_error);
+    }
+
+    let now = env.ledger().timestamp();
+    for leg in batch.legs.iter() {
+        match leg.token {
+            Some(ref token_addr) => {
+                let current = treasury.token_balances.get(token_addr.clone()).unwrap_or(0);
+                treasury
+                    .token_balances
+                    .set(token_addr.clone(), current - leg.amount);
+                TokenClient::new(env, token_addr).transfer(
+                    &env.current_contract_address(),
+                    &leg.recipient,
+                    &leg.amount,
+                );
+            }
+            None => {
+                treasury.balance_xlm -= leg.amount;
+            }
+        }
+        record_velocity_spend(
+            env,
+            tx.treasury_id,
+            &tx.proposer,
+            &leg.recipient,
+            leg.amount,
+            &leg.token,
+        );
+
+        let leg_tx_id = get_next_tx_id(env);
+        store_transaction(
+            env,
+            &Transaction {
+                id: leg_tx_id,
+                treasury_id: tx.treasury_id,
+                tx_type: TransactionType::Withdrawal,
+                amount: leg.amount,
+                token: leg.token.clone(),
+                recipient: Some(leg.recipient.clone()),
+                proposer: tx.proposer.clone(),
+                approvals: tx.approvals.clone(),
+                status: TransactionStatus::Executed,
+                created_at: now,
+                expires_at: now,
+                reason: tx.reason.clone(),
+                velocity_decision: tx.velocity_decision.clone(),
+            },
+        );
+        batch.leg_tx_ids.push_back(leg_tx_id);
+        treasury.total_withdrawals += leg.amount;
+    }
+    store_batch(env, &batch);
+}
+
+/// Total of the legs in each token.
+fn token_totals(env: &Env, legs: &Vec<BatchLeg>) -> Map<Option<Address>, i128> {
+    let mut totals: Map<Option<Address>, i128> = Map::new(env);
+    for leg in legs.iter() {
+        let current = totals.get(leg.token.clone()).unwrap_or(0);
+        totals.set(
+            leg.token,
+            current.checked_add(leg.amount).expect("overflow"),
+        );
+    }
+    totals
+}
+
+/// The token with the largest total, and that total.
+fn largest_total(totals: &Map<Option<Address>, i128>) -> (Option<Address>, i128) {
+    let mut largest = (None, 0);
+    for (token, total) in totals.iter() {
+        if total > largest.1 {
+            largest = (token, total);
+        }
+    }
+    largest
+}
+
+/// Leg amounts per token, then per recipient.
+fn outflows(env: &Env, legs: &Vec<BatchLeg>) -> Map<Option<Address>, Map<Address, i128>> {
+    let mut by_token: Map<Option<Address>, Map<Address, i128>> = Map::new(env);
+    for leg in legs.iter() {
+        let mut by_recipient = by_token
+            .get(leg.token.clone())
+            .unwrap_or_else(|| Map::new(env));
+        let current = by_recipient.get(leg.recipient.clone()).unwrap_or(0);
+        by_recipient.set(leg.recipient, current + leg.amount);
+        by_token.set(leg.token, by_recipient);
+    }
+    by_token
+}
+
+fn treasury_error(e: TreasuryError) {
+    match e {
+        TreasuryError::BudgetExceeded => panic!("budget exceeded"),
+        TreasuryError::AllowanceExceeded => panic!("allowance exceeded"),
+        TreasuryError::BudgetOverspendNotApproved => {
+            panic!("budget overspend requires approval from all signers")
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use soroban_sdk::{Address, Env};
+
+use crate::treasury::storage::{get_committed, set_committed};
+use crate::treasury::types::Treasury;
+
+/// Liquid balance of a token not committed to milestone projects.
+pub fn available_balance(env: &Env, treasury: &Treasury, token: &Option<Address>) -> i128 {
+    let liquid = match token {
+        Some(token_addr) => treasury.token_balances.get(token_addr.clone()).unwrap_or(0),
+        None => treasury.balance_xlm,
+    };
+    liquid - get_committed(env, treasury.id, token)
+}
+
+/// Panic if paying `amount` would dip into funds committed to projects.
+/// Plain shortfalls are left to the caller's own balance check.
+pub fn ensure_uncommitted(env: &Env, treasury: &Treasury, token: &Option<Address>, amount: i128) {
+    if get_committed(env, treasury.id, token) > 0
+        && available_balance(env, treasury, token) < amount
+    {
+        panic!("funds are committed to projects");
+    }
+}
+
+/// Reserve `amount` of the available balance so other outflows cannot spend it.
+pub fn commit_funds(env: &Env, treasury: &Treasury, token: &Option<Address>, amount: i128) {
+    if available_balance(env, treasury, token) < amount {
+        panic!("insufficient available balance");
+    }
+    let committed = get_committed(env, treasury.id, token);
+    set_committed(env, treasury.id, token, committed + amount);
+}
+
+/// Return up to `amount` of a reservation to the available balance.
+pub fn release_commitment(env: &Env, treasury_id: u64, token: &Option<Address>, amount: i128) {
+    let committed = get_committed(env, treasury_id, token);
+    set_committed(env, treasury_id, token, (committed - amount).max(0));
+}
@@ -1,26 +1,38 @@
+use crate::dispute::storage as dispute_storage;
+use crate::dispute::types::DisputeReference;
 use crate::events::emit::emit_event;
 use crate::events::topics::{
-    ACT_APPROVED, ACT_CREATED, ACT_EXECUTED, ACT_FUNDED, ACT_GRANTED, ACT_PAUSED, ACT_PROPOSED,
-    ACT_RESUMED, ACT_UPDATED, MOD_TREASURY,
+    ACT_APPROVED, ACT_CANCELLED, ACT_CREATED, ACT_EXECUTED, ACT_FUNDED, ACT_GRANTED, ACT_PAUSED,
+    ACT_PROPOSED, ACT_REJECTED, ACT_RESUMED, ACT_UPDATED, MOD_TREASURY,
 };
 use soroban_sdk::{token::Client as TokenClient, Address, Env, String, Vec};
 
 use crate::analytics::storage::store_snapshot;
 use crate::analytics::types::TreasurySnapshot;
+use crate::interfaces::swapper::{convert_payout, PayoutConversion};
 
+use crate::treasury::batch::{evaluate_batch_velocity, execute_batch_withdrawal};
+use crate::treasury::commitment::ensure_uncommitted;
 use crate::treasury::multisig::{
-    add_approval, assert_signer, ensure_is_signer, expire_if_needed, required_approvals_for_tx,
-    validate_threshold, TX_EXPIRY_SECONDS,
+    add_approval, assert_signer, ensure_is_signer, expire_if_needed, has_all_signer_approvals,
+    has_approved, required_approvals_for_tx, validate_threshold, TX_EXPIRY_SECONDS,
 };
 use crate::treasury::storage::{
-    get_allowance, get_budget, get_next_treasury_id, get_next_tx_id, get_treasury,
-    get_treasury_transactions, store_allowance, store_budget, store_transaction, store_treasury,
+    add_rejection, get_allowance, get_budget, get_next_treasury_id, get_next_tx_id, get_rejections,
+    get_treasury, get_treasury_transactions, store_allowance, store_budget, store_transaction,
+    store_treasury,
 };
+use crate::treasury::swap::{execute_swap_transaction, execute_swapper_change};
 use crate::treasury::types::{
-    Allowance, Budget, DepositEvent, EmergencyPauseEvent, Transaction, TransactionApprovedEvent,
-    TransactionExecutedEvent, TransactionStatus, TransactionType, Treasury, TreasuryError,
-    TreasuryInitializedEvent, WithdrawalProposedEvent,
+    Allowance, Budget, BudgetConfiguredEvent, BudgetMode, DepositEvent, EmergencyPauseEvent,
+    Transaction, TransactionApprovedEvent, TransactionExecutedEvent, TransactionRejectedEvent,
+    TransactionRejection, TransactionStatus, TransactionType, Treasury, TreasuryError,
+    TreasuryInitializedEvent, VelocityDecision, WithdrawalProposedEvent,
 };
+use crate::treasury::vault::{execute_vault_deposit, execute_vault_withdrawal};
+use crate::treasury::velocity::{evaluate_velocity, record_velocity_spend};
+
+const MAX_BUDGET_DEPTH: u32 = 8;
 
 pub fn initialize_treasury(
     env: &Env,
@@ -85,6 +97,35 @@ pub fn deposit(
         panic!("amount must be positive");
     }
 
+    let treasury = get_treasury(env, treasury_id).expect("treasury not found");
+    if treasury.paused {
+        panic!("treasury is paused");
+    }
+
+    if let Some(ref token_addr) = token {
+        let client = TokenClient::new(env, token_addr);
+        client.transfer(&depositor, &env.current_contract_address(), &amount);
+    }
+
+    credit_deposit(env, treasury_id, depositor, amount, token)
+}
+
+/// Book funds the contract already holds as a deposit into a treasury.
+///
+/// Performs the accounting half of `deposit` without moving tokens, so other
+/// modules that collected funds on the guild's behalf (e.g. subscription
+/// revenue) can hand them over.
+pub fn credit_deposit(
+    env: &Env,
+    treasury_id: u64,
+    depositor: Address,
+    amount: i128,
+    token: Option<Address>,
+) -> bool {
+    if amount <= 0 {
+        panic!("amount must be positive");
+    }
+
     let mut treasury = get_treasury(env, treasury_id).expect("treasury not found");
     if treasury.paused {
         panic!("treasury is paused");
@@ -92,9 +133,6 @@ pub fn deposit(
 
     match token {
         Some(ref token_addr) => {
-            let client = TokenClient::new(env, token_addr);
-            client.transfer(&depositor, &env.current_contract_address(), &amount);
-
             let mut balances = treasury.token_balances.clone();
             let current = balances.get(token_addr.clone()).unwrap_or(0i128);
             balances.set(token_addr.clone(), current + amount);
@@ -124,6 +162,7 @@ pub fn deposit(
         created_at: now,
         expires_at: now,
         reason: String::from_str(env, "deposit"),
+        velocity_decision: VelocityDecision::WithinLimits,
     };
     store_transaction(env, &tx);
 
@@ -166,6 +205,15 @@ pub fn propose_withdrawal(
     let mut approvals = Vec::new(env);
     approvals.push_back(proposer.clone());
 
+    // Withdrawals over a blocking velocity limit are recorded as rejected
+    let velocity_decision =
+        evaluate_velocity(env, treasury_id, &proposer, &recipient, amount, &token);
+    let status = if velocity_decision == VelocityDecision::Blocked {
+        TransactionStatus::Rejected
+    } else {
+        TransactionStatus::Pending
+    };
+
     let tx = Transaction {
         id: tx_id,
         treasury_id,
@@ -175,10 +223,11 @@ pub fn propose_withdrawal(
         recipient: Some(recipient.clone()),
         proposer: proposer.clone(),
         approvals,
-        status: TransactionStatus::Pending,
+        status,
         created_at: now,
         expires_at: now + TX_EXPIRY_SECONDS,
         reason,
+        velocity_decision,
     };
     store_transaction(env, &tx);
 
@@ -205,12 +254,22 @@ pub fn approve_transaction(env: &Env, tx_id: u64, approver: Address) -> bool {
     expire_if_needed(&mut tx, now);
     if matches!(
         tx.status,
-        TransactionStatus::Rejected | TransactionStatus::Executed | TransactionStatus::Expired
+        TransactionStatus::Rejected
+            | TransactionStatus::Executed
+            | TransactionStatus::Expired
+            | TransactionStatus::Cancelled
     ) {
         panic!("transaction not approvable");
     }
 
+    if dispute_storage::is_reference_locked(env, &DisputeReference::TreasuryTransaction, tx_id) {
+        panic!("transaction is in active dispute");
+    }
+
     ensure_is_signer(&treasury, &approver);
+    if has_rejected(env, tx_id, &approver) {
+        panic!("signer already rejected");
+    }
     add_approval(&mut tx, &approver);
 
     let required = required_approvals_for_tx(&treasury, &tx);
@@ -230,11 +289,113 @@ pub fn approve_transaction(env: &Env, tx_id: u64, approver: Address) -> bool {
     true
 }
 
-fn enforce_budget(
+/// Vote to reject a pending transaction.
+///
+/// The transaction is rejected once the signers who have not rejected it can
+/// no longer reach the approvals it requires. A signer who approved cannot
+/// also reject.
+pub fn reject_transaction(env: &Env, tx_id: u64, signer: Address, reason: String) -> bool {
+    signer.require_auth();
+
+    let mut tx = crate::treasury::storage::get_transaction(env, tx_id).expect("tx not found");
+    let treasury = get_treasury(env, tx.treasury_id).expect("treasury not found");
+
+    let now = env.ledger().timestamp();
+    expire_if_needed(&mut tx, now);
+    if tx.status != TransactionStatus::Pending {
+        panic!("transaction not rejectable");
+    }
+
+    if dispute_storage::is_reference_locked(env, &DisputeReference::TreasuryTransaction, tx_id) {
+        panic!("transaction is in active dispute");
+    }
+
+    ensure_is_signer(&treasury, &signer);
+    if has_approved(&tx, &signer) {
+        panic!("signer already approved");
+    }
+    if has_rejected(env, tx_id, &signer) {
+        panic!("duplicate rejection");
+    }
+
+    add_rejection(
+        env,
+        tx_id,
+        &TransactionRejection {
+            signer: signer.clone(),
+            reason: reason.clone(),
+            timestamp: now,
+        },
+    );
+
+    let rejections = get_rejections(env, tx_id).len();
+    let remaining = treasury.signers.len().saturating_sub(rejections);
+    if remaining < required_approvals_for_tx(&treasury, &tx) {
+        tx.status = TransactionStatus::Rejected;
+    }
+    store_transaction(env, &tx);
+
+    let event = TransactionRejectedEvent {
+        treasury_id: tx.treasury_id,
+        tx_id,
+        signer,
+        reason,
+        status: tx.status,
+    };
+    emit_event(env, MOD_TREASURY, ACT_REJECTED, event);
+
+    true
+}
+
+/// Withdraw a pending transaction. Only its proposer may call.
+pub fn cancel_transaction(env: &Env, tx_id: u64, proposer: Address, reason: String) -> bool {
+    proposer.require_auth();
+
+    let mut tx = crate::treasury::storage::get_transaction(env, tx_id).expect("tx not found");
+    if tx.proposer != proposer {
+        panic!("only proposer can cancel");
+    }
+
+    expire_if_needed(&mut tx, env.ledger().timestamp());
+    if tx.status != TransactionStatus::Pending {
+        panic!("transaction not cancellable");
+    }
+
+    if dispute_storage::is_reference_locked(env, &DisputeReference::TreasuryTransaction, tx_id) {
+        panic!("transaction is in active dispute");
+    }
+
+    tx.status = TransactionStatus::Cancelled;
+    store_transaction(env, &tx);
+
+    let event = TransactionRejectedEvent {
+        treasury_id: tx.treasury_id,
+        tx_id,
+        signer: proposer,
+        reason,
+        status: TransactionStatus::Cancelled,
+    };
+    emit_event(env, MOD_TREASURY, ACT_CANCELLED, event);
+
+    true
+}
+
+fn has_rejected(env: &Env, tx_id: u64, signer: &Address) -> bool {
+    get_rejections(env, tx_id)
+        .iter()
+        .any(|r| r.signer == signer.clone())
+}
+
+/// Charge `amount` to a category's budget and every ancestor budget above it.
+///
+/// Nothing is charged unless every budget in the chain accepts the spend. A
+/// soft budget accepts an overspend only when `overspend_approved` is set.
+pub fn enforce_budget(
     env: &Env,
     treasury_id: u64,
     category: &String,
     amount: i128,
+    overspend_approved: bool,
 ) -> Result<(), TreasuryError> {
     if amount <= 0 {
         return Ok(());
@@ -247,24 +408,69 @@ fn enforce_budget(
         spent_amount: 0,
         period_seconds: 0,
         period_start: now,
+        carried_over: 0,
+        carry_over_cap: 0,
+        parent: None,
+        mode: BudgetMode::Hard,
     });
 
-    if budget.period_seconds > 0 && now >= budget.period_start.saturating_add(budget.period_seconds)
-    {
-        budget.period_start = now;
-        budget.spent_amount = 0;
-    }
+    let mut chain = Vec::new(env);
+    loop {
+        if budget.period_seconds > 0 {
+            roll_budget_period(&mut budget, now);
+        }
+
+        let limit = budget.allocated_amount + budget.carried_over;
+        if budget.allocated_amount > 0 && budget.spent_amount + amount > limit {
+            match budget.mode {
+                BudgetMode::Hard => return Err(TreasuryError::BudgetExceeded),
+                BudgetMode::Soft if !overspend_approved => {
+                    return Err(TreasuryError::BudgetOverspendNotApproved)
+                }
+                BudgetMode::Soft => {}
+            }
+        }
 
-    if budget.allocated_amount > 0 && budget.spent_amount + amount > budget.allocated_amount {
-        return Err(TreasuryError::BudgetExceeded);
+        let parent = budget.parent.clone();
+        chain.push_back(budget);
+        match parent.and_then(|p| get_budget(env, treasury_id, &p)) {
+            Some(next) => budget = next,
+            None => break,
+        }
     }
 
-    budget.spent_amount += amount;
-    store_budget(env, &budget);
+    for mut budget in chain.iter() {
+        budget.spent_amount += amount;
+        store_budget(env, &budget);
+    }
     Ok(())
 }
 
-fn enforce_allowance(
+/// Start a new period once the current one has ended, carrying unused
+/// allocation forward up to the budget's cap. Periods that passed without
+/// any spend count as fully unused.
+fn roll_budget_period(budget: &mut Budget, now: u64) {
+    let elapsed = now.saturating_sub(budget.period_start);
+    // a zero-length period ends immediately
+    let periods = elapsed.checked_div(budget.period_seconds).unwrap_or(1);
+    if periods == 0 {
+        return;
+    }
+
+    budget.carried_over = if budget.carry_over_cap > 0 && budget.allocated_amount > 0 {
+        let unused = (budget.allocated_amount + budget.carried_over - budget.spent_amount).max(0);
+        let idle = budget
+            .allocated_amount
+            .saturating_mul((periods - 1) as i128);
+        unused.saturating_add(idle).min(budget.carry_over_cap)
+    } else {
+        0
+    };
+    budget.period_start = now;
+    budget.spent_amount = 0;
+}
+
+pub fn enforce_allowance(
     env: &Env,
     treasury_id: u64,
     admin: &Address,
@@ -308,11 +514,18 @@ pub fn execute_transaction(env: &Env, tx_id: u64, executor: Address) -> bool {
     expire_if_needed(&mut tx, now);
     if matches!(
         tx.status,
-        TransactionStatus::Rejected | TransactionStatus::Executed | TransactionStatus::Expired
+        TransactionStatus::Rejected
+            | TransactionStatus::Executed
+            | TransactionStatus::Expired
+            | TransactionStatus::Cancelled
     ) {
         panic!("transaction not executable");
     }
 
+    if dispute_storage::is_reference_locked(env, &DisputeReference::TreasuryTransaction, tx_id) {
+        panic!("transaction is in active dispute");
+    }
+
     // when paused, only already-approved transactions may be executed
     if treasury.paused && !matches!(tx.status, TransactionStatus::Approved) {
         panic!("treasury is paused");
@@ -330,6 +543,18 @@ pub fn execute_transaction(env: &Env, tx_id: u64, executor: Address) -> bool {
         | TransactionType::MilestonePayment => {
             let recipient = tx.recipient.clone().expect("recipient required");
 
+            let decision = evaluate_velocity(
+                env,
+                tx.treasury_id,
+                &tx.proposer,
+                &recipient,
+                tx.amount,
+                &tx.token,
+            );
+            if !recheck_velocity(env, &treasury, &mut tx, decision) {
+                return false;
+            }
+
             // budget category name from tx_type
             let category = match tx.tx_type {
                 TransactionType::Withdrawal => String::from_str(env, "withdrawal"),
@@ -341,9 +566,19 @@ pub fn execute_transaction(env: &Env, tx_id: u64, executor: Address) -> bool {
             // Convert Result to panic with expected error message
             // This creates a proper contract error (all panics in Soroban become contract errors)
             // while maintaining the expected error message for test compatibility
-            enforce_budget(env, tx.treasury_id, &category, tx.amount).unwrap_or_else(|e| match e {
+            enforce_budget(
+                env,
+                tx.treasury_id,
+                &category,
+                tx.amount,
+                has_all_signer_approvals(&treasury, &tx),
+            )
+            .unwrap_or_else(|e| match e {
                 TreasuryError::BudgetExceeded => panic!("budget exceeded"),
                 TreasuryError::AllowanceExceeded => panic!("allowance exceeded"),
+                TreasuryError::BudgetOverspendNotApproved => {
+                    panic!("budget overspend requires approval from all signers")
+                }
             });
 
             let op_type = match tx.tx_type {
@@ -368,8 +603,12 @@ pub fn execute_transaction(env: &Env, tx_id: u64, executor: Address) -> bool {
             .unwrap_or_else(|e| match e {
                 TreasuryError::BudgetExceeded => panic!("budget exceeded"),
                 TreasuryError::AllowanceExceeded => panic!("allowance exceeded"),
+                TreasuryError::BudgetOverspendNotApproved => {
+                    panic!("budget overspend requires approval from all signers")
+                }
             });
 
+            ensure_uncommitted(env, &treasury, &tx.token, tx.amount);
             match tx.token {
                 Some(ref token_addr) => {
                     let client = TokenClient::new(env, token_addr);
@@ -394,6 +633,14 @@ pub fn execute_transaction(env: &Env, tx_id: u64, executor: Address) -> bool {
 
             treasury.total_withdrawals += tx.amount;
             store_treasury(env, &treasury);
+            record_velocity_spend(
+                env,
+                tx.treasury_id,
+                &tx.proposer,
+                &recipient,
+                tx.amount,
+                &tx.token,
+            );
         }
         TransactionType::Deposit => {
             panic!("cannot execute deposit transaction");
@@ -401,6 +648,29 @@ pub fn execute_transaction(env: &Env, tx_id: u64, executor: Address) -> bool {
         TransactionType::AllowanceGrant => {
             // state-only; execution path not used in this simplified version
         }
+        TransactionType::Swap => {
+            execute_swap_transaction(env, &mut treasury, &tx);
+            store_treasury(env, &treasury);
+        }
+        TransactionType::SwapperChange => {
+            execute_swapper_change(env, &tx);
+        }
+        TransactionType::VaultDeposit => {
+            execute_vault_deposit(env, &mut treasury, &tx);
+            store_treasury(env, &treasury);
+        }
+        TransactionType::VaultWithdrawal => {
+            execute_vault_withdrawal(env, &mut treasury, &tx);
+            store_treasury(env, &treasury);
+        }
+        TransactionType::BatchWithdrawal => {
+            let decision = evaluate_batch_velocity(env, &tx);
+            if !recheck_velocity(env, &treasury, &mut tx, decision) {
+                return false;
+            }
+            execute_batch_withdrawal(env, &mut treasury, &tx, &executor);
+            store_treasury(env, &treasury);
+        }
     }
 
     tx.status = TransactionStatus::Executed;
@@ -419,13 +689,137 @@ pub fn execute_transaction(env: &Env, tx_id: u64, executor: Address) -> bool {
     true
 }
 
+/// Apply a velocity decision re-evaluated against outflows executed since the
+/// proposal. A decision is only ever tightened, never relaxed. Returns `false`
+/// if the transaction must not execute now.
+fn recheck_velocity(
+    env: &Env,
+    treasury: &Treasury,
+    tx: &mut Transaction,
+    decision: VelocityDecision,
+) -> bool {
+    match decision {
+        VelocityDecision::Blocked => {
+            tx.status = TransactionStatus::Rejected;
+            tx.velocity_decision = VelocityDecision::Blocked;
+            store_transaction(env, tx);
+            false
+        }
+        VelocityDecision::Escalated => {
+            tx.velocity_decision = VelocityDecision::Escalated;
+            if tx.approvals.len() < required_approvals_for_tx(treasury, tx) {
+                tx.status = TransactionStatus::Pending;
+                store_transaction(env, tx);
+                return false;
+            }
+            true
+        }
+        VelocityDecision::WithinLimits => true,
+    }
+}
+
+/// Reject a transaction after a dispute ruled against it. The dispute is
+/// recorded as a rejection by the disputant; the proposal's own reason is kept.
+pub fn reject_disputed_transaction(env: &Env, tx_id: u64, disputant: Address, reason: String) {
+    let mut tx = crate::treasury::storage::get_transaction(env, tx_id).expect("tx not found");
+
+    add_rejection(
+        env,
+        tx_id,
+        &TransactionRejection {
+            signer: disputant.clone(),
+            reason: reason.clone(),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    tx.status = TransactionStatus::Rejected;
+    store_transaction(env, &tx);
+
+    let event = TransactionRejectedEvent {
+        treasury_id: tx.treasury_id,
+        tx_id,
+        signer: disputant,
+        reason,
+        status: tx.status,
+    };
+    emit_event(env, MOD_TREASURY, ACT_REJECTED, event);
+}
+
+/// Give a disputed transaction a fresh expiry window once its dispute is
+/// closed without blocking it, so time spent in dispute does not expire it.
+pub fn reopen_disputed_transaction(env: &Env, tx_id: u64) {
+    let mut tx = crate::treasury::storage::get_transaction(env, tx_id).expect("tx not found");
+    if matches!(
+        tx.status,
+        TransactionStatus::Pending | TransactionStatus::Approved
+    ) {
+        tx.expires_at = env.ledger().timestamp() + TX_EXPIRY_SECONDS;
+        store_transaction(env, &tx);
+    }
+}
+
+/// Pay a milestone from the treasury. `overspend_approved` lets the payment
+/// run past a soft milestone budget, as when every signer approved the
+/// project's funding.
 pub fn execute_milestone_payment(
     env: &Env,
     treasury_id: u64,
     token: Option<Address>,
     recipient: Address,
     amount: i128,
+    overspend_approved: bool,
 ) -> bool {
+    let treasury = charge_milestone_payment(env, treasury_id, &token, amount, overspend_approved);
+
+    // Move funds from treasury to recipient
+    if let Some(ref token_addr) = token {
+        let client = TokenClient::new(env, token_addr);
+        client.transfer(&env.current_contract_address(), &recipient, &amount);
+    }
+    store_treasury(env, &treasury);
+
+    record_milestone_payment(env, treasury_id, token, recipient, amount);
+
+    true
+}
+
+/// Pay a milestone from `conversion.source_token` held by the treasury,
+/// converting `amount` of it into `conversion.target_token` for the recipient.
+/// Returns the target amount the recipient received.
+pub fn execute_converted_milestone_payment(
+    env: &Env,
+    treasury_id: u64,
+    conversion: &PayoutConversion,
+    recipient: Address,
+    amount: i128,
+    overspend_approved: bool,
+) -> i128 {
+    let source = Some(conversion.source_token.clone());
+    let treasury = charge_milestone_payment(env, treasury_id, &source, amount, overspend_approved);
+    store_treasury(env, &treasury);
+
+    let amount_out = convert_payout(env, conversion, amount);
+    TokenClient::new(env, &conversion.target_token).transfer(
+        &env.current_contract_address(),
+        &recipient,
+        &amount_out,
+    );
+
+    record_milestone_payment(env, treasury_id, source, recipient, amount);
+
+    amount_out
+}
+
+/// Enforce the milestone budget and allowance and debit `amount` from the
+/// treasury's accounting. The caller moves the funds and stores the treasury.
+fn charge_milestone_payment(
+    env: &Env,
+    treasury_id: u64,
+    token: &Option<Address>,
+    amount: i128,
+    overspend_approved: bool,
+) -> Treasury {
     if amount <= 0 {
         panic!("amount must be positive");
     }
@@ -437,27 +831,33 @@ pub fn execute_milestone_payment(
 
     // Budget enforcement under the "milestone" category
     let category = String::from_str(env, "milestone");
-    enforce_budget(env, treasury_id, &category, amount).unwrap_or_else(|e| match e {
-        TreasuryError::BudgetExceeded => panic!("budget exceeded"),
-        TreasuryError::AllowanceExceeded => panic!("allowance exceeded"),
+    enforce_budget(env, treasury_id, &category, amount, overspend_approved).unwrap_or_else(|e| {
+        match e {
+            TreasuryError::BudgetExceeded => panic!("budget exceeded"),
+            TreasuryError::AllowanceExceeded => panic!("allowance exceeded"),
+            TreasuryError::BudgetOverspendNotApproved => {
+                panic!("budget overspend requires approval from all signers")
+            }
+        }
     });
 
     // Allowance enforcement (if any) keyed by current contract address;
     // if no allowance exists this is a no-op.
     let executor = env.current_contract_address();
     let op_type = crate::allowance::AllowanceOperation::MilestonePayment;
-    enforce_allowance(env, treasury_id, &executor, &token, amount, &op_type).unwrap_or_else(|e| {
+    enforce_allowance(env, treasury_id, &executor, token, amount, &op_type).unwrap_or_else(|e| {
         match e {
             TreasuryError::BudgetExceeded => panic!("budget exceeded"),
             TreasuryError::AllowanceExceeded => panic!("allowance exceeded"),
+            TreasuryError::BudgetOverspendNotApproved => {
+                panic!("budget overspend requires approval from all signers")
+            }
         }
     });
 
-    // Move funds from treasury to recipient
+    ensure_uncommitted(env, &treasury, token, amount);
     match token {
         Some(ref token_addr) => {
-            let client = TokenClient::new(env, token_addr);
-
             let mut balances = treasury.token_balances.clone();
             let current = balances.get(token_addr.clone()).unwrap_or(0i128);
             if current < amount {
@@ -465,8 +865,6 @@ pub fn execute_milestone_payment(
             }
             balances.set(token_addr.clone(), current - amount);
             treasury.token_balances = balances;
-
-            client.transfer(&env.current_contract_address(), &recipient, &amount);
         }
         None => {
             if treasury.balance_xlm < amount {
@@ -477,9 +875,17 @@ pub fn execute_milestone_payment(
     }
 
     treasury.total_withdrawals += amount;
-    store_treasury(env, &treasury);
+    treasury
+}
 
-    // Record a MilestonePayment transaction as already executed
+/// Record a MilestonePayment transaction as already executed
+fn record_milestone_payment(
+    env: &Env,
+    treasury_id: u64,
+    token: Option<Address>,
+    recipient: Address,
+    amount: i128,
+) {
     let tx_id = get_next_tx_id(env);
     let now = env.ledger().timestamp();
     let tx = Transaction {
@@ -489,19 +895,18 @@ pub fn execute_milestone_payment(
         amount,
         token,
         recipient: Some(recipient),
-        proposer: executor,
+        proposer: env.current_contract_address(),
         approvals: Vec::new(env),
         status: TransactionStatus::Executed,
         created_at: now,
         expires_at: now,
         reason: String::from_str(env, "milestone_payment"),
+        velocity_decision: VelocityDecision::WithinLimits,
     };
     store_transaction(env, &tx);
 
     let event = TransactionExecutedEvent { treasury_id, tx_id };
     emit_event(env, MOD_TREASURY, ACT_EXECUTED, event);
-
-    true
 }
 
 pub fn set_budget(
@@ -523,6 +928,10 @@ pub fn set_budget(
         spent_amount: 0,
         period_seconds,
         period_start: now,
+        carried_over: 0,
+        carry_over_cap: 0,
+        parent: None,
+        mode: BudgetMode::Hard,
     });
 
     if budget.period_seconds != period_seconds {
@@ -530,8 +939,7 @@ pub fn set_budget(
     }
 
     if now >= budget.period_start.saturating_add(budget.period_seconds) {
-        budget.period_start = now;
-        budget.spent_amount = 0;
+        roll_budget_period(&mut budget, now);
     }
 
     budget.allocated_amount = amount;
@@ -548,6 +956,64 @@ pub fn set_budget(
     true
 }
 
+/// Set carry-over, parent category and mode for an existing budget.
+///
+/// A `carry_over_cap` of 0 disables carry-over. The parent must already have
+/// a budget and may not be the category itself or one of its descendants.
+pub fn configure_budget(
+    env: &Env,
+    treasury_id: u64,
+    caller: Address,
+    category: String,
+    carry_over_cap: i128,
+    parent: Option<String>,
+    mode: BudgetMode,
+) -> bool {
+    let treasury = get_treasury(env, treasury_id).expect("treasury not found");
+    assert_signer(env, &treasury, &caller);
+
+    let mut budget = get_budget(env, treasury_id, &category).expect("budget not found");
+    if carry_over_cap < 0 {
+        panic!("carry-over cap cannot be negative");
+    }
+
+    if let Some(ref parent_category) = parent {
+        let mut ancestor = Some(parent_category.clone());
+        let mut depth = 0u32;
+        while let Some(current) = ancestor {
+            if current == category {
+                panic!("budget hierarchy cycle");
+            }
+            depth += 1;
+            if depth > MAX_BUDGET_DEPTH {
+                panic!("budget hierarchy too deep");
+            }
+            ancestor = get_budget(env, treasury_id, &current)
+                .expect("parent budget not found")
+                .parent;
+        }
+    }
+
+    budget.carry_over_cap = carry_over_cap;
+    if budget.carried_over > carry_over_cap {
+        budget.carried_over = carry_over_cap;
+    }
+    budget.parent = parent.clone();
+    budget.mode = mode.clone();
+    store_budget(env, &budget);
+
+    let event = BudgetConfiguredEvent {
+        treasury_id,
+        category,
+        carry_over_cap,
+        parent,
+        mode,
+    };
+    emit_event(env, MOD_TREASURY, ACT_UPDATED, event);
+
+    true
+}
+
 pub fn get_balance(env: &Env, treasury_id: u64, token: Option<Address>) -> i128 {
     let treasury = get_treasury(env, treasury_id).expect("treasury not found");
     match token {
@@ -645,7 +1111,7 @@ pub fn emergency_pause(env: &Env, treasury_id: u64, signer: Address, paused: boo
 }
 
 /// Record a point-in-time treasury snapshot for analytics tracking.
-fn record_snapshot(env: &Env, treasury: &Treasury) {
+pub fn record_snapshot(env: &Env, treasury: &Treasury) {
     use crate::analytics::storage::get_snapshot_count;
 
     let index = get_snapshot_count(env, treasury.id);
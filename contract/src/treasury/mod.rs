@@ -1,13 +1,30 @@
-﻿pub mod management;
+﻿pub mod batch;
+pub mod commitment;
+pub mod management;
 pub mod multisig;
+pub mod schedule;
 pub mod storage;
+pub mod swap;
 pub mod types;
+pub mod vault;
+pub mod velocity;
 
+pub use batch::propose_batch_withdrawal;
 pub use management::{
-    approve_transaction, deposit, emergency_pause, execute_milestone_payment, execute_transaction,
+    approve_transaction, cancel_transaction, configure_budget, deposit, emergency_pause,
+    execute_converted_milestone_payment, execute_milestone_payment, execute_transaction,
     get_balance, get_transaction_history, grant_allowance, initialize_treasury, propose_withdrawal,
-    set_budget,
+    reject_transaction, set_budget,
 };
+pub use schedule::{
+    approve_scheduled_payment, cancel_scheduled_payment, process_scheduled_payments,
+    propose_scheduled_payment,
+};
+pub use swap::{propose_swap, propose_swapper_change};
+pub use vault::{
+    get_balance_detail, propose_vault_deposit, propose_vault_withdrawal, set_vault_allocation,
+};
+pub use velocity::set_velocity_limit;
 
 #[allow(unused_imports)]
 pub use storage::initialize_treasury_storage;
@@ -1,6 +1,8 @@
 ﻿use soroban_sdk::{Address, Env};
 
-use crate::treasury::types::{Transaction, TransactionStatus, TransactionType, Treasury};
+use crate::treasury::types::{
+    Transaction, TransactionStatus, TransactionType, Treasury, VelocityDecision,
+};
 
 pub const TX_EXPIRY_SECONDS: u64 = 60 * 60 * 24 * 7; // 7 days
 
@@ -44,10 +46,16 @@ pub fn add_approval(tx: &mut Transaction, addr: &Address) {
 }
 
 pub fn required_approvals_for_tx(treasury: &Treasury, tx: &Transaction) -> u32 {
+    // withdrawals over an escalating velocity limit need every signer
+    if tx.velocity_decision == VelocityDecision::Escalated {
+        return treasury.signers.len();
+    }
     match tx.tx_type {
         TransactionType::Withdrawal
+        | TransactionType::BatchWithdrawal
         | TransactionType::BountyFunding
-        | TransactionType::MilestonePayment => {
+        | TransactionType::MilestonePayment
+        | TransactionType::Swap => {
             if tx.amount >= treasury.high_value_threshold {
                 treasury.approval_threshold
             } else {
@@ -55,10 +63,20 @@ pub fn required_approvals_for_tx(treasury: &Treasury, tx: &Transaction) -> u32 {
                 1u32.min(treasury.approval_threshold)
             }
         }
+        // moving funds in or out of yield vaults, or changing the swapper that
+        // trades them, always needs the full threshold
+        TransactionType::VaultDeposit
+        | TransactionType::VaultWithdrawal
+        | TransactionType::SwapperChange => treasury.approval_threshold,
         _ => 1,
     }
 }
 
+/// Whether every signer has approved, as a soft budget overspend requires.
+pub fn has_all_signer_approvals(treasury: &Treasury, tx: &Transaction) -> bool {
+    treasury.signers.iter().all(|s| has_approved(tx, &s))
+}
+
 pub fn is_expired(tx: &Transaction, now: u64) -> bool {
     now >= tx.expires_at
 }
@@ -0,0 +1,318 @@
+use soroban_sdk::{token::Client as TokenClient, Address, Env, String, Vec};
+
+use crate::events::emit::emit_event;
+use crate::events::topics::{
+    ACT_APPROVED, ACT_CANCELLED, ACT_COMPLETED, ACT_EXECUTED, ACT_FAILED, ACT_PROPOSED,
+    MOD_TREASURY,
+};
+use crate::treasury::commitment::available_balance;
+use crate::treasury::management::{enforce_budget, record_snapshot};
+use crate::treasury::multisig::assert_signer;
+use crate::treasury::storage::{
+    add_active_schedule, get_active_schedules, get_next_schedule_id, get_next_tx_id, get_schedule,
+    get_treasury, remove_active_schedule, store_schedule, store_transaction, store_treasury,
+};
+use crate::treasury::types::{
+    ScheduleStatus, ScheduleStatusEvent, ScheduleTerms, ScheduledPayment, ScheduledPaymentEvent,
+    ScheduledPaymentFailedEvent, Transaction, TransactionStatus, TransactionType, VelocityDecision,
+};
+use crate::treasury::velocity::{evaluate_velocity, record_velocity_spend};
+
+/// Propose a recurring payment from a treasury. The proposer's approval is
+/// counted; the schedule becomes active once the treasury's approval
+/// threshold is met.
+pub fn propose_scheduled_payment(
+    env: &Env,
+    treasury_id: u64,
+    proposer: Address,
+    terms: ScheduleTerms,
+) -> u64 {
+    let treasury = get_treasury(env, treasury_id).expect("treasury not found");
+    if treasury.paused {
+        panic!("treasury is paused");
+    }
+    assert_signer(env, &treasury, &proposer);
+
+    if terms.amount <= 0 {
+        panic!("amount must be positive");
+    }
+    if terms.interval_seconds == 0 {
+        panic!("invalid interval");
+    }
+    if let Some(end_at) = terms.end_at {
+        if end_at < terms.start_at {
+            panic!("schedule ends before it starts");
+        }
+    }
+    if terms.max_occurrences == Some(0) {
+        panic!("invalid max occurrences");
+    }
+
+    let id = get_next_schedule_id(env);
+    let mut approvals = Vec::new(env);
+    approvals.push_back(proposer.clone());
+
+    let mut schedule = ScheduledPayment {
+        id,
+        treasury_id,
+        next_due: terms.start_at,
+        terms,
+        proposer,
+        approvals,
+        status: ScheduleStatus::Pending,
+        occurrences: 0,
+        created_at: env.ledger().timestamp(),
+    };
+    activate_if_approved(env, &mut schedule, treasury.approval_threshold);
+    store_schedule(env, &schedule);
+
+    let event = ScheduleStatusEvent {
+        treasury_id,
+        schedule_id: id,
+        status: schedule.status.clone(),
+    };
+    emit_event(env, MOD_TREASURY, ACT_PROPOSED, event);
+
+    id
+}
+
+/// Approve a pending schedule. Once approved by the treasury's threshold it
+/// runs without further signatures. An active schedule still takes approvals,
+/// which its payments need once a velocity limit escalates them.
+pub fn approve_scheduled_payment(env: &Env, schedule_id: u64, approver: Address) -> bool {
+    let mut schedule = get_schedule(env, schedule_id).expect("schedule not found");
+    if !matches!(
+        schedule.status,
+        ScheduleStatus::Pending | ScheduleStatus::Active
+    ) {
+        panic!("schedule not approvable");
+    }
+
+    let treasury = get_treasury(env, schedule.treasury_id).expect("treasury not found");
+    assert_signer(env, &treasury, &approver);
+    if has_approved_schedule(&schedule, &approver) {
+        panic!("duplicate approval");
+    }
+    schedule.approvals.push_back(approver.clone());
+
+    activate_if_approved(env, &mut schedule, treasury.approval_threshold);
+    store_schedule(env, &schedule);
+
+    let event = ScheduleStatusEvent {
+        treasury_id: schedule.treasury_id,
+        schedule_id,
+        status: schedule.status,
+    };
+    emit_event(env, MOD_TREASURY, ACT_APPROVED, event);
+
+    true
+}
+
+/// Cancel a pending or active schedule. Any treasury signer may stop one.
+pub fn cancel_scheduled_payment(env: &Env, schedule_id: u64, signer: Address) -> bool {
+    let mut schedule = get_schedule(env, schedule_id).expect("schedule not found");
+    if !matches!(
+        schedule.status,
+        ScheduleStatus::Pending | ScheduleStatus::Active
+    ) {
+        panic!("schedule not cancellable");
+    }
+
+    let treasury = get_treasury(env, schedule.treasury_id).expect("treasury not found");
+    assert_signer(env, &treasury, &signer);
+
+    schedule.status = ScheduleStatus::Cancelled;
+    store_schedule(env, &schedule);
+    remove_active_schedule(env, schedule_id);
+
+    let event = ScheduleStatusEvent {
+        treasury_id: schedule.treasury_id,
+        schedule_id,
+        status: ScheduleStatus::Cancelled,
+    };
+    emit_event(env, MOD_TREASURY, ACT_CANCELLED, event);
+
+    true
+}
+
+/// Pay up to `max_count` due schedules. Callable by anyone.
+///
+/// Each due schedule pays at most one occurrence per call. Schedules of a
+/// paused treasury are left untouched; a payment that would exceed its budget
+/// category or the treasury balance is skipped and retried on a later call.
+/// Payments are checked against the treasury's velocity limits like any
+/// withdrawal: a blocked payment is skipped, and an escalated one is held
+/// unless every signer approved the schedule. Returns the number of payments
+/// made.
+pub fn process_scheduled_payments(env: &Env, max_count: u32) -> u32 {
+    let now = env.ledger().timestamp();
+    let mut processed = 0u32;
+    let mut paid = 0u32;
+
+    for schedule_id in get_active_schedules(env).iter() {
+        if processed >= max_count {
+            break;
+        }
+        let mut schedule = match get_schedule(env, schedule_id) {
+            Some(s) => s,
+            None => continue,
+        };
+        if schedule.next_due > now {
+            continue;
+        }
+        if let Some(end_at) = schedule.terms.end_at {
+            if schedule.next_due > end_at {
+                complete(env, &mut schedule);
+                continue;
+            }
+        }
+
+        let mut treasury = match get_treasury(env, schedule.treasury_id) {
+            Some(t) => t,
+            None => continue,
+        };
+        if treasury.paused {
+            continue;
+        }
+        processed += 1;
+
+        let terms = schedule.terms.clone();
+        if available_balance(env, &treasury, &terms.token) < terms.amount {
+            payment_failed(env, &schedule, "insufficient treasury balance");
+            continue;
+        }
+        if enforce_budget(env, treasury.id, &terms.category, terms.amount, false).is_err() {
+            payment_failed(env, &schedule, "budget exceeded");
+            continue;
+        }
+        let velocity_decision = evaluate_velocity(
+            env,
+            treasury.id,
+            &schedule.proposer,
+            &terms.recipient,
+            terms.amount,
+            &terms.token,
+        );
+        match velocity_decision {
+            VelocityDecision::Blocked => {
+                payment_failed(env, &schedule, "velocity limit exceeded");
+                continue;
+            }
+            VelocityDecision::Escalated => {
+                let all_approved = treasury
+                    .signers
+                    .iter()
+                    .all(|s| has_approved_schedule(&schedule, &s));
+                if !all_approved {
+                    payment_failed(env, &schedule, "velocity limit requires all signers");
+                    continue;
+                }
+            }
+            VelocityDecision::WithinLimits => {}
+        }
+
+        match terms.token {
+            Some(ref token_addr) => {
+                let current = treasury.token_balances.get(token_addr.clone()).unwrap_or(0);
+                treasury
+                    .token_balances
+                    .set(token_addr.clone(), current - terms.amount);
+                TokenClient::new(env, token_addr).transfer(
+                    &env.current_contract_address(),
+                    &terms.recipient,
+                    &terms.amount,
+                );
+            }
+            None => {
+                treasury.balance_xlm -= terms.amount;
+            }
+        }
+        treasury.total_withdrawals += terms.amount;
+        store_treasury(env, &treasury);
+        record_velocity_spend(
+            env,
+            treasury.id,
+            &schedule.proposer,
+            &terms.recipient,
+            terms.amount,
+            &terms.token,
+        );
+        record_snapshot(env, &treasury);
+
+        let tx_id = get_next_tx_id(env);
+        let tx = Transaction {
+            id: tx_id,
+            treasury_id: treasury.id,
+            tx_type: TransactionType::Withdrawal,
+            amount: terms.amount,
+            token: terms.token.clone(),
+            recipient: Some(terms.recipient.clone()),
+            proposer: schedule.proposer.clone(),
+            approvals: schedule.approvals.clone(),
+            status: TransactionStatus::Executed,
+            created_at: now,
+            expires_at: now,
+            reason: String::from_str(env, "scheduled_payment"),
+            velocity_decision,
+        };
+        store_transaction(env, &tx);
+
+        schedule.occurrences += 1;
+        schedule.next_due = schedule.next_due.saturating_add(terms.interval_seconds);
+        paid += 1;
+
+        let event = ScheduledPaymentEvent {
+            treasury_id: treasury.id,
+            schedule_id,
+            tx_id,
+            occurrence: schedule.occurrences,
+            amount: terms.amount,
+        };
+        emit_event(env, MOD_TREASURY, ACT_EXECUTED, event);
+
+        let exhausted = terms
+            .max_occurrences
+            .is_some_and(|max| schedule.occurrences >= max);
+        let ended = terms.end_at.is_some_and(|end| schedule.next_due > end);
+        if exhausted || ended {
+            complete(env, &mut schedule);
+        } else {
+            store_schedule(env, &schedule);
+        }
+    }
+
+    paid
+}
+
+fn has_approved_schedule(schedule: &ScheduledPayment, addr: &Address) -> bool {
+    schedule.approvals.iter().any(|a| a == addr.clone())
+}
+
+fn activate_if_approved(env: &Env, schedule: &mut ScheduledPayment, threshold: u32) {
+    if schedule.status == ScheduleStatus::Pending && schedule.approvals.len() >= threshold {
+        schedule.status = ScheduleStatus::Active;
+        add_active_schedule(env, schedule.id);
+    }
+}
+
+fn complete(env: &Env, schedule: &mut ScheduledPayment) {
+    schedule.status = ScheduleStatus::Completed;
+    store_schedule(env, schedule);
+    remove_active_schedule(env, schedule.id);
+
+    let event = ScheduleStatusEvent {
+        treasury_id: schedule.treasury_id,
+        schedule_id: schedule.id,
+        status: ScheduleStatus::Completed,
+    };
+    emit_event(env, MOD_TREASURY, ACT_COMPLETED, event);
+}
+
+fn payment_failed(env: &Env, schedule: &ScheduledPayment, reason: &str) {
+    let event = ScheduledPaymentFailedEvent {
+        treasury_id: schedule.treasury_id,
+        schedule_id: schedule.id,
+        reason: String::from_str(env, reason),
+    };
+    emit_event(env, MOD_TREASURY, ACT_FAILED, event);
+}
@@ -1,6 +1,9 @@
 ﻿use soroban_sdk::{symbol_short, Address, Env, Map, String, Symbol, Vec};
 
-use crate::treasury::types::{Allowance, Budget, Transaction, Treasury};
+use crate::treasury::types::{
+    Allowance, BatchWithdrawal, Budget, ScheduledPayment, SwapOrder, Transaction,
+    TransactionRejection, Treasury, VaultAllocation, VelocityLimit, VelocityScope, VelocitySpend,
+};
 
 const TREASURY_CNT_KEY: Symbol = symbol_short!("t_cnt");
 const TREASURIES_KEY: Symbol = symbol_short!("trsries");
@@ -8,10 +11,26 @@ const TREASURIES_KEY: Symbol = symbol_short!("trsries");
 const TX_CNT_KEY: Symbol = symbol_short!("tx_cnt");
 const TRANSACTIONS_KEY: Symbol = symbol_short!("txs");
 const TREASURY_TX_INDEX_KEY: Symbol = symbol_short!("t_tx_idx");
+const REJECTIONS_KEY: Symbol = symbol_short!("t_rejects");
 
 const BUDGETS_KEY: Symbol = symbol_short!("budgets");
 const ALLOWANCES_KEY: Symbol = symbol_short!("allows");
 
+const VELOCITY_LIMITS_KEY: Symbol = symbol_short!("t_vlim");
+const VELOCITY_LOG_KEY: Symbol = symbol_short!("t_vlog");
+
+const SCHEDULE_CNT_KEY: Symbol = symbol_short!("t_sch_cnt");
+const SCHEDULES_KEY: Symbol = symbol_short!("t_sched");
+const ACTIVE_SCHEDULES_KEY: Symbol = symbol_short!("t_sch_act");
+
+const SWAPPERS_KEY: Symbol = symbol_short!("t_swapper");
+const SWAP_ORDERS_KEY: Symbol = symbol_short!("t_swaps");
+const BATCHES_KEY: Symbol = symbol_short!("t_batches");
+const COMMITTED_KEY: Symbol = symbol_short!("t_commit");
+
+const VAULTS_KEY: Symbol = symbol_short!("t_vaults");
+const VAULT_TOTALS_KEY: Symbol = symbol_short!("t_vtot");
+
 #[allow(dead_code)]
 pub fn initialize_treasury_storage(env: &Env) {
     let storage = env.storage().persistent();
@@ -197,3 +216,301 @@ pub fn list_budgets_for_treasury(env: &Env, treasury_id: u64) -> Vec<Budget> {
     }
     result
 }
+
+pub fn store_velocity_limit(env: &Env, limit: &VelocityLimit) {
+    let mut limits: Map<(u64, VelocityScope, Option<Address>), VelocityLimit> = env
+        .storage()
+        .persistent()
+        .get(&VELOCITY_LIMITS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    limits.set(
+        (limit.treasury_id, limit.scope.clone(), limit.token.clone()),
+        limit.clone(),
+    );
+    env.storage()
+        .persistent()
+        .set(&VELOCITY_LIMITS_KEY, &limits);
+}
+
+pub fn remove_velocity_limit(
+    env: &Env,
+    treasury_id: u64,
+    scope: &VelocityScope,
+    token: &Option<Address>,
+) {
+    let mut limits: Map<(u64, VelocityScope, Option<Address>), VelocityLimit> = env
+        .storage()
+        .persistent()
+        .get(&VELOCITY_LIMITS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    limits.remove((treasury_id, scope.clone(), token.clone()));
+    env.storage()
+        .persistent()
+        .set(&VELOCITY_LIMITS_KEY, &limits);
+}
+
+pub fn get_velocity_limits(env: &Env, treasury_id: u64) -> Vec<VelocityLimit> {
+    let limits: Map<(u64, VelocityScope, Option<Address>), VelocityLimit> = env
+        .storage()
+        .persistent()
+        .get(&VELOCITY_LIMITS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    let mut result = Vec::new(env);
+    for ((t_id, _scope, _token), limit) in limits.iter() {
+        if t_id == treasury_id {
+            result.push_back(limit);
+        }
+    }
+    result
+}
+
+pub fn get_velocity_log(
+    env: &Env,
+    treasury_id: u64,
+    token: &Option<Address>,
+) -> Vec<VelocitySpend> {
+    let log: Map<(u64, Option<Address>), Vec<VelocitySpend>> = env
+        .storage()
+        .persistent()
+        .get(&VELOCITY_LOG_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    log.get((treasury_id, token.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn store_velocity_log(
+    env: &Env,
+    treasury_id: u64,
+    token: &Option<Address>,
+    entries: &Vec<VelocitySpend>,
+) {
+    let mut log: Map<(u64, Option<Address>), Vec<VelocitySpend>> = env
+        .storage()
+        .persistent()
+        .get(&VELOCITY_LOG_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    log.set((treasury_id, token.clone()), entries.clone());
+    env.storage().persistent().set(&VELOCITY_LOG_KEY, &log);
+}
+
+pub fn get_next_schedule_id(env: &Env) -> u64 {
+    let storage = env.storage().persistent();
+    let current: u64 = storage.get(&SCHEDULE_CNT_KEY).unwrap_or(0u64);
+    let next = current + 1;
+    storage.set(&SCHEDULE_CNT_KEY, &next);
+    next
+}
+
+pub fn store_schedule(env: &Env, schedule: &ScheduledPayment) {
+    let mut schedules: Map<u64, ScheduledPayment> = env
+        .storage()
+        .persistent()
+        .get(&SCHEDULES_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    schedules.set(schedule.id, schedule.clone());
+    env.storage().persistent().set(&SCHEDULES_KEY, &schedules);
+}
+
+pub fn get_schedule(env: &Env, schedule_id: u64) -> Option<ScheduledPayment> {
+    let schedules: Map<u64, ScheduledPayment> = env
+        .storage()
+        .persistent()
+        .get(&SCHEDULES_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    schedules.get(schedule_id)
+}
+
+/// IDs of approved schedules that may still fall due, oldest first.
+pub fn get_active_schedules(env: &Env) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&ACTIVE_SCHEDULES_KEY)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn add_active_schedule(env: &Env, schedule_id: u64) {
+    let mut ids = get_active_schedules(env);
+    if !ids.contains(schedule_id) {
+        ids.push_back(schedule_id);
+        env.storage().persistent().set(&ACTIVE_SCHEDULES_KEY, &ids);
+    }
+}
+
+pub fn remove_active_schedule(env: &Env, schedule_id: u64) {
+    let ids = get_active_schedules(env);
+    if let Some(index) = ids.first_index_of(schedule_id) {
+        let mut ids = ids;
+        ids.remove(index);
+        env.storage().persistent().set(&ACTIVE_SCHEDULES_KEY, &ids);
+    }
+}
+
+pub fn set_swapper(env: &Env, treasury_id: u64, swapper: &Address) {
+    let mut swappers: Map<u64, Address> = env
+        .storage()
+        .persistent()
+        .get(&SWAPPERS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    swappers.set(treasury_id, swapper.clone());
+    env.storage().persistent().set(&SWAPPERS_KEY, &swappers);
+}
+
+pub fn get_swapper(env: &Env, treasury_id: u64) -> Option<Address> {
+    let swappers: Map<u64, Address> = env
+        .storage()
+        .persistent()
+        .get(&SWAPPERS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    swappers.get(treasury_id)
+}
+
+pub fn store_swap_order(env: &Env, order: &SwapOrder) {
+    let mut orders: Map<u64, SwapOrder> = env
+        .storage()
+        .persistent()
+        .get(&SWAP_ORDERS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    orders.set(order.tx_id, order.clone());
+    env.storage().persistent().set(&SWAP_ORDERS_KEY, &orders);
+}
+
+pub fn get_swap_order(env: &Env, tx_id: u64) -> Option<SwapOrder> {
+    let orders: Map<u64, SwapOrder> = env
+        .storage()
+        .persistent()
+        .get(&SWAP_ORDERS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    orders.get(tx_id)
+}
+
+pub fn store_batch(env: &Env, batch: &BatchWithdrawal) {
+    let mut batches: Map<u64, BatchWithdrawal> = env
+        .storage()
+        .persistent()
+        .get(&BATCHES_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    batches.set(batch.tx_id, batch.clone());
+    env.storage().persistent().set(&BATCHES_KEY, &batches);
+}
+
+pub fn get_batch(env: &Env, tx_id: u64) -> Option<BatchWithdrawal> {
+    let batches: Map<u64, BatchWithdrawal> = env
+        .storage()
+        .persistent()
+        .get(&BATCHES_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    batches.get(tx_id)
+}
+
+pub fn store_vault_allocation(env: &Env, treasury_id: u64, allocation: &VaultAllocation) {
+    let mut all: Map<u64, Map<Address, VaultAllocation>> = env
+        .storage()
+        .persistent()
+        .get(&VAULTS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    let mut vaults = all.get(treasury_id).unwrap_or_else(|| Map::new(env));
+    vaults.set(allocation.vault.clone(), allocation.clone());
+    all.set(treasury_id, vaults);
+    env.storage().persistent().set(&VAULTS_KEY, &all);
+}
+
+pub fn get_vault_allocation(
+    env: &Env,
+    treasury_id: u64,
+    vault: &Address,
+) -> Option<VaultAllocation> {
+    get_vault_allocations(env, treasury_id).get(vault.clone())
+}
+
+pub fn get_vault_allocations(env: &Env, treasury_id: u64) -> Map<Address, VaultAllocation> {
+    let all: Map<u64, Map<Address, VaultAllocation>> = env
+        .storage()
+        .persistent()
+        .get(&VAULTS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    all.get(treasury_id).unwrap_or_else(|| Map::new(env))
+}
+
+/// Principal deployed to a vault by all treasuries of this contract. The vault
+/// sees them as a single depositor, so each treasury's share of the vault's
+/// value is its principal over this total.
+pub fn get_vault_total_principal(env: &Env, vault: &Address) -> i128 {
+    let totals: Map<Address, i128> = env
+        .storage()
+        .persistent()
+        .get(&VAULT_TOTALS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    totals.get(vault.clone()).unwrap_or(0)
+}
+
+pub fn set_vault_total_principal(env: &Env, vault: &Address, total: i128) {
+    let mut totals: Map<Address, i128> = env
+        .storage()
+        .persistent()
+        .get(&VAULT_TOTALS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    totals.set(vault.clone(), total);
+    env.storage().persistent().set(&VAULT_TOTALS_KEY, &totals);
+}
+
+pub fn add_rejection(env: &Env, tx_id: u64, rejection: &TransactionRejection) {
+    let mut all: Map<u64, Vec<TransactionRejection>> = env
+        .storage()
+        .persistent()
+        .get(&REJECTIONS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    let mut rejections = all.get(tx_id).unwrap_or_else(|| Vec::new(env));
+    rejections.push_back(rejection.clone());
+    all.set(tx_id, rejections);
+    env.storage().persistent().set(&REJECTIONS_KEY, &all);
+}
+
+pub fn get_rejections(env: &Env, tx_id: u64) -> Vec<TransactionRejection> {
+    let all: Map<u64, Vec<TransactionRejection>> = env
+        .storage()
+        .persistent()
+        .get(&REJECTIONS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    all.get(tx_id).unwrap_or_else(|| Vec::new(env))
+}
+
+/// Funds of a token reserved for milestone projects. `None` is XLM.
+pub fn get_committed(env: &Env, treasury_id: u64, token: &Option<Address>) -> i128 {
+    let committed: Map<(u64, Option<Address>), i128> = env
+        .storage()
+        .persistent()
+        .get(&COMMITTED_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    committed.get((treasury_id, token.clone())).unwrap_or(0)
+}
+
+pub fn set_committed(env: &Env, treasury_id: u64, token: &Option<Address>, amount: i128) {
+    let mut committed: Map<(u64, Option<Address>), i128> = env
+        .storage()
+        .persistent()
+        .get(&COMMITTED_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    committed.set((treasury_id, token.clone()), amount);
+    env.storage().persistent().set(&COMMITTED_KEY, &committed);
+}
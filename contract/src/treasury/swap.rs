@@ -0,0 +1,207 @@
+use soroban_sdk::{token::Client as TokenClient, Address, Env, String, Vec};
+
+use crate::events::emit::emit_event;
+use crate::events::topics::{ACT_EXECUTED, ACT_PROPOSED, ACT_UPDATED, MOD_TREASURY};
+use crate::interfaces::swapper::TokenSwapperClient;
+use crate::treasury::commitment::ensure_uncommitted;
+use crate::treasury::multisig::{assert_signer, TX_EXPIRY_SECONDS};
+use crate::treasury::storage::{
+    get_next_tx_id, get_swap_order, get_swapper, get_treasury, set_swapper, store_swap_order,
+    store_transaction,
+};
+use crate::treasury::types::{
+    SwapExecutedEvent, SwapOrder, SwapProposedEvent, SwapperChangeProposedEvent,
+    SwapperUpdatedEvent, Transaction, TransactionStatus, TransactionType, Treasury,
+    VelocityDecision,
+};
+
+/// Propose the swapper contract a treasury trades through. The change needs
+/// the treasury's full approval threshold and takes effect when executed.
+pub fn propose_swapper_change(
+    env: &Env,
+    treasury_id: u64,
+    proposer: Address,
+    swapper: Address,
+    reason: String,
+) -> u64 {
+    let treasury = get_treasury(env, treasury_id).expect("treasury not found");
+    if treasury.paused {
+        panic!("treasury is paused");
+    }
+    assert_signer(env, &treasury, &proposer);
+
+    let tx_id = get_next_tx_id(env);
+    let now = env.ledger().timestamp();
+    let mut approvals = Vec::new(env);
+    approvals.push_back(proposer.clone());
+
+    let tx = Transaction {
+        id: tx_id,
+        treasury_id,
+        tx_type: TransactionType::SwapperChange,
+        amount: 0,
+        token: None,
+        recipient: Some(swapper.clone()),
+        proposer: proposer.clone(),
+        approvals,
+        status: TransactionStatus::Pending,
+        created_at: now,
+        expires_at: now + TX_EXPIRY_SECONDS,
+        reason,
+        velocity_decision: VelocityDecision::WithinLimits,
+    };
+    store_transaction(env, &tx);
+
+    let event = SwapperChangeProposedEvent {
+        treasury_id,
+        tx_id,
+        proposer,
+        swapper,
+    };
+    emit_event(env, MOD_TREASURY, ACT_PROPOSED, event);
+
+    tx_id
+}
+
+/// Apply an approved swapper change. Swaps proposed earlier keep the swapper
+/// they were proposed with.
+pub fn execute_swapper_change(env: &Env, tx: &Transaction) {
+    let swapper = tx.recipient.clone().expect("swapper required");
+    set_swapper(env, tx.treasury_id, &swapper);
+
+    let event = SwapperUpdatedEvent {
+        treasury_id: tx.treasury_id,
+        swapper,
+    };
+    emit_event(env, MOD_TREASURY, ACT_UPDATED, event);
+}
+
+/// Propose swapping `amount` of one held token for at least `min_amount_out`
+/// of another through the treasury's current swapper. The swap needs the same
+/// approvals as a withdrawal of `amount`.
+#[allow(clippy::too_many_arguments)]
+pub fn propose_swap(
+    env: &Env,
+    treasury_id: u64,
+    proposer: Address,
+    from_token: Address,
+    to_token: Address,
+    amount: i128,
+    min_amount_out: i128,
+    reason: String,
+) -> u64 {
+    if amount <= 0 {
+        panic!("amount must be positive");
+    }
+    if min_amount_out <= 0 {
+        panic!("minimum output must be positive");
+    }
+    if from_token == to_token {
+        panic!("cannot swap a token for itself");
+    }
+
+    let treasury = get_treasury(env, treasury_id).expect("treasury not found");
+    if treasury.paused {
+        panic!("treasury is paused");
+    }
+    assert_signer(env, &treasury, &proposer);
+
+    let swapper = get_swapper(env, treasury_id).expect("no swapper registered");
+
+    let tx_id = get_next_tx_id(env);
+    let now = env.ledger().timestamp();
+    let mut approvals = Vec::new(env);
+    approvals.push_back(proposer.clone());
+
+    let tx = Transaction {
+        id: tx_id,
+        treasury_id,
+        tx_type: TransactionType::Swap,
+        amount,
+        token: Some(from_token.clone()),
+        recipient: None,
+        proposer: proposer.clone(),
+        approvals,
+        status: TransactionStatus::Pending,
+        created_at: now,
+        expires_at: now + TX_EXPIRY_SECONDS,
+        reason,
+        velocity_decision: VelocityDecision::WithinLimits,
+    };
+    store_transaction(env, &tx);
+    store_swap_order(
+        env,
+        &SwapOrder {
+            tx_id,
+            swapper,
+            to_token: to_token.clone(),
+            min_amount_out,
+            amount_out: 0,
+        },
+    );
+
+    let event = SwapProposedEvent {
+        treasury_id,
+        tx_id,
+        proposer,
+        from_token,
+        to_token,
+        amount_in: amount,
+        min_amount_out,
+    };
+    emit_event(env, MOD_TREASURY, ACT_PROPOSED, event);
+
+    tx_id
+}
+
+/// Run an approved swap through the swapper recorded on its order.
+///
+/// The swapper receives the input tokens before `execute_swap` is called and
+/// must send the output back to this contract. The output is what this
+/// contract's balance actually grew by, not what the swapper reports. Panics
+/// if that is below the order's minimum.
+pub fn execute_swap_transaction(env: &Env, treasury: &mut Treasury, tx: &Transaction) {
+    let mut order = get_swap_order(env, tx.id).expect("swap order not found");
+    let from_token = tx.token.clone().expect("swap source token required");
+
+    ensure_uncommitted(env, treasury, &tx.token, tx.amount);
+    let mut balances = treasury.token_balances.clone();
+    let from_balance = balances.get(from_token.clone()).unwrap_or(0i128);
+    if from_balance < tx.amount {
+        panic!("insufficient treasury balance");
+    }
+
+    let this = env.current_contract_address();
+    let to_client = TokenClient::new(env, &order.to_token);
+    let before = to_client.balance(&this);
+
+    TokenClient::new(env, &from_token).transfer(&this, &order.swapper, &tx.amount);
+    TokenSwapperClient::new(env, &order.swapper).execute_swap(
+        &from_token,
+        &order.to_token,
+        &tx.amount,
+    );
+
+    let amount_out = to_client.balance(&this) - before;
+    if amount_out < order.min_amount_out {
+        panic!("slippage exceeded");
+    }
+
+    let to_balance = balances.get(order.to_token.clone()).unwrap_or(0i128);
+    balances.set(from_token.clone(), from_balance - tx.amount);
+    balances.set(order.to_token.clone(), to_balance + amount_out);
+    treasury.token_balances = balances;
+
+    order.amount_out = amount_out;
+    store_swap_order(env, &order);
+
+    let event = SwapExecutedEvent {
+        treasury_id: treasury.id,
+        tx_id: tx.id,
+        from_token,
+        to_token: order.to_token,
+        amount_in: tx.amount,
+        amount_out,
+    };
+    emit_event(env, MOD_TREASURY, ACT_EXECUTED, event);
+}
@@ -1,11 +1,16 @@
 #[cfg(test)]
 mod tests {
-    use crate::treasury::types::{Allowance, TransactionStatus, TransactionType, Treasury};
+    use crate::interfaces::mocks::{MockSwapper, MockSwapperClient, MockVault, MockVaultClient};
+    use crate::treasury::types::{
+        Allowance, BatchLeg, BudgetMode, ScheduleStatus, ScheduleTerms, Transaction,
+        TransactionStatus, TransactionType, Treasury, VelocityAction, VelocityDecision,
+        VelocityScope,
+    };
     use crate::InitializerProof;
     use crate::StellarGuildsContract;
     use crate::StellarGuildsContractClient;
     use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
-    use soroban_sdk::{Address, Env, String, Vec};
+    use soroban_sdk::{token, Address, Env, String, Vec};
 
     fn setup_env() -> Env {
         let env = Env::default();
@@ -162,6 +167,299 @@ mod tests {
         client.execute_transaction(&tx_id, &owner);
     }
 
+    #[test]
+    fn test_reject_votes_reject_once_approval_impossible() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        let guild_id = setup_guild(&client, &env, &owner);
+        let (treasury_id, owner, signer1, signer2) = create_treasury(&env, &client, guild_id);
+
+        client.deposit_treasury(&treasury_id, &owner, &2000i128, &None);
+        let recipient = Address::generate(&env);
+        let tx_id = client.propose_withdrawal(
+            &treasury_id,
+            &signer1,
+            &recipient,
+            &1500i128,
+            &None,
+            &String::from_str(&env, "vendor invoice"),
+        );
+
+        // Two of three signers can still approve after one rejection
+        client.reject_transaction(&tx_id, &owner, &String::from_str(&env, "no invoice"));
+        assert_eq!(
+            find_tx(&client, treasury_id, tx_id).status,
+            TransactionStatus::Pending
+        );
+
+        client.reject_transaction(&tx_id, &signer2, &String::from_str(&env, "overpriced"));
+        let tx = find_tx(&client, treasury_id, tx_id);
+        assert_eq!(tx.status, TransactionStatus::Rejected);
+        assert_eq!(tx.reason, String::from_str(&env, "vendor invoice"));
+
+        let rejections = client.get_transaction_rejections(&tx_id);
+        assert_eq!(rejections.len(), 2);
+        assert_eq!(rejections.get(0).unwrap().signer, owner);
+        assert_eq!(
+            rejections.get(1).unwrap().reason,
+            String::from_str(&env, "overpriced")
+        );
+
+        let result = client.try_approve_transaction(&tx_id, &signer2);
+        assert!(result.is_err());
+        assert_eq!(client.get_treasury_balance(&treasury_id, &None), 2000);
+    }
+
+    #[test]
+    fn test_proposer_can_cancel_pending_transaction() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        let guild_id = setup_guild(&client, &env, &owner);
+        let (treasury_id, owner, signer1, _signer2) = create_treasury(&env, &client, guild_id);
+
+        client.deposit_treasury(&treasury_id, &owner, &2000i128, &None);
+        let recipient = Address::generate(&env);
+        let reason = String::from_str(&env, "typo in amount");
+        let tx_id = client.propose_withdrawal(
+            &treasury_id,
+            &signer1,
+            &recipient,
+            &1500i128,
+            &None,
+            &String::from_str(&env, "payout"),
+        );
+
+        // Signers cannot reject with their own approval on the transaction,
+        // and only the proposer may withdraw it
+        assert!(client
+            .try_reject_transaction(&tx_id, &signer1, &reason)
+            .is_err());
+        assert!(client
+            .try_cancel_transaction(&tx_id, &owner, &reason)
+            .is_err());
+
+        assert!(client.cancel_transaction(&tx_id, &signer1, &reason));
+        assert_eq!(
+            find_tx(&client, treasury_id, tx_id).status,
+            TransactionStatus::Cancelled
+        );
+        assert!(client.try_execute_transaction(&tx_id, &owner).is_err());
+    }
+
+    fn execute_withdrawal(
+        env: &Env,
+        client: &StellarGuildsContractClient<'_>,
+        treasury_id: u64,
+        signers: (&Address, &Address, &Address),
+        amount: i128,
+    ) -> u64 {
+        let (owner, signer1, signer2) = signers;
+        let tx_id = client.propose_withdrawal(
+            &treasury_id,
+            signer1,
+            &Address::generate(env),
+            &amount,
+            &None,
+            &String::from_str(env, "payout"),
+        );
+        client.approve_transaction(&tx_id, signer2);
+        client.execute_transaction(&tx_id, owner);
+        tx_id
+    }
+
+    #[test]
+    fn test_budget_carries_unused_allocation_up_to_cap() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        let guild_id = setup_guild(&client, &env, &owner);
+        let (treasury_id, owner, signer1, signer2) = create_treasury(&env, &client, guild_id);
+        client.deposit_treasury(&treasury_id, &owner, &10000i128, &None);
+
+        let category = String::from_str(&env, "withdrawal");
+        client.set_budget(&treasury_id, &category, &1000i128, &3600u64, &owner);
+        client.configure_budget(
+            &treasury_id,
+            &category,
+            &500i128,
+            &None,
+            &BudgetMode::Hard,
+            &owner,
+        );
+
+        execute_withdrawal(
+            &env,
+            &client,
+            treasury_id,
+            (&owner, &signer1, &signer2),
+            800,
+        );
+
+        // 200 unused carries into the next period
+        set_ledger_timestamp(&env, 5000);
+        execute_withdrawal(
+            &env,
+            &client,
+            treasury_id,
+            (&owner, &signer1, &signer2),
+            1150,
+        );
+        let budget = client.get_budget(&treasury_id, &category);
+        assert_eq!(budget.carried_over, 200);
+        assert_eq!(budget.spent_amount, 1150);
+
+        // Two idle periods carry at most the cap
+        set_ledger_timestamp(&env, 5000 + 3 * 3600);
+        execute_withdrawal(
+            &env,
+            &client,
+            treasury_id,
+            (&owner, &signer1, &signer2),
+            1500,
+        );
+        assert_eq!(client.get_budget(&treasury_id, &category).carried_over, 500);
+        assert_eq!(client.get_treasury_balance(&treasury_id, &None), 6550);
+    }
+
+    #[test]
+    fn test_child_budget_spend_counts_against_parent() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        let guild_id = setup_guild(&client, &env, &owner);
+        let (treasury_id, owner, signer1, signer2) = create_treasury(&env, &client, guild_id);
+        client.deposit_treasury(&treasury_id, &owner, &10000i128, &None);
+
+        let operations = String::from_str(&env, "operations");
+        let withdrawal = String::from_str(&env, "withdrawal");
+        client.set_budget(&treasury_id, &operations, &1000i128, &3600u64, &owner);
+        client.set_budget(&treasury_id, &withdrawal, &5000i128, &3600u64, &owner);
+        client.configure_budget(
+            &treasury_id,
+            &withdrawal,
+            &0i128,
+            &Some(operations.clone()),
+            &BudgetMode::Hard,
+            &owner,
+        );
+
+        // A parent cannot sit below its own child
+        assert!(client
+            .try_configure_budget(
+                &treasury_id,
+                &operations,
+                &0i128,
+                &Some(withdrawal.clone()),
+                &BudgetMode::Hard,
+                &owner,
+            )
+            .is_err());
+
+        execute_withdrawal(
+            &env,
+            &client,
+            treasury_id,
+            (&owner, &signer1, &signer2),
+            800,
+        );
+        assert_eq!(
+            client.get_budget(&treasury_id, &operations).spent_amount,
+            800
+        );
+
+        // Within the child's budget but over the parent's
+        let tx_id = client.propose_withdrawal(
+            &treasury_id,
+            &signer1,
+            &Address::generate(&env),
+            &500i128,
+            &None,
+            &String::from_str(&env, "payout"),
+        );
+        client.approve_transaction(&tx_id, &signer2);
+        assert!(client.try_execute_transaction(&tx_id, &owner).is_err());
+        assert_eq!(
+            client.get_budget(&treasury_id, &withdrawal).spent_amount,
+            800
+        );
+        assert_eq!(
+            client.get_budget(&treasury_id, &operations).spent_amount,
+            800
+        );
+    }
+
+    #[test]
+    fn test_soft_budget_overspend_requires_all_signers() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        let guild_id = setup_guild(&client, &env, &owner);
+        let (treasury_id, owner, signer1, signer2) = create_treasury(&env, &client, guild_id);
+        client.deposit_treasury(&treasury_id, &owner, &10000i128, &None);
+
+        let category = String::from_str(&env, "withdrawal");
+        client.set_budget(&treasury_id, &category, &1000i128, &3600u64, &owner);
+        client.configure_budget(
+            &treasury_id,
+            &category,
+            &0i128,
+            &None,
+            &BudgetMode::Soft,
+            &owner,
+        );
+
+        let tx_id = client.propose_withdrawal(
+            &treasury_id,
+            &signer1,
+            &Address::generate(&env),
+            &1500i128,
+            &None,
+            &String::from_str(&env, "conference"),
+        );
+        client.approve_transaction(&tx_id, &signer2);
+        assert!(client.try_execute_transaction(&tx_id, &owner).is_err());
+
+        client.approve_transaction(&tx_id, &owner);
+        assert!(client.execute_transaction(&tx_id, &owner));
+        assert_eq!(
+            client.get_budget(&treasury_id, &category).spent_amount,
+            1500
+        );
+        assert_eq!(client.get_treasury_balance(&treasury_id, &None), 8500);
+    }
+
     #[test]
     #[should_panic] // Removed strict string match
     fn test_multisig_timeout_expiration() {
@@ -271,6 +569,1020 @@ mod tests {
         client.propose_withdrawal(&treasury_id, &signer1, &recipient, &100i128, &None, &reason);
     }
 
+    fn find_tx(
+        client: &StellarGuildsContractClient<'_>,
+        treasury_id: u64,
+        tx_id: u64,
+    ) -> Transaction {
+        client
+            .get_transaction_history(&treasury_id, &100u32)
+            .iter()
+            .find(|tx| tx.id == tx_id)
+            .expect("tx not found")
+    }
+
+    #[test]
+    fn test_velocity_limit_blocks_over_window_and_resets() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        let guild_id = setup_guild(&client, &env, &owner);
+        let (treasury_id, owner, signer1, signer2) = create_treasury(&env, &client, guild_id);
+        client.deposit_treasury(&treasury_id, &owner, &5000i128, &None);
+
+        client.set_velocity_limit(
+            &treasury_id,
+            &VelocityScope::Treasury,
+            &1000i128,
+            &None,
+            &86_400u64,
+            &VelocityAction::Block,
+            &owner,
+        );
+        assert_eq!(client.get_velocity_limits(&treasury_id).len(), 1);
+
+        let recipient = Address::generate(&env);
+        let tx1 = client.propose_withdrawal(
+            &treasury_id,
+            &signer1,
+            &recipient,
+            &800i128,
+            &None,
+            &String::from_str(&env, "first"),
+        );
+        client.approve_transaction(&tx1, &signer2);
+        assert!(client.execute_transaction(&tx1, &owner));
+
+        let tx2 = client.propose_withdrawal(
+            &treasury_id,
+            &signer1,
+            &recipient,
+            &300i128,
+            &None,
+            &String::from_str(&env, "second"),
+        );
+        let blocked = find_tx(&client, treasury_id, tx2);
+        assert_eq!(blocked.status, TransactionStatus::Rejected);
+        assert_eq!(blocked.velocity_decision, VelocityDecision::Blocked);
+
+        // Once the first withdrawal leaves the window the same amount passes
+        set_ledger_timestamp(&env, 1000 + 86_401);
+        let tx3 = client.propose_withdrawal(
+            &treasury_id,
+            &signer1,
+            &recipient,
+            &300i128,
+            &None,
+            &String::from_str(&env, "third"),
+        );
+        let allowed = find_tx(&client, treasury_id, tx3);
+        assert_eq!(allowed.status, TransactionStatus::Pending);
+        assert_eq!(allowed.velocity_decision, VelocityDecision::WithinLimits);
+    }
+
+    #[test]
+    fn test_velocity_limit_counts_only_its_token() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        let guild_id = setup_guild(&client, &env, &owner);
+        let (treasury_id, owner, signer1, signer2) = create_treasury(&env, &client, guild_id);
+        client.deposit_treasury(&treasury_id, &owner, &5000i128, &None);
+
+        let usdc = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        token::StellarAssetClient::new(&env, &usdc).mint(&owner, &5000);
+        client.deposit_treasury(&treasury_id, &owner, &5000i128, &Some(usdc.clone()));
+
+        client.set_velocity_limit(
+            &treasury_id,
+            &VelocityScope::Treasury,
+            &1000i128,
+            &Some(usdc.clone()),
+            &86_400u64,
+            &VelocityAction::Block,
+            &owner,
+        );
+
+        // XLM outflows are not counted against the USDC limit
+        let recipient = Address::generate(&env);
+        let tx1 = client.propose_withdrawal(
+            &treasury_id,
+            &signer1,
+            &recipient,
+            &2000i128,
+            &None,
+            &String::from_str(&env, "xlm"),
+        );
+        assert_eq!(
+            find_tx(&client, treasury_id, tx1).velocity_decision,
+            VelocityDecision::WithinLimits
+        );
+        client.approve_transaction(&tx1, &signer2);
+        assert!(client.execute_transaction(&tx1, &owner));
+
+        let tx2 = client.propose_withdrawal(
+            &treasury_id,
+            &signer1,
+            &recipient,
+            &800i128,
+            &Some(usdc.clone()),
+            &String::from_str(&env, "usdc"),
+        );
+        assert_eq!(
+            find_tx(&client, treasury_id, tx2).velocity_decision,
+            VelocityDecision::WithinLimits
+        );
+        client.approve_transaction(&tx2, &signer2);
+        assert!(client.execute_transaction(&tx2, &owner));
+
+        let tx3 = client.propose_withdrawal(
+            &treasury_id,
+            &signer1,
+            &recipient,
+            &300i128,
+            &Some(usdc.clone()),
+            &String::from_str(&env, "usdc again"),
+        );
+        assert_eq!(
+            find_tx(&client, treasury_id, tx3).velocity_decision,
+            VelocityDecision::Blocked
+        );
+    }
+
+    #[test]
+    fn test_velocity_limit_escalates_recipient_to_all_signers() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        let guild_id = setup_guild(&client, &env, &owner);
+        let (treasury_id, owner, signer1, signer2) = create_treasury(&env, &client, guild_id);
+        client.deposit_treasury(&treasury_id, &owner, &5000i128, &None);
+
+        client.set_velocity_limit(
+            &treasury_id,
+            &VelocityScope::Recipient,
+            &500i128,
+            &None,
+            &86_400u64,
+            &VelocityAction::Escalate,
+            &owner,
+        );
+
+        // Below the high-value threshold, but over the recipient limit
+        let recipient = Address::generate(&env);
+        let tx_id = client.propose_withdrawal(
+            &treasury_id,
+            &signer1,
+            &recipient,
+            &600i128,
+            &None,
+            &String::from_str(&env, "vendor"),
+        );
+        assert_eq!(
+            find_tx(&client, treasury_id, tx_id).velocity_decision,
+            VelocityDecision::Escalated
+        );
+
+        client.approve_transaction(&tx_id, &signer2);
+        assert_eq!(
+            find_tx(&client, treasury_id, tx_id).status,
+            TransactionStatus::Pending
+        );
+
+        client.approve_transaction(&tx_id, &owner);
+        assert_eq!(
+            find_tx(&client, treasury_id, tx_id).status,
+            TransactionStatus::Approved
+        );
+        assert!(client.execute_transaction(&tx_id, &owner));
+        assert_eq!(client.get_treasury_balance(&treasury_id, &None), 4400);
+
+        // A different recipient is unaffected
+        let other = Address::generate(&env);
+        let other_tx = client.propose_withdrawal(
+            &treasury_id,
+            &signer1,
+            &other,
+            &400i128,
+            &None,
+            &String::from_str(&env, "other"),
+        );
+        assert_eq!(
+            find_tx(&client, treasury_id, other_tx).velocity_decision,
+            VelocityDecision::WithinLimits
+        );
+    }
+
+    #[test]
+    fn test_velocity_limit_rechecked_at_execution() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        let guild_id = setup_guild(&client, &env, &owner);
+        let (treasury_id, owner, signer1, signer2) = create_treasury(&env, &client, guild_id);
+        client.deposit_treasury(&treasury_id, &owner, &5000i128, &None);
+
+        client.set_velocity_limit(
+            &treasury_id,
+            &VelocityScope::Signer,
+            &600i128,
+            &None,
+            &3600u64,
+            &VelocityAction::Block,
+            &owner,
+        );
+
+        let recipient = Address::generate(&env);
+        let tx1 = client.propose_withdrawal(
+            &treasury_id,
+            &signer1,
+            &recipient,
+            &400i128,
+            &None,
+            &String::from_str(&env, "one"),
+        );
+        let tx2 = client.propose_withdrawal(
+            &treasury_id,
+            &signer1,
+            &recipient,
+            &400i128,
+            &None,
+            &String::from_str(&env, "two"),
+        );
+        client.approve_transaction(&tx1, &signer2);
+        client.approve_transaction(&tx2, &signer2);
+
+        assert!(client.execute_transaction(&tx1, &owner));
+        assert!(!client.execute_transaction(&tx2, &owner));
+
+        let blocked = find_tx(&client, treasury_id, tx2);
+        assert_eq!(blocked.status, TransactionStatus::Rejected);
+        assert_eq!(blocked.velocity_decision, VelocityDecision::Blocked);
+        assert_eq!(client.get_treasury_balance(&treasury_id, &None), 4600);
+    }
+
+    fn payroll_terms(
+        env: &Env,
+        recipient: &Address,
+        amount: i128,
+        max_occurrences: Option<u32>,
+    ) -> ScheduleTerms {
+        ScheduleTerms {
+            recipient: recipient.clone(),
+            amount,
+            token: None,
+            interval_seconds: 1000,
+            start_at: 2000,
+            end_at: None,
+            max_occurrences,
+            category: String::from_str(env, "payroll"),
+        }
+    }
+
+    #[test]
+    fn test_scheduled_payment_runs_until_max_occurrences() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        let guild_id = setup_guild(&client, &env, &owner);
+        let (treasury_id, owner, signer1, signer2) = create_treasury(&env, &client, guild_id);
+        client.deposit_treasury(&treasury_id, &owner, &1000i128, &None);
+
+        let recipient = Address::generate(&env);
+        let schedule_id = client.propose_scheduled_payment(
+            &treasury_id,
+            &signer1,
+            &payroll_terms(&env, &recipient, 100, Some(2)),
+        );
+        assert_eq!(
+            client.get_scheduled_payment(&schedule_id).status,
+            ScheduleStatus::Pending
+        );
+
+        // Not approved yet: nothing runs even when due
+        set_ledger_timestamp(&env, 2000);
+        assert_eq!(client.process_scheduled_payments(&10u32), 0);
+
+        client.approve_scheduled_payment(&schedule_id, &signer2);
+        assert_eq!(
+            client.get_scheduled_payment(&schedule_id).status,
+            ScheduleStatus::Active
+        );
+
+        assert_eq!(client.process_scheduled_payments(&10u32), 1);
+        assert_eq!(client.get_treasury_balance(&treasury_id, &None), 900);
+        // Same occurrence is not paid twice
+        assert_eq!(client.process_scheduled_payments(&10u32), 0);
+
+        set_ledger_timestamp(&env, 3000);
+        assert_eq!(client.process_scheduled_payments(&10u32), 1);
+        assert_eq!(client.get_treasury_balance(&treasury_id, &None), 800);
+
+        let schedule = client.get_scheduled_payment(&schedule_id);
+        assert_eq!(schedule.occurrences, 2);
+        assert_eq!(schedule.status, ScheduleStatus::Completed);
+
+        set_ledger_timestamp(&env, 4000);
+        assert_eq!(client.process_scheduled_payments(&10u32), 0);
+
+        let history = client.get_transaction_history(&treasury_id, &10u32);
+        let last = history.get(history.len() - 1).unwrap();
+        assert_eq!(last.status, TransactionStatus::Executed);
+        assert_eq!(last.recipient, Some(recipient));
+    }
+
+    #[test]
+    fn test_scheduled_payments_respect_velocity_limits() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        let guild_id = setup_guild(&client, &env, &owner);
+        let (treasury_id, owner, signer1, signer2) = create_treasury(&env, &client, guild_id);
+        client.deposit_treasury(&treasury_id, &owner, &5000i128, &None);
+        client.set_velocity_limit(
+            &treasury_id,
+            &VelocityScope::Treasury,
+            &150i128,
+            &None,
+            &86_400u64,
+            &VelocityAction::Block,
+            &owner,
+        );
+        client.set_velocity_limit(
+            &treasury_id,
+            &VelocityScope::Recipient,
+            &50i128,
+            &None,
+            &86_400u64,
+            &VelocityAction::Escalate,
+            &owner,
+        );
+
+        let recipient = Address::generate(&env);
+        let held = client.propose_scheduled_payment(
+            &treasury_id,
+            &signer1,
+            &payroll_terms(&env, &recipient, 100, None),
+        );
+        client.approve_scheduled_payment(&held, &signer2);
+
+        // Escalated and approved by only two of three signers: held
+        set_ledger_timestamp(&env, 2000);
+        assert_eq!(client.process_scheduled_payments(&10u32), 0);
+        assert_eq!(client.get_scheduled_payment(&held).occurrences, 0);
+        assert_eq!(client.get_treasury_balance(&treasury_id, &None), 5000);
+
+        // Once every signer has approved, the escalated payment goes through
+        client.approve_scheduled_payment(&held, &owner);
+        assert_eq!(client.process_scheduled_payments(&10u32), 1);
+        assert_eq!(client.get_treasury_balance(&treasury_id, &None), 4900);
+        let history = client.get_transaction_history(&treasury_id, &10u32);
+        let last = history.get(history.len() - 1).unwrap();
+        assert_eq!(last.velocity_decision, VelocityDecision::Escalated);
+
+        // A second occurrence would take the treasury past its block limit
+        set_ledger_timestamp(&env, 3000);
+        assert_eq!(client.process_scheduled_payments(&10u32), 0);
+        assert_eq!(client.get_scheduled_payment(&held).occurrences, 1);
+        assert_eq!(client.get_treasury_balance(&treasury_id, &None), 4900);
+    }
+
+    #[test]
+    fn test_scheduled_payments_respect_budget_pause_and_batch_size() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        let guild_id = setup_guild(&client, &env, &owner);
+        let (treasury_id, owner, signer1, signer2) = create_treasury(&env, &client, guild_id);
+        client.deposit_treasury(&treasury_id, &owner, &1000i128, &None);
+        client.set_budget(
+            &treasury_id,
+            &String::from_str(&env, "payroll"),
+            &250i128,
+            &100_000u64,
+            &owner,
+        );
+
+        let first = client.propose_scheduled_payment(
+            &treasury_id,
+            &signer1,
+            &payroll_terms(&env, &Address::generate(&env), 100, None),
+        );
+        let second = client.propose_scheduled_payment(
+            &treasury_id,
+            &signer1,
+            &payroll_terms(&env, &Address::generate(&env), 100, None),
+        );
+        client.approve_scheduled_payment(&first, &signer2);
+        client.approve_scheduled_payment(&second, &signer2);
+
+        // Batch size bounds how many due schedules one call handles
+        set_ledger_timestamp(&env, 2000);
+        assert_eq!(client.process_scheduled_payments(&1u32), 1);
+        assert_eq!(client.process_scheduled_payments(&1u32), 1);
+        assert_eq!(client.get_treasury_balance(&treasury_id, &None), 800);
+
+        // Paused treasuries are skipped without consuming the occurrence
+        set_ledger_timestamp(&env, 3000);
+        client.emergency_pause(&treasury_id, &owner, &true);
+        assert_eq!(client.process_scheduled_payments(&10u32), 0);
+        client.emergency_pause(&treasury_id, &owner, &false);
+
+        // Only 50 of the payroll budget is left, so the remaining schedule is skipped
+        client.cancel_scheduled_payment(&second, &signer2);
+        assert_eq!(client.process_scheduled_payments(&10u32), 0);
+        assert_eq!(client.get_scheduled_payment(&first).occurrences, 1);
+        assert_eq!(
+            client.get_scheduled_payment(&second).status,
+            ScheduleStatus::Cancelled
+        );
+        assert_eq!(client.get_treasury_balance(&treasury_id, &None), 800);
+    }
+
+    fn setup_swapper(
+        env: &Env,
+        client: &StellarGuildsContractClient<'_>,
+        treasury_id: u64,
+        owner: &Address,
+        signer: &Address,
+        contract_id: &Address,
+    ) -> Address {
+        let swapper = env.register_contract(None, MockSwapper);
+        MockSwapperClient::new(env, &swapper).init(contract_id);
+        let tx_id = client.propose_swapper_change(
+            &treasury_id,
+            owner,
+            &swapper,
+            &String::from_str(env, "swapper"),
+        );
+        client.approve_transaction(&tx_id, signer);
+        client.execute_transaction(&tx_id, owner);
+        swapper
+    }
+
+    fn setup_swap_treasury(
+        env: &Env,
+        client: &StellarGuildsContractClient<'_>,
+        contract_id: &Address,
+    ) -> (u64, Address, Address, Address, Address, Address, Address) {
+        let owner = Address::generate(env);
+        let guild_id = setup_guild(client, env, &owner);
+        let (treasury_id, owner, signer1, signer2) = create_treasury(env, client, guild_id);
+
+        let token_admin = Address::generate(env);
+        let usdc = env
+            .register_stellar_asset_contract_v2(token_admin.clone())
+            .address();
+        let eurc = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+        token::StellarAssetClient::new(env, &usdc).mint(&owner, &5000);
+        client.deposit_treasury(&treasury_id, &owner, &5000i128, &Some(usdc.clone()));
+
+        let swapper = setup_swapper(env, client, treasury_id, &owner, &signer1, contract_id);
+        token::StellarAssetClient::new(env, &eurc).mint(&swapper, &5000);
+        assert_eq!(
+            token::TokenClient::new(env, &usdc).balance(contract_id),
+            5000
+        );
+
+        (treasury_id, owner, signer1, signer2, usdc, eurc, swapper)
+    }
+
+    #[test]
+    fn test_swap_rebalances_token_balances_after_approval() {
+        let env = setup_env();
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (treasury_id, owner, signer1, signer2, usdc, eurc, swapper) =
+            setup_swap_treasury(&env, &client, &contract_id);
+
+        // At the high-value threshold the swap needs the full signer threshold
+        let tx_id = client.propose_swap(
+            &treasury_id,
+            &signer1,
+            &usdc,
+            &eurc,
+            &2000i128,
+            &1990i128,
+            &String::from_str(&env, "rebalance"),
+        );
+        let tx = find_tx(&client, treasury_id, tx_id);
+        assert_eq!(tx.tx_type, TransactionType::Swap);
+        assert_eq!(tx.status, TransactionStatus::Pending);
+
+        client.approve_transaction(&tx_id, &signer2);
+        assert!(client.execute_transaction(&tx_id, &owner));
+
+        assert_eq!(
+            client.get_treasury_balance(&treasury_id, &Some(usdc.clone())),
+            3000
+        );
+        assert_eq!(
+            client.get_treasury_balance(&treasury_id, &Some(eurc.clone())),
+            2000
+        );
+        assert_eq!(token::TokenClient::new(&env, &usdc).balance(&swapper), 2000);
+        assert_eq!(
+            token::TokenClient::new(&env, &eurc).balance(&contract_id),
+            2000
+        );
+        assert_eq!(client.get_swap_order(&tx_id).amount_out, 2000);
+        assert_eq!(
+            find_tx(&client, treasury_id, tx_id).status,
+            TransactionStatus::Executed
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "slippage exceeded")]
+    fn test_swap_below_minimum_output_fails() {
+        let env = setup_env();
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (treasury_id, owner, signer1, signer2, usdc, eurc, _swapper) =
+            setup_swap_treasury(&env, &client, &contract_id);
+
+        // The mock swapper quotes 1:1, so demanding more than the input fails
+        let tx_id = client.propose_swap(
+            &treasury_id,
+            &signer1,
+            &usdc,
+            &eurc,
+            &500i128,
+            &501i128,
+            &String::from_str(&env, "greedy"),
+        );
+        client.approve_transaction(&tx_id, &signer2);
+        client.execute_transaction(&tx_id, &owner);
+    }
+
+    #[test]
+    #[should_panic(expected = "slippage exceeded")]
+    fn test_swap_credits_output_received_not_reported() {
+        let env = setup_env();
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (treasury_id, owner, signer1, signer2, usdc, eurc, swapper) =
+            setup_swap_treasury(&env, &client, &contract_id);
+
+        // The swapper reports the full output but delivers 20 less
+        MockSwapperClient::new(&env, &swapper).set_shortfall(&20);
+        let tx_id = client.propose_swap(
+            &treasury_id,
+            &signer1,
+            &usdc,
+            &eurc,
+            &2000i128,
+            &1990i128,
+            &String::from_str(&env, "rebalance"),
+        );
+        client.approve_transaction(&tx_id, &signer2);
+        client.execute_transaction(&tx_id, &owner);
+    }
+
+    #[test]
+    fn test_swapper_change_needs_threshold_and_spares_proposed_swaps() {
+        let env = setup_env();
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (treasury_id, owner, signer1, signer2, usdc, eurc, swapper) =
+            setup_swap_treasury(&env, &client, &contract_id);
+
+        let swap_id = client.propose_swap(
+            &treasury_id,
+            &signer1,
+            &usdc,
+            &eurc,
+            &500i128,
+            &500i128,
+            &String::from_str(&env, "rebalance"),
+        );
+
+        let other = env.register_contract(None, MockSwapper);
+        let change_id = client.propose_swapper_change(
+            &treasury_id,
+            &owner,
+            &other,
+            &String::from_str(&env, "cheaper venue"),
+        );
+        let change = find_tx(&client, treasury_id, change_id);
+        assert_eq!(change.tx_type, TransactionType::SwapperChange);
+        assert_eq!(change.status, TransactionStatus::Pending);
+        assert!(client.try_execute_transaction(&change_id, &owner).is_err());
+
+        client.approve_transaction(&change_id, &signer2);
+        assert!(client.execute_transaction(&change_id, &owner));
+
+        // The earlier swap still trades through the swapper it was proposed with
+        assert_eq!(client.get_swap_order(&swap_id).swapper, swapper);
+        client.approve_transaction(&swap_id, &signer2);
+        assert!(client.execute_transaction(&swap_id, &signer1));
+        assert_eq!(token::TokenClient::new(&env, &usdc).balance(&swapper), 500);
+        assert_eq!(token::TokenClient::new(&env, &usdc).balance(&other), 0);
+    }
+
+    fn setup_vault(
+        env: &Env,
+        client: &StellarGuildsContractClient<'_>,
+        treasury_id: u64,
+        owner: &Address,
+        usdc: &Address,
+        cap_bps: u32,
+    ) -> Address {
+        let vault = env.register_contract(None, MockVault);
+        MockVaultClient::new(env, &vault).init(usdc);
+        client.set_vault_allocation(&treasury_id, &vault, usdc, &cap_bps, owner);
+        vault
+    }
+
+    #[test]
+    fn test_vault_allocation_tracks_liquid_and_deployed_balances() {
+        let env = setup_env();
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (treasury_id, owner, signer1, signer2, usdc, _eurc, _swapper) =
+            setup_swap_treasury(&env, &client, &contract_id);
+        let vault = setup_vault(&env, &client, treasury_id, &owner, &usdc, 5000);
+
+        let tx_id = client.propose_vault_deposit(
+            &treasury_id,
+            &signer1,
+            &vault,
+            &2000i128,
+            &String::from_str(&env, "idle funds"),
+        );
+        assert_eq!(
+            find_tx(&client, treasury_id, tx_id).tx_type,
+            TransactionType::VaultDeposit
+        );
+        client.approve_transaction(&tx_id, &signer2);
+        assert!(client.execute_transaction(&tx_id, &owner));
+
+        let detail = client.get_treasury_balance_detail(&treasury_id, &Some(usdc.clone()));
+        assert_eq!(detail.liquid, 3000);
+        assert_eq!(detail.deployed, 2000);
+        assert_eq!(
+            client.get_treasury_balance(&treasury_id, &Some(usdc.clone())),
+            3000
+        );
+        assert_eq!(token::TokenClient::new(&env, &usdc).balance(&vault), 2000);
+
+        // The vault earns 200 on the position
+        token::StellarAssetClient::new(&env, &usdc).mint(&vault, &200);
+        MockVaultClient::new(&env, &vault).accrue(&contract_id, &200);
+
+        let report = client.get_balance_report(&treasury_id);
+        let usdc_report = report
+            .iter()
+            .find(|r| r.token == Some(usdc.clone()))
+            .unwrap();
+        assert_eq!(usdc_report.liquid, 3000);
+        assert_eq!(usdc_report.deployed, 2000);
+        assert_eq!(usdc_report.deployed_value, 2200);
+
+        let tx_id = client.propose_vault_withdrawal(
+            &treasury_id,
+            &signer1,
+            &vault,
+            &2200i128,
+            &String::from_str(&env, "recall"),
+        );
+        client.approve_transaction(&tx_id, &signer2);
+        assert!(client.execute_transaction(&tx_id, &owner));
+
+        let detail = client.get_treasury_balance_detail(&treasury_id, &Some(usdc.clone()));
+        assert_eq!(detail.liquid, 5200);
+        assert_eq!(detail.deployed, 0);
+        assert_eq!(
+            token::TokenClient::new(&env, &usdc).balance(&contract_id),
+            5200
+        );
+
+        let breakdown = client.get_category_breakdown(&treasury_id, &0u64, &2000u64);
+        assert!(breakdown.iter().any(|c| c.category_name
+            == String::from_str(&env, "VaultDeposit")
+            && c.total_amount == 2000));
+    }
+
+    #[test]
+    fn test_vault_withdrawal_credits_amount_received() {
+        let env = setup_env();
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (treasury_id, owner, signer1, signer2, usdc, _eurc, _swapper) =
+            setup_swap_treasury(&env, &client, &contract_id);
+        let vault = setup_vault(&env, &client, treasury_id, &owner, &usdc, 5000);
+
+        let tx_id = client.propose_vault_deposit(
+            &treasury_id,
+            &signer1,
+            &vault,
+            &2000i128,
+            &String::from_str(&env, "deploy"),
+        );
+        client.approve_transaction(&tx_id, &signer2);
+        client.execute_transaction(&tx_id, &owner);
+
+        // The vault reports the full amount but sends 50 less
+        MockVaultClient::new(&env, &vault).set_shortfall(&50);
+        let tx_id = client.propose_vault_withdrawal(
+            &treasury_id,
+            &signer1,
+            &vault,
+            &2000i128,
+            &String::from_str(&env, "recall"),
+        );
+        client.approve_transaction(&tx_id, &signer2);
+        assert!(client.execute_transaction(&tx_id, &owner));
+
+        let detail = client.get_treasury_balance_detail(&treasury_id, &Some(usdc.clone()));
+        assert_eq!(detail.liquid, 4950);
+        assert_eq!(
+            token::TokenClient::new(&env, &usdc).balance(&contract_id),
+            4950
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "vault allocation cap exceeded")]
+    fn test_vault_deposit_over_cap_fails() {
+        let env = setup_env();
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (treasury_id, owner, signer1, _signer2, usdc, _eurc, _swapper) =
+            setup_swap_treasury(&env, &client, &contract_id);
+        // At most 25% of 5000 may be deployed
+        let vault = setup_vault(&env, &client, treasury_id, &owner, &usdc, 2500);
+
+        client.propose_vault_deposit(
+            &treasury_id,
+            &signer1,
+            &vault,
+            &1500i128,
+            &String::from_str(&env, "too much"),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "vault not whitelisted")]
+    fn test_vault_deposit_requires_whitelist() {
+        let env = setup_env();
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (treasury_id, owner, signer1, _signer2, usdc, _eurc, _swapper) =
+            setup_swap_treasury(&env, &client, &contract_id);
+        let vault = setup_vault(&env, &client, treasury_id, &owner, &usdc, 5000);
+        client.set_vault_allocation(&treasury_id, &vault, &usdc, &0u32, &owner);
+
+        client.propose_vault_deposit(
+            &treasury_id,
+            &signer1,
+            &vault,
+            &100i128,
+            &String::from_str(&env, "delisted"),
+        );
+    }
+
+    #[test]
+    fn test_batch_withdrawal_pays_every_leg_once_approved() {
+        let env = setup_env();
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+        let (treasury_id, owner, signer1, signer2, usdc, _eurc, _swapper) =
+            setup_swap_treasury(&env, &client, &contract_id);
+        client.deposit_treasury(&treasury_id, &owner, &1000i128, &None);
+
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let mut legs = Vec::new(&env);
+        legs.push_back(BatchLeg {
+            recipient: alice.clone(),
+            token: Some(usdc.clone()),
+            amount: 1200,
+        });
+        legs.push_back(BatchLeg {
+            recipient: bob.clone(),
+            token: Some(usdc.clone()),
+            amount: 800,
+        });
+        legs.push_back(BatchLeg {
+            recipient: bob.clone(),
+            token: None,
+            amount: 300,
+        });
+
+        let tx_id = client.propose_batch_withdrawal(
+            &treasury_id,
+            &signer1,
+            &legs,
+            &String::from_str(&env, "march contributors"),
+        );
+        let tx = find_tx(&client, treasury_id, tx_id);
+        assert_eq!(tx.tx_type, TransactionType::BatchWithdrawal);
+        assert_eq!(tx.amount, 2000);
+        assert_eq!(tx.token, Some(usdc.clone()));
+
+        client.approve_transaction(&tx_id, &signer2);
+        assert!(client.execute_transaction(&tx_id, &owner));
+
+        let usdc_client = token::TokenClient::new(&env, &usdc);
+        assert_eq!(usdc_client.balance(&alice), 1200);
+        assert_eq!(usdc_client.balance(&bob), 800);
+        assert_eq!(
+            client.get_treasury_balance(&treasury_id, &Some(usdc.clone())),
+            3000
+        );
+        assert_eq!(client.get_treasury_balance(&treasury_id, &None), 700);
+
+        let batch = client.get_batch_withdrawal(&tx_id);
+        assert_eq!(batch.leg_tx_ids.len(), 3);
+        let leg = find_tx(&client, treasury_id, batch.leg_tx_ids.get(2).unwrap());
+        assert_eq!(leg.tx_type, TransactionType::Withdrawal);
+        assert_eq!(leg.status, TransactionStatus::Executed);
+        assert_eq!(leg.recipient, Some(bob));
+        assert_eq!(leg.amount, 300);
+
+        let summary = client.get_spending_summary(&treasury_id, &0u64, &2000u64);
+        assert_eq!(summary.total_withdrawals, 2300);
+        assert_eq!(
+            find_tx(&client, treasury_id, tx_id).status,
+            TransactionStatus::Executed
+        );
+    }
+
+    #[test]
+    fn test_batch_withdrawal_threshold_checked_per_token() {
+        let env = setup_env();
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        let owner = Address::generate(&env);
+        let signer1 = Address::generate(&env);
+        let signer2 = Address::generate(&env);
+        let guild_id = setup_guild(&client, &env, &owner);
+        let mut signers = Vec::new(&env);
+        signers.push_back(owner.clone());
+        signers.push_back(signer1.clone());
+        signers.push_back(signer2.clone());
+        let treasury_id = client.initialize_treasury(&guild_id, &signers, &3u32);
+
+        let usdc = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        token::StellarAssetClient::new(&env, &usdc).mint(&owner, &1000);
+        client.deposit_treasury(&treasury_id, &owner, &1000i128, &Some(usdc.clone()));
+        client.deposit_treasury(&treasury_id, &owner, &1000i128, &None);
+
+        // Each token's total is below the high-value threshold; their sum is not
+        let recipient = Address::generate(&env);
+        let mut legs = Vec::new(&env);
+        legs.push_back(BatchLeg {
+            recipient: recipient.clone(),
+            token: Some(usdc.clone()),
+            amount: 700,
+        });
+        legs.push_back(BatchLeg {
+            recipient: recipient.clone(),
+            token: None,
+            amount: 600,
+        });
+
+        let tx_id = client.propose_batch_withdrawal(
+            &treasury_id,
+            &signer1,
+            &legs,
+            &String::from_str(&env, "small payouts"),
+        );
+        let tx = find_tx(&client, treasury_id, tx_id);
+        assert_eq!(tx.amount, 700);
+        assert_eq!(tx.token, Some(usdc.clone()));
+
+        // A low-value batch needs one approval, not all three signers
+        client.approve_transaction(&tx_id, &signer2);
+        assert_eq!(
+            find_tx(&client, treasury_id, tx_id).status,
+            TransactionStatus::Approved
+        );
+        assert!(client.execute_transaction(&tx_id, &owner));
+        assert_eq!(
+            token::TokenClient::new(&env, &usdc).balance(&recipient),
+            700
+        );
+        assert_eq!(client.get_treasury_balance(&treasury_id, &None), 400);
+    }
+
+    #[test]
+    #[should_panic(expected = "budget exceeded")]
+    fn test_batch_withdrawal_budget_checked_against_total() {
+        let env = setup_env();
+        let owner = Address::generate(&env);
+
+        set_ledger_timestamp(&env, 1000);
+        env.mock_all_auths();
+
+        let contract_id = register_and_init_contract(&env);
+        let client = StellarGuildsContractClient::new(&env, &contract_id);
+
+        let guild_id = setup_guild(&client, &env, &owner);
+        let (treasury_id, owner, signer1, signer2) = create_treasury(&env, &client, guild_id);
+        client.deposit_treasury(&treasury_id, &owner, &5000i128, &None);
+        let category = String::from_str(&env, "withdrawal");
+        client.set_budget(&treasury_id, &category, &1000i128, &3600u64, &owner);
+
+        // Each leg fits the budget on its own, the total does not
+        let mut legs = Vec::new(&env);
+        for _ in 0..2 {
+            legs.push_back(BatchLeg {
+                recipient: Address::generate(&env),
+                token: None,
+                amount: 600,
+            });
+        }
+        let tx_id = client.propose_batch_withdrawal(
+            &treasury_id,
+            &signer1,
+            &legs,
+            &String::from_str(&env, "bonuses"),
+        );
+        client.approve_transaction(&tx_id, &signer2);
+        client.execute_transaction(&tx_id, &owner);
+    }
+
     #[test]
     fn test_treasury_type_helpers() {
         let env = setup_env();
@@ -7,6 +7,7 @@
 pub enum TreasuryError {
     BudgetExceeded = 1,
     AllowanceExceeded = 2,
+    BudgetOverspendNotApproved = 3,
 }
 
 #[contracttype]
@@ -17,6 +18,11 @@ pub enum TransactionType {
     BountyFunding,
     MilestonePayment,
     AllowanceGrant,
+    Swap,
+    VaultDeposit,
+    VaultWithdrawal,
+    BatchWithdrawal,
+    SwapperChange,
 }
 
 #[contracttype]
@@ -27,6 +33,38 @@ pub enum TransactionStatus {
     Executed,
     Rejected,
     Expired,
+    Cancelled,
+}
+
+/// What a velocity limit does when a withdrawal would exceed it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum VelocityAction {
+    /// Require every treasury signer to approve.
+    Escalate,
+    /// Reject the withdrawal outright.
+    Block,
+}
+
+/// What a velocity limit's rolling window is keyed by.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum VelocityScope {
+    /// All outflows from the treasury.
+    Treasury,
+    /// Outflows proposed by the same signer.
+    Signer,
+    /// Outflows paid to the same recipient.
+    Recipient,
+}
+
+/// Outcome of the velocity check recorded on a transaction.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum VelocityDecision {
+    WithinLimits,
+    Escalated,
+    Blocked,
 }
 
 #[contracttype]
@@ -44,6 +82,7 @@ pub struct Transaction {
     pub created_at: u64,
     pub expires_at: u64,
     pub reason: String,
+    pub velocity_decision: VelocityDecision,
 }
 
 #[contracttype]
@@ -62,6 +101,21 @@ pub struct Treasury {
     pub paused: bool,
 }
 
+/// How a budget treats spending past its allocation.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BudgetMode {
+    /// Overspending fails with `TreasuryError::BudgetExceeded`.
+    Hard,
+    /// Overspending is allowed once every treasury signer has approved.
+    Soft,
+}
+
+/// A spending budget for one category. Spend in a category also counts
+/// against its `parent` and every ancestor above it.
+///
+/// When a period ends, unused allocation carries into the next period as
+/// `carried_over`, up to `carry_over_cap` (0 disables carry-over).
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Budget {
@@ -71,6 +125,10 @@ pub struct Budget {
     pub spent_amount: i128,
     pub period_seconds: u64,
     pub period_start: u64,
+    pub carried_over: i128,
+    pub carry_over_cap: i128,
+    pub parent: Option<String>,
+    pub mode: BudgetMode,
 }
 
 #[contracttype]
@@ -85,6 +143,135 @@ pub struct Allowance {
     pub period_start: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VelocityLimit {
+    pub treasury_id: u64,
+    pub scope: VelocityScope,
+    pub max_amount: i128,
+    /// Token the limit counts outflows of (None for native XLM)
+    pub token: Option<Address>,
+    pub window_seconds: u64,
+    pub action: VelocityAction,
+}
+
+/// An executed outflow, kept for rolling-window velocity checks.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VelocitySpend {
+    pub timestamp: u64,
+    pub proposer: Address,
+    pub recipient: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ScheduleStatus {
+    Pending,
+    Active,
+    Completed,
+    Cancelled,
+}
+
+/// Terms of a recurring treasury payment.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduleTerms {
+    pub recipient: Address,
+    pub amount: i128,
+    pub token: Option<Address>,
+    pub interval_seconds: u64,
+    pub start_at: u64,
+    pub end_at: Option<u64>,
+    pub max_occurrences: Option<u32>,
+    /// Budget category each payment is charged against.
+    pub category: String,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduledPayment {
+    pub id: u64,
+    pub treasury_id: u64,
+    pub terms: ScheduleTerms,
+    pub proposer: Address,
+    pub approvals: Vec<Address>,
+    pub status: ScheduleStatus,
+    pub occurrences: u32,
+    pub next_due: u64,
+    pub created_at: u64,
+}
+
+/// Output side of a `Swap` transaction. The input token and amount are the
+/// transaction's `token` and `amount`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwapOrder {
+    pub tx_id: u64,
+    /// Swapper in place when the swap was proposed.
+    pub swapper: Address,
+    pub to_token: Address,
+    pub min_amount_out: i128,
+    pub amount_out: i128,
+}
+
+/// One payment of a batch withdrawal. `token` is `None` for XLM.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchLeg {
+    pub recipient: Address,
+    pub token: Option<Address>,
+    pub amount: i128,
+}
+
+/// Legs of a `BatchWithdrawal` transaction, whose `amount` and `token` are
+/// those of its largest per-token total.
+/// Once executed, `leg_tx_ids` holds the executed `Withdrawal` transaction
+/// recorded for each leg, in order.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchWithdrawal {
+    pub tx_id: u64,
+    pub legs: Vec<BatchLeg>,
+    pub leg_tx_ids: Vec<u64>,
+}
+
+/// A signer's vote to reject a pending transaction.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransactionRejection {
+    pub signer: Address,
+    pub reason: String,
+    pub timestamp: u64,
+}
+
+/// A whitelisted yield vault and the treasury's principal deployed in it.
+///
+/// `max_allocation_bps` caps the principal as a fraction of the treasury's
+/// total holdings of `token` (liquid plus deployed). A vault removed from the
+/// whitelist keeps its position so funds can still be recalled.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VaultAllocation {
+    pub vault: Address,
+    pub token: Address,
+    pub max_allocation_bps: u32,
+    pub whitelisted: bool,
+    pub principal: i128,
+}
+
+/// A token balance split into funds held by the treasury and funds deployed
+/// to yield vaults. `deployed` is the principal at cost. `committed` is the
+/// part of `liquid` reserved for milestone projects; the rest is available.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasuryBalance {
+    pub liquid: i128,
+    pub deployed: i128,
+    pub committed: i128,
+}
+
 // Events
 
 #[contracttype]
@@ -123,6 +310,27 @@ pub struct TransactionApprovedEvent {
     pub approver: Address,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchWithdrawalProposedEvent {
+    pub treasury_id: u64,
+    pub tx_id: u64,
+    pub proposer: Address,
+    pub leg_count: u32,
+    /// Total of the legs in each token
+    pub token_totals: Map<Option<Address>, i128>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransactionRejectedEvent {
+    pub treasury_id: u64,
+    pub tx_id: u64,
+    pub signer: Address,
+    pub reason: String,
+    pub status: TransactionStatus,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct TransactionExecutedEvent {
@@ -139,6 +347,16 @@ pub struct BudgetUpdatedEvent {
     pub period_seconds: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BudgetConfiguredEvent {
+    pub treasury_id: u64,
+    pub category: String,
+    pub carry_over_cap: i128,
+    pub parent: Option<String>,
+    pub mode: BudgetMode,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct AllowanceGrantedEvent {
@@ -149,6 +367,102 @@ pub struct AllowanceGrantedEvent {
     pub period_seconds: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VelocityLimitUpdatedEvent {
+    pub treasury_id: u64,
+    pub scope: VelocityScope,
+    pub max_amount: i128,
+    pub token: Option<Address>,
+    pub window_seconds: u64,
+    pub action: VelocityAction,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduleStatusEvent {
+    pub treasury_id: u64,
+    pub schedule_id: u64,
+    pub status: ScheduleStatus,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduledPaymentEvent {
+    pub treasury_id: u64,
+    pub schedule_id: u64,
+    pub tx_id: u64,
+    pub occurrence: u32,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduledPaymentFailedEvent {
+    pub treasury_id: u64,
+    pub schedule_id: u64,
+    pub reason: String,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwapperUpdatedEvent {
+    pub treasury_id: u64,
+    pub swapper: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwapperChangeProposedEvent {
+    pub treasury_id: u64,
+    pub tx_id: u64,
+    pub proposer: Address,
+    pub swapper: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwapProposedEvent {
+    pub treasury_id: u64,
+    pub tx_id: u64,
+    pub proposer: Address,
+    pub from_token: Address,
+    pub to_token: Address,
+    pub amount_in: i128,
+    pub min_amount_out: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwapExecutedEvent {
+    pub treasury_id: u64,
+    pub tx_id: u64,
+    pub from_token: Address,
+    pub to_token: Address,
+    pub amount_in: i128,
+    pub amount_out: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VaultAllocationUpdatedEvent {
+    pub treasury_id: u64,
+    pub vault: Address,
+    pub token: Address,
+    pub max_allocation_bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VaultMovementEvent {
+    pub treasury_id: u64,
+    pub tx_id: u64,
+    pub vault: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub principal: i128,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct EmergencyPauseEvent {
@@ -0,0 +1,328 @@
+use soroban_sdk::{token::Client as TokenClient, Address, Env, String, Vec};
+
+use crate::events::emit::emit_event;
+use crate::events::topics::{ACT_EXECUTED, ACT_PROPOSED, ACT_UPDATED, MOD_TREASURY};
+use crate::interfaces::yield_vault::YieldVaultClient;
+use crate::treasury::commitment::ensure_uncommitted;
+use crate::treasury::multisig::{assert_signer, TX_EXPIRY_SECONDS};
+use crate::treasury::storage::{
+    get_committed, get_next_tx_id, get_treasury, get_vault_allocation, get_vault_allocations,
+    get_vault_total_principal, set_vault_total_principal, store_transaction,
+    store_vault_allocation,
+};
+use crate::treasury::types::{
+    Transaction, TransactionStatus, TransactionType, Treasury, TreasuryBalance, VaultAllocation,
+    VaultAllocationUpdatedEvent, VaultMovementEvent, VelocityDecision,
+};
+
+const BPS_DENOMINATOR: i128 = 10_000;
+
+/// Whitelist a yield vault for one token, or update its allocation cap. A cap
+/// of zero removes the vault from the whitelist; funds already deployed can
+/// still be recalled. Only the treasury owner may call.
+pub fn set_vault_allocation(
+    env: &Env,
+    treasury_id: u64,
+    owner: Address,
+    vault: Address,
+    token: Address,
+    max_allocation_bps: u32,
+) -> bool {
+    let treasury = get_treasury(env, treasury_id).expect("treasury not found");
+    if treasury.owner != owner {
+        panic!("only owner can manage vaults");
+    }
+    owner.require_auth();
+
+    if max_allocation_bps as i128 > BPS_DENOMINATOR {
+        panic!("invalid allocation cap");
+    }
+
+    let allocation = match get_vault_allocation(env, treasury_id, &vault) {
+        Some(mut existing) => {
+            if existing.token != token {
+                panic!("vault token cannot change");
+            }
+            existing.max_allocation_bps = max_allocation_bps;
+            existing.whitelisted = max_allocation_bps > 0;
+            existing
+        }
+        None => VaultAllocation {
+            vault: vault.clone(),
+            token: token.clone(),
+            max_allocation_bps,
+            whitelisted: max_allocation_bps > 0,
+            principal: 0,
+        },
+    };
+    store_vault_allocation(env, treasury_id, &allocation);
+
+    let event = VaultAllocationUpdatedEvent {
+        treasury_id,
+        vault,
+        token,
+        max_allocation_bps,
+    };
+    emit_event(env, MOD_TREASURY, ACT_UPDATED, event);
+
+    true
+}
+
+/// Propose deploying `amount` of a vault's token into that vault. Needs the
+/// treasury's full approval threshold.
+pub fn propose_vault_deposit(
+    env: &Env,
+    treasury_id: u64,
+    proposer: Address,
+    vault: Address,
+    amount: i128,
+    reason: String,
+) -> u64 {
+    let treasury = get_treasury(env, treasury_id).expect("treasury not found");
+    let allocation = get_vault_allocation(env, treasury_id, &vault).expect("vault not found");
+    if !allocation.whitelisted {
+        panic!("vault not whitelisted");
+    }
+    ensure_within_cap(&treasury, &allocation, amount);
+
+    propose(
+        env,
+        &treasury,
+        proposer,
+        TransactionType::VaultDeposit,
+        allocation,
+        amount,
+        reason,
+    )
+}
+
+/// Propose recalling `amount` of a treasury's funds from a vault. Needs the
+/// treasury's full approval threshold.
+pub fn propose_vault_withdrawal(
+    env: &Env,
+    treasury_id: u64,
+    proposer: Address,
+    vault: Address,
+    amount: i128,
+    reason: String,
+) -> u64 {
+    let treasury = get_treasury(env, treasury_id).expect("treasury not found");
+    let allocation = get_vault_allocation(env, treasury_id, &vault).expect("vault not found");
+    if allocation.principal == 0 {
+        panic!("nothing deployed to vault");
+    }
+
+    propose(
+        env,
+        &treasury,
+        proposer,
+        TransactionType::VaultWithdrawal,
+        allocation,
+        amount,
+        reason,
+    )
+}
+
+/// Move an approved deposit from the treasury's liquid balance into its vault.
+pub fn execute_vault_deposit(env: &Env, treasury: &mut Treasury, tx: &Transaction) {
+    let vault = tx.recipient.clone().expect("vault required");
+    let mut allocation = get_vault_allocation(env, treasury.id, &vault).expect("vault not found");
+    if !allocation.whitelisted {
+        panic!("vault not whitelisted");
+    }
+    ensure_within_cap(treasury, &allocation, tx.amount);
+    ensure_uncommitted(env, treasury, &tx.token, tx.amount);
+
+    let liquid = treasury
+        .token_balances
+        .get(allocation.token.clone())
+        .unwrap_or(0);
+    if liquid < tx.amount {
+        panic!("insufficient treasury balance");
+    }
+
+    TokenClient::new(env, &allocation.token).transfer(
+        &env.current_contract_address(),
+        &vault,
+        &tx.amount,
+    );
+    YieldVaultClient::new(env, &vault).deposit(&env.current_contract_address(), &tx.amount);
+
+    treasury
+        .token_balances
+        .set(allocation.token.clone(), liquid - tx.amount);
+    allocation.principal += tx.amount;
+    store_vault_allocation(env, treasury.id, &allocation);
+    set_vault_total_principal(
+        env,
+        &vault,
+        get_vault_total_principal(env, &vault) + tx.amount,
+    );
+
+    let event = VaultMovementEvent {
+        treasury_id: treasury.id,
+        tx_id: tx.id,
+        vault,
+        token: allocation.token,
+        amount: tx.amount,
+        principal: allocation.principal,
+    };
+    emit_event(env, MOD_TREASURY, ACT_EXECUTED, event);
+}
+
+/// Recall an approved amount from a vault back into the liquid balance.
+///
+/// The amount may include yield, up to the treasury's share of the vault's
+/// value. Principal is reduced in proportion to the share withdrawn. The
+/// liquid balance is credited with what this contract's token balance
+/// actually grew by, not what the vault reports.
+pub fn execute_vault_withdrawal(env: &Env, treasury: &mut Treasury, tx: &Transaction) {
+    let vault = tx.recipient.clone().expect("vault required");
+    let mut allocation = get_vault_allocation(env, treasury.id, &vault).expect("vault not found");
+
+    let value = position_value(env, &allocation);
+    if tx.amount > value {
+        panic!("insufficient vault balance");
+    }
+    let released = if tx.amount == value {
+        allocation.principal
+    } else {
+        tx.amount * allocation.principal / value
+    };
+
+    let this = env.current_contract_address();
+    let token = TokenClient::new(env, &allocation.token);
+    let before = token.balance(&this);
+    YieldVaultClient::new(env, &vault).withdraw(&this, &tx.amount);
+    let received = token.balance(&this) - before;
+
+    let liquid = treasury
+        .token_balances
+        .get(allocation.token.clone())
+        .unwrap_or(0);
+    treasury
+        .token_balances
+        .set(allocation.token.clone(), liquid + received);
+    allocation.principal -= released;
+    store_vault_allocation(env, treasury.id, &allocation);
+    set_vault_total_principal(
+        env,
+        &vault,
+        get_vault_total_principal(env, &vault) - released,
+    );
+
+    let event = VaultMovementEvent {
+        treasury_id: treasury.id,
+        tx_id: tx.id,
+        vault,
+        token: allocation.token,
+        amount: received,
+        principal: allocation.principal,
+    };
+    emit_event(env, MOD_TREASURY, ACT_EXECUTED, event);
+}
+
+/// Current value of a treasury's position, including accrued yield.
+pub fn position_value(env: &Env, allocation: &VaultAllocation) -> i128 {
+    let total = get_vault_total_principal(env, &allocation.vault);
+    if total <= 0 || allocation.principal <= 0 {
+        return 0;
+    }
+    let held =
+        YieldVaultClient::new(env, &allocation.vault).balance_of(&env.current_contract_address());
+    held * allocation.principal / total
+}
+
+/// Principal of `token` deployed across all of a treasury's vaults.
+pub fn deployed_balance(env: &Env, treasury_id: u64, token: &Address) -> i128 {
+    get_vault_allocations(env, treasury_id)
+        .values()
+        .iter()
+        .filter(|a| a.token == *token)
+        .map(|a| a.principal)
+        .sum()
+}
+
+/// Liquid, deployed and committed balances of a token. XLM is never deployed.
+pub fn get_balance_detail(env: &Env, treasury_id: u64, token: Option<Address>) -> TreasuryBalance {
+    let treasury = get_treasury(env, treasury_id).expect("treasury not found");
+    match token {
+        Some(ref token_addr) => TreasuryBalance {
+            liquid: treasury.token_balances.get(token_addr.clone()).unwrap_or(0),
+            deployed: deployed_balance(env, treasury_id, token_addr),
+            committed: get_committed(env, treasury_id, &token),
+        },
+        None => TreasuryBalance {
+            liquid: treasury.balance_xlm,
+            deployed: 0,
+            committed: get_committed(env, treasury_id, &None),
+        },
+    }
+}
+
+fn ensure_within_cap(treasury: &Treasury, allocation: &VaultAllocation, amount: i128) {
+    if amount <= 0 {
+        panic!("amount must be positive");
+    }
+    let liquid = treasury
+        .token_balances
+        .get(allocation.token.clone())
+        .unwrap_or(0);
+    let total = liquid + allocation.principal;
+    let cap = total * allocation.max_allocation_bps as i128 / BPS_DENOMINATOR;
+    if allocation.principal + amount > cap {
+        panic!("vault allocation cap exceeded");
+    }
+}
+
+fn propose(
+    env: &Env,
+    treasury: &Treasury,
+    proposer: Address,
+    tx_type: TransactionType,
+    allocation: VaultAllocation,
+    amount: i128,
+    reason: String,
+) -> u64 {
+    if amount <= 0 {
+        panic!("amount must be positive");
+    }
+    if treasury.paused {
+        panic!("treasury is paused");
+    }
+    assert_signer(env, treasury, &proposer);
+
+    let tx_id = get_next_tx_id(env);
+    let now = env.ledger().timestamp();
+    let mut approvals = Vec::new(env);
+    approvals.push_back(proposer.clone());
+
+    let tx = Transaction {
+        id: tx_id,
+        treasury_id: treasury.id,
+        tx_type,
+        amount,
+        token: Some(allocation.token.clone()),
+        recipient: Some(allocation.vault.clone()),
+        proposer: proposer.clone(),
+        approvals,
+        status: TransactionStatus::Pending,
+        created_at: now,
+        expires_at: now + TX_EXPIRY_SECONDS,
+        reason,
+        velocity_decision: VelocityDecision::WithinLimits,
+    };
+    store_transaction(env, &tx);
+
+    let event = VaultMovementEvent {
+        treasury_id: treasury.id,
+        tx_id,
+        vault: allocation.vault,
+        token: allocation.token,
+        amount,
+        principal: allocation.principal,
+    };
+    emit_event(env, MOD_TREASURY, ACT_PROPOSED, event);
+
+    tx_id
+}
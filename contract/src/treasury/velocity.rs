@@ -0,0 +1,189 @@
+use soroban_sdk::{Address, Env, Map, Vec};
+
+use crate::events::emit::emit_event;
+use crate::events::topics::{ACT_UPDATED, MOD_TREASURY};
+use crate::treasury::storage::{
+    get_treasury, get_velocity_limits, get_velocity_log, remove_velocity_limit,
+    store_velocity_limit, store_velocity_log,
+};
+use crate::treasury::types::{
+    VelocityAction, VelocityDecision, VelocityLimit, VelocityLimitUpdatedEvent, VelocityScope,
+    VelocitySpend,
+};
+
+/// Set the rolling-window velocity limit for one scope and token of a
+/// treasury. Only the treasury owner may call. A `max_amount` of zero removes
+/// the limit. Outflows are only tracked while the treasury has at least one
+/// limit set.
+#[allow(clippy::too_many_arguments)]
+pub fn set_velocity_limit(
+    env: &Env,
+    treasury_id: u64,
+    owner: Address,
+    scope: VelocityScope,
+    max_amount: i128,
+    token: Option<Address>,
+    window_seconds: u64,
+    action: VelocityAction,
+) -> bool {
+    let treasury = get_treasury(env, treasury_id).expect("treasury not found");
+    if treasury.owner != owner {
+        panic!("only owner can set velocity limits");
+    }
+    owner.require_auth();
+
+    if max_amount < 0 {
+        panic!("limit cannot be negative");
+    }
+
+    if max_amount == 0 {
+        remove_velocity_limit(env, treasury_id, &scope, &token);
+    } else {
+        if window_seconds == 0 {
+            panic!("invalid window");
+        }
+        store_velocity_limit(
+            env,
+            &VelocityLimit {
+                treasury_id,
+                scope: scope.clone(),
+                max_amount,
+                token: token.clone(),
+                window_seconds,
+                action: action.clone(),
+            },
+        );
+    }
+
+    let event = VelocityLimitUpdatedEvent {
+        treasury_id,
+        scope,
+        max_amount,
+        token,
+        window_seconds,
+        action,
+    };
+    emit_event(env, MOD_TREASURY, ACT_UPDATED, event);
+
+    true
+}
+
+/// Check an outflow against every velocity limit of the treasury in its token.
+///
+/// Each limit sums the executed outflows of its token in its scope over the
+/// trailing window and adds `amount`. Any exceeded `Block` limit blocks the
+/// outflow; otherwise any exceeded `Escalate` limit escalates it.
+pub fn evaluate_velocity(
+    env: &Env,
+    treasury_id: u64,
+    proposer: &Address,
+    recipient: &Address,
+    amount: i128,
+    token: &Option<Address>,
+) -> VelocityDecision {
+    let mut outflows = Map::new(env);
+    outflows.set(recipient.clone(), amount);
+    evaluate_outflows(env, treasury_id, proposer, token, &outflows)
+}
+
+/// Check a set of outflows of one token that execute together, keyed by
+/// recipient.
+///
+/// Treasury and signer limits see the combined total; recipient limits see
+/// the total going to each recipient.
+pub fn evaluate_outflows(
+    env: &Env,
+    treasury_id: u64,
+    proposer: &Address,
+    token: &Option<Address>,
+    outflows: &Map<Address, i128>,
+) -> VelocityDecision {
+    let mut limits = Vec::new(env);
+    for limit in get_velocity_limits(env, treasury_id).iter() {
+        if limit.token == *token {
+            limits.push_back(limit);
+        }
+    }
+    if limits.is_empty() {
+        return VelocityDecision::WithinLimits;
+    }
+
+    let now = env.ledger().timestamp();
+    let log = get_velocity_log(env, treasury_id, token);
+    let total: i128 = outflows.values().iter().sum();
+    let mut decision = VelocityDecision::WithinLimits;
+
+    for limit in limits.iter() {
+        let window_start = now.saturating_sub(limit.window_seconds);
+        let exceeded = match limit.scope {
+            VelocityScope::Treasury | VelocityScope::Signer => {
+                let mut spent: i128 = 0;
+                for entry in log.iter() {
+                    if entry.timestamp < window_start {
+                        continue;
+                    }
+                    if limit.scope == VelocityScope::Treasury || entry.proposer == *proposer {
+                        spent += entry.amount;
+                    }
+                }
+                spent + total > limit.max_amount
+            }
+            VelocityScope::Recipient => outflows.iter().any(|(recipient, amount)| {
+                let mut spent: i128 = 0;
+                for entry in log.iter() {
+                    if entry.timestamp >= window_start && entry.recipient == recipient {
+                        spent += entry.amount;
+                    }
+                }
+                spent + amount > limit.max_amount
+            }),
+        };
+
+        if exceeded {
+            match limit.action {
+                VelocityAction::Block => return VelocityDecision::Blocked,
+                VelocityAction::Escalate => decision = VelocityDecision::Escalated,
+            }
+        }
+    }
+
+    decision
+}
+
+/// Record an executed outflow in its token's log and drop entries older than
+/// the longest window of that token's limits.
+pub fn record_velocity_spend(
+    env: &Env,
+    treasury_id: u64,
+    proposer: &Address,
+    recipient: &Address,
+    amount: i128,
+    token: &Option<Address>,
+) {
+    let longest = get_velocity_limits(env, treasury_id)
+        .iter()
+        .filter(|l| l.token == *token)
+        .map(|l| l.window_seconds)
+        .max();
+    let longest = match longest {
+        Some(w) => w,
+        None => return,
+    };
+
+    let now = env.ledger().timestamp();
+    let window_start = now.saturating_sub(longest);
+
+    let mut kept = Vec::new(env);
+    for entry in get_velocity_log(env, treasury_id, token).iter() {
+        if entry.timestamp >= window_start {
+            kept.push_back(entry);
+        }
+    }
+    kept.push_back(VelocitySpend {
+        timestamp: now,
+        proposer: proposer.clone(),
+        recipient: recipient.clone(),
+        amount,
+    });
+    store_velocity_log(env, treasury_id, token, &kept);
+}